@@ -22,9 +22,8 @@ use bevy::prelude::*;
 use clap::Parser;
 use minion::game_logic::errors::{MinionError, MinionResult};
 use minion::map::{EnvironmentObject, MapDefinition};
-use minion::terrain_generation::is_suitable_for_spawning;
-use rand::{Rng, SeedableRng};
-use rand_pcg::Pcg64;
+use minion::terrain::biomes::ObjectRule;
+use minion::terrain::scatter::{ScatterConfig, ScatterRules, scatter_objects};
 
 #[derive(Parser)]
 #[command(name = "add_objects")]
@@ -47,10 +46,10 @@ struct Args {
     types: String,
 
     /// Number of objects to attempt to place
-    #[arg(long, default_value = "50")]
+    #[arg(long, default_value = "50", value_parser = clap::value_parser!(u32).range(1..))]
     count: u32,
 
-    /// Object density factor (0.0-1.0, affects placement success rate)
+    /// Object density factor (0.0-1.0, 1.0 packs objects at the minimum distance)
     #[arg(long, default_value = "0.1")]
     density: f32,
 
@@ -131,12 +130,6 @@ fn main() -> MinionResult<()> {
     let args = Args::parse();
 
     // Validate arguments
-    if args.count == 0 {
-        return Err(MinionError::InvalidMapData {
-            reason: "Count must be greater than 0".to_string(),
-        });
-    }
-
     if args.density < 0.0 || args.density > 1.0 {
         return Err(MinionError::InvalidMapData {
             reason: "Density must be between 0.0 and 1.0".to_string(),
@@ -181,94 +174,48 @@ fn main() -> MinionResult<()> {
         println!("Attempting to place {} objects", args.count);
     }
 
-    // Setup RNG
     let seed = args.seed.unwrap_or_else(|| rand::random());
-    let mut rng = Pcg64::seed_from_u64(seed as u64);
-
     if args.verbose {
         println!("Using seed: {}", seed);
     }
 
-    // Calculate terrain bounds
-    let terrain_width = map.terrain.width as f32 * map.terrain.scale;
-    let terrain_height = map.terrain.height as f32 * map.terrain.scale;
-
-    // Place objects
-    let mut new_objects = Vec::new();
-    let mut attempts = 0;
-    let max_attempts = args.count * 10; // Prevent infinite loops
-
-    while new_objects.len() < args.count as usize && attempts < max_attempts {
-        attempts += 1;
-
-        // Generate random position
-        let x = rng.gen_range(0.0..terrain_width);
-        let z = rng.gen_range(0.0..terrain_height);
-
-        // Get terrain height at this position
-        let y = map.get_height_at_world(x, z).unwrap_or(0.0);
-        let position = Vec3::new(x, y, z);
-
-        // Check if position is suitable (not too steep, etc.)
-        if !is_suitable_for_spawning(&map.terrain, x, z, 0.5) {
-            continue;
-        }
-
-        // Check density (probability of placement)
-        if rng.gen_range(0.0..1.0) > args.density {
-            continue;
-        }
-
-        // Check distance from existing objects
-        if !is_position_valid(position, &map.environment_objects, args.min_distance) {
-            continue;
-        }
-
-        // Check distance from new objects
-        if !is_position_valid(position, &new_objects, args.min_distance) {
-            continue;
-        }
-
-        // Choose object type
-        let object_type = object_types[rng.gen_range(0..object_types.len())].clone();
-
-        // Generate random scale
-        let scale_factor = rng.gen_range(min_scale..=max_scale);
-        let scale = Vec3::splat(scale_factor);
-
-        // Generate random rotation (only Y-axis for most objects)
-        let rotation = Vec3::new(0.0, rng.gen_range(0.0..std::f32::consts::TAU), 0.0);
-
-        // Create the object
-        let obj = EnvironmentObject::new(object_type, position, rotation, scale);
-        new_objects.push(obj);
+    // Full density packs objects as tightly as the spacing allows
+    let spacing = args.min_distance.max(0.1);
+    let rules: Vec<ObjectRule> = object_types
+        .iter()
+        .map(|object_type| {
+            ObjectRule::new(
+                object_type,
+                args.density * 100.0 / (spacing * spacing) / object_types.len() as f32,
+                spacing,
+            )
+            .with_scale(min_scale, max_scale)
+            .with_max_slope(0.5)
+        })
+        .collect();
+    let config = ScatterConfig {
+        seed: seed as u64,
+        ..Default::default()
+    };
 
-        if args.verbose && new_objects.len() % 10 == 0 {
-            println!("Placed {} objects so far...", new_objects.len());
-        }
-    }
+    let mut new_objects = scatter_objects(
+        &map.terrain,
+        ScatterRules::Uniform(&rules),
+        &config,
+        &map.environment_objects,
+    );
+    new_objects
+        .retain(|obj| is_position_valid(obj.position, &map.environment_objects, args.min_distance));
+    new_objects.truncate(args.count as usize);
 
     let placed_count = new_objects.len();
-    let success_rate = if attempts > 0 {
-        (placed_count as f32 / attempts as f32) * 100.0
-    } else {
-        0.0
-    };
 
     // Display results
     println!(
         "Object placement results: {} objects placed out of {} requested ({:.1}% success)",
         placed_count,
         args.count,
-        if args.count > 0 {
-            (placed_count as f32 / args.count as f32) * 100.0
-        } else {
-            0.0
-        }
-    );
-    println!(
-        "Placement attempts: {} (success rate: {:.1}%)",
-        attempts, success_rate
+        (placed_count as f32 / args.count as f32) * 100.0
     );
     println!(
         "Final object count: {} (was {})",
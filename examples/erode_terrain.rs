@@ -0,0 +1,193 @@
+//! Erode Terrain Utility
+//!
+//! This utility runs a particle-based hydraulic erosion pass over a map's
+//! heightmap, carving gullies and filling valleys with sediment. It can also
+//! export the sediment and water flow maps as grayscale images for planning
+//! rivers and biomes.
+//!
+//! # Example Usage
+//! ```bash
+//! # Erode with default settings
+//! cargo run --example erode_terrain -- --input mountains.bin --output eroded_mountains.bin
+//!
+//! # Heavier erosion that follows the slope less strictly
+//! cargo run --example erode_terrain -- --input hills.bin --output eroded_hills.bin --iterations 200000 --inertia 0.3
+//!
+//! # Export sediment and flow maps alongside the eroded map
+//! cargo run --example erode_terrain -- --input map.bin --output eroded.bin --sediment-map sediment.png --flow-map flow.png
+//!
+//! # Dry run to see effects without making changes
+//! cargo run --example erode_terrain -- --input map.bin --output test.bin --dry-run --verbose
+//! ```
+
+use clap::Parser;
+use image::{GrayImage, Luma};
+use minion::game_logic::errors::{MinionError, MinionResult};
+use minion::map::{MapDefinition, TerrainData};
+use minion::terrain_generation::{HydraulicErosionConfig, hydraulic_erosion};
+
+#[derive(Parser)]
+#[command(name = "erode_terrain")]
+#[command(about = "Apply hydraulic erosion to terrain heightmaps")]
+struct Args {
+    /// Input map file (in maps/ directory)
+    #[arg(long)]
+    input: String,
+
+    /// Output map file (in maps/ directory)
+    #[arg(long)]
+    output: String,
+
+    /// Number of water droplets to simulate
+    #[arg(long, default_value = "50000")]
+    iterations: u32,
+
+    /// How much droplets keep their direction instead of following the slope (0.0-1.0)
+    #[arg(long, default_value = "0.05")]
+    inertia: f32,
+
+    /// Sediment carried per unit of droplet speed and water
+    #[arg(long, default_value = "4.0")]
+    capacity: f32,
+
+    /// Fraction of droplet water lost per step (0.0-1.0)
+    #[arg(long, default_value = "0.01")]
+    evaporation: f32,
+
+    /// Random seed for droplet placement
+    #[arg(long, default_value = "42")]
+    seed: u64,
+
+    /// Write the deposited sediment map to this PNG file
+    #[arg(long)]
+    sediment_map: Option<String>,
+
+    /// Write the water flow map to this PNG file
+    #[arg(long)]
+    flow_map: Option<String>,
+
+    /// Show what would be changed without making changes
+    #[arg(long, default_value = "false")]
+    dry_run: bool,
+
+    /// Verbose output with statistics
+    #[arg(short, long, default_value = "false")]
+    verbose: bool,
+}
+
+/// Mean absolute height change between two terrains
+fn mean_height_change(before: &TerrainData, after: &TerrainData) -> f32 {
+    let total: f32 = before
+        .heights
+        .iter()
+        .zip(&after.heights)
+        .map(|(a, b)| (a - b).abs())
+        .sum();
+    total / before.heights.len().max(1) as f32
+}
+
+/// Scale a field to 0-255, log-compressed so faint trickles stay visible
+fn field_to_image(width: u32, height: u32, values: &[f32]) -> GrayImage {
+    let max = values.iter().copied().fold(0.0f32, f32::max);
+    let norm = (1.0 + max).ln().max(f32::EPSILON);
+
+    GrayImage::from_fn(width, height, |x, y| {
+        let value = values[(y * width + x) as usize].max(0.0);
+        Luma([((1.0 + value).ln() / norm * 255.0).round() as u8])
+    })
+}
+
+fn save_field(path: &str, width: u32, height: u32, values: &[f32]) -> MinionResult<()> {
+    field_to_image(width, height, values)
+        .save(path)
+        .map_err(|err| MinionError::InvalidMapData {
+            reason: format!("Failed to write image '{path}': {err}"),
+        })
+}
+
+fn main() -> MinionResult<()> {
+    let args = Args::parse();
+
+    let config = HydraulicErosionConfig {
+        iterations: args.iterations,
+        inertia: args.inertia,
+        sediment_capacity: args.capacity,
+        evaporation: args.evaporation,
+        ..Default::default()
+    };
+    config.validate()?;
+
+    // Load the input map
+    let mut map = MapDefinition::load_from_file(&args.input)?;
+
+    if args.verbose {
+        println!(
+            "Loaded map '{}' with terrain {}x{}",
+            map.name, map.terrain.width, map.terrain.height
+        );
+        println!(
+            "Simulating {} droplets (inertia {:.2}, capacity {:.2}, evaporation {:.3})",
+            config.iterations, config.inertia, config.sediment_capacity, config.evaporation
+        );
+    }
+
+    let mut eroded = map.terrain.clone();
+    let maps = hydraulic_erosion(&mut eroded, &config, args.seed);
+
+    if args.verbose {
+        let deposited: f32 = maps.sediment.iter().sum();
+        println!(
+            "Mean height change: {:.3}",
+            mean_height_change(&map.terrain, &eroded)
+        );
+        println!("Total sediment deposited: {deposited:.2}");
+    }
+
+    println!("Erosion completed: {} droplets", config.iterations);
+
+    if args.dry_run {
+        println!("Dry run - no changes made to files");
+        return Ok(());
+    }
+
+    if let Some(path) = &args.sediment_map {
+        save_field(path, maps.width, maps.height, &maps.sediment)?;
+        println!("Sediment map saved to: {path}");
+    }
+    if let Some(path) = &args.flow_map {
+        save_field(path, maps.width, maps.height, &maps.flow)?;
+        println!("Flow map saved to: {path}");
+    }
+
+    // Update map with eroded terrain
+    map.terrain = eroded;
+
+    // Save the eroded map
+    map.save_to_file(&args.output)?;
+
+    println!("Eroded map saved to: {}", args.output);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_to_image_normalizes() {
+        let image = field_to_image(2, 2, &[0.0, 1.0, 10.0, 100.0]);
+
+        assert_eq!(image.get_pixel(0, 0)[0], 0);
+        assert_eq!(image.get_pixel(1, 1)[0], 255);
+        assert!(image.get_pixel(1, 0)[0] < image.get_pixel(0, 1)[0]);
+    }
+
+    #[test]
+    fn test_mean_height_change() {
+        let before = TerrainData::new(2, 1, vec![0.0, 2.0], 1.0).unwrap();
+        let after = TerrainData::new(2, 1, vec![1.0, 1.0], 1.0).unwrap();
+
+        assert_eq!(mean_height_change(&before, &after), 1.0);
+    }
+}
@@ -1,6 +1,15 @@
 use clap::Parser;
 use minion::game_logic::errors::MinionResult;
 use minion::map::MapDefinition;
+use minion::pathfinding::grid_blocking::block_deep_water;
+use minion::pathfinding::{
+    BakedNavigation, NavigationGrid, PathfindingConfig, navigation_source_hash,
+};
+use minion::terrain::climate::ClimateConfig;
+use minion::terrain::settlements::SettlementConfig;
+use minion::terrain_generation::{
+    DifficultyConfig, DungeonConfig, HydraulicErosionConfig, HydrologyConfig, TerrainRecipe,
+};
 
 mod mapgen {
     pub mod cli_utils;
@@ -32,11 +41,16 @@ struct Args {
     #[arg(long, default_value = "0.0,1.0,0.0")]
     player_spawn: String,
 
-    /// Terrain type preset (flat, hills, mountains, valleys)
+    /// Terrain type preset (flat, hills, mountains, valleys, canyons, mesas, dunes)
+    /// or custom algorithm (perlin, ridged, fbm, simplex, billow, worley)
     #[arg(long, default_value = "flat")]
     terrain_type: String,
 
-    /// Random seed for reproducible generation
+    /// TOML terrain recipe evaluated instead of the terrain type
+    #[arg(long)]
+    recipe: Option<String>,
+
+    /// Root seed every generation stage derives its own seed from; random when omitted
     #[arg(long)]
     seed: Option<u32>,
 
@@ -52,6 +66,18 @@ struct Args {
     #[arg(long, default_value = "4")]
     octaves: u32,
 
+    /// Amplitude multiplier per octave for fbm, simplex, and billow terrain
+    #[arg(long, default_value = "0.5")]
+    persistence: f32,
+
+    /// Frequency multiplier per octave for fbm, simplex, and billow terrain
+    #[arg(long, default_value = "2.0")]
+    lacunarity: f32,
+
+    /// Domain warp strength in world units (0 disables warping)
+    #[arg(long, default_value = "0.0")]
+    warp: f32,
+
     /// Object density (0.0-1.0, higher = more objects)
     #[arg(long, default_value = "0.1")]
     objects: f32,
@@ -60,9 +86,9 @@ struct Args {
     #[arg(long, default_value = "tree,rock")]
     object_types: String,
 
-    /// Object scale range as min,max (e.g., "0.8,1.2")
-    #[arg(long, default_value = "0.8,1.2")]
-    object_scale: String,
+    /// Object scale range as min,max (e.g., "0.8,1.2"); each object rule keeps its own if omitted
+    #[arg(long)]
+    object_scale: Option<String>,
 
     /// Terrain scale (world units per grid cell - smaller = higher density)
     #[arg(long, default_value = "0.5")]
@@ -76,6 +102,10 @@ struct Args {
     #[arg(long, default_value = "6")]
     biome_regions: u32,
 
+    /// Assign biomes from temperature and humidity maps instead of Voronoi regions (only used with --biomes)
+    #[arg(long)]
+    climate: bool,
+
     /// Enable path network generation
     #[arg(long)]
     paths: bool,
@@ -87,6 +117,79 @@ struct Args {
     /// Number of trails per biome region (only used with --paths)
     #[arg(long, default_value = "2")]
     trails_per_biome: u32,
+
+    /// Flatten generated paths into the terrain and mark them as road surface (only used with --paths)
+    #[arg(long)]
+    carve_roads: bool,
+
+    /// Number of fenced towns to place on flat ground beside roads
+    #[arg(long, default_value = "0")]
+    towns: u32,
+
+    /// Number of camps to place on flat ground beside roads
+    #[arg(long, default_value = "0")]
+    camps: u32,
+
+    /// Walking distance around the player spawn kept free of enemy spawn zones
+    #[arg(long, default_value = "12.0")]
+    safe_radius: f32,
+
+    /// Comma-separated enemy types, weakest first; deeper zones unlock stronger types.
+    /// Each needs scenes in assets/enemies
+    #[arg(long, default_value = "dark-knight")]
+    enemy_types: String,
+
+    /// Hydraulic erosion droplets to simulate (0 disables erosion)
+    #[arg(long, default_value = "0")]
+    erosion_iterations: u32,
+
+    /// How much eroding droplets keep their direction (0.0-1.0)
+    #[arg(long, default_value = "0.05")]
+    erosion_inertia: f32,
+
+    /// Sediment carried per unit of droplet speed and water
+    #[arg(long, default_value = "4.0")]
+    erosion_capacity: f32,
+
+    /// Fraction of droplet water lost per step (0.0-1.0)
+    #[arg(long, default_value = "0.01")]
+    erosion_evaporation: f32,
+
+    /// Terrain filter applied after erosion, repeatable and run in order
+    /// (e.g. "thermal:iterations=50,talus=35", "terrace:steps=6,above=2", "curve:0/0,0.5/0.2,1/1")
+    #[arg(long = "filter")]
+    filters: Vec<String>,
+
+    /// Feature stamped onto the terrain after filtering, repeatable and run in order
+    /// (e.g. "crater@10,-5,8", "mesa@0,20,12:height=6,falloff=0.1", "ramp@5,5,6:rotation=90",
+    /// "assets/stamps/ridge.png@0,0,20:blend=add")
+    #[arg(long = "stamp")]
+    stamps: Vec<String>,
+
+    /// Number of rivers to trace from high ground (0 disables rivers)
+    #[arg(long, default_value = "0")]
+    rivers: u32,
+
+    /// Fill closed depressions with lakes
+    #[arg(long)]
+    lakes: bool,
+
+    /// Flood everything below this height as sea
+    #[arg(long)]
+    sea_level: Option<f32>,
+
+    /// Generate an indoor dungeon instead of outdoor terrain: "rooms" or "caves".
+    /// The size is in tiles of --scale world units; the player starts at the entrance
+    #[arg(long)]
+    dungeon: Option<String>,
+
+    /// Comma-separated agent radii to pre-inflate in the baked navigation data
+    #[arg(long, default_value = "0.3,0.5,1.0")]
+    nav_radii: String,
+
+    /// Skip baking navigation data into the map file
+    #[arg(long)]
+    no_nav_bake: bool,
 }
 
 fn validate_output_path(filename: &str) -> MinionResult<()> {
@@ -120,8 +223,25 @@ fn main() -> MinionResult<()> {
     let (width, height) = parse_size(&args.size)?;
     let player_spawn = parse_position(&args.player_spawn)?;
     let object_types = parse_object_types(&args.object_types);
-    let scale_range = parse_scale_range(&args.object_scale)?;
+    let enemy_types = parse_object_types(&args.enemy_types);
+    validate_enemy_types(&enemy_types)?;
+    let scale_range = args
+        .object_scale
+        .as_deref()
+        .map(parse_scale_range)
+        .transpose()?;
     let object_density = validate_density(args.objects);
+    let nav_radii = parse_radii(&args.nav_radii)?;
+    let filters = args
+        .filters
+        .iter()
+        .map(|spec| parse_filter(spec))
+        .collect::<MinionResult<Vec<_>>>()?;
+    let stamps = args
+        .stamps
+        .iter()
+        .map(|spec| parse_stamp(spec))
+        .collect::<MinionResult<Vec<_>>>()?;
     let output_filename = args
         .output
         .unwrap_or_else(|| format!("{name}.bin", name = args.name));
@@ -129,14 +249,65 @@ fn main() -> MinionResult<()> {
     // Validate output path early to catch obvious issues
     validate_output_path(&output_filename)?;
 
+    // One root seed drives every stage, report it so the map can be regenerated
+    let seed = args.seed.unwrap_or_else(rand::random);
+    println!("Map seed: {seed}");
+
     // Build terrain generator using builder pattern
     let generator = TerrainBuilder::new(args.terrain_type)
-        .seed(args.seed)
+        .seed(seed)
         .amplitude(args.amplitude)
         .frequency(args.frequency)
         .octaves(args.octaves)
+        .persistence(args.persistence)
+        .lacunarity(args.lacunarity)
+        .warp_strength(args.warp)
         .build()?;
 
+    let recipe = args
+        .recipe
+        .as_deref()
+        .map(|path| TerrainRecipe::load(std::path::Path::new(path)))
+        .transpose()?;
+
+    let erosion = if args.erosion_iterations > 0 {
+        let erosion = HydraulicErosionConfig {
+            iterations: args.erosion_iterations,
+            inertia: args.erosion_inertia,
+            sediment_capacity: args.erosion_capacity,
+            evaporation: args.erosion_evaporation,
+            ..Default::default()
+        };
+        erosion.validate()?;
+        Some(erosion)
+    } else {
+        None
+    };
+
+    let hydrology =
+        (args.rivers > 0 || args.lakes || args.sea_level.is_some()).then(|| HydrologyConfig {
+            rivers: args.rivers,
+            lakes: args.lakes,
+            sea_level: args.sea_level,
+            ..Default::default()
+        });
+
+    let settlements = (args.towns > 0 || args.camps > 0).then(|| SettlementConfig {
+        towns: args.towns,
+        camps: args.camps,
+        ..Default::default()
+    });
+
+    let dungeon = args
+        .dungeon
+        .as_deref()
+        .map(parse_dungeon_style)
+        .transpose()?
+        .map(|style| DungeonConfig {
+            style,
+            ..Default::default()
+        });
+
     // Create map generation config
     let config = MapGenerationConfig {
         name: args.name.clone(),
@@ -144,26 +315,67 @@ fn main() -> MinionResult<()> {
         height,
         player_spawn,
         generator,
+        recipe,
         object_density,
         object_types,
         scale_range,
         terrain_scale: args.scale,
         enable_biomes: args.biomes,
         biome_regions: args.biome_regions,
+        climate: args.climate.then(ClimateConfig::default),
         enable_paths: args.paths,
         main_roads: args.main_roads,
         trails_per_biome: args.trails_per_biome,
+        carve_roads: args.carve_roads,
+        erosion,
+        filters,
+        stamps,
+        hydrology,
+        settlements,
+        dungeon,
+        difficulty: DifficultyConfig::default()
+            .with_safe_radius(args.safe_radius)
+            .with_enemy_types(enemy_types),
     };
 
     // Generate the map
     let map = MapGenerator::generate(config)?;
 
+    // Bake navigation so the game can skip rebuilding the grid on load
+    let navigation = if args.no_nav_bake {
+        None
+    } else {
+        Some(bake_navigation(&map, &nav_radii)?)
+    };
+
     // Save and display results
-    map.save_to_file(&output_filename)?;
+    map.save_with_navigation(&output_filename, navigation.as_ref())?;
 
     print_map_summary(&map, &output_filename)
 }
 
+fn bake_navigation(map: &MapDefinition, agent_radii: &[f32]) -> MinionResult<BakedNavigation> {
+    let config = PathfindingConfig::default();
+    let source_hash = navigation_source_hash(
+        &map.terrain,
+        &map.environment_objects,
+        &map.water_bodies,
+        &config,
+    );
+    let mut nav_grid =
+        NavigationGrid::from_terrain_and_objects(&map.terrain, &map.environment_objects, config)?;
+    block_deep_water(&mut nav_grid, &map.terrain, &map.water_bodies);
+    let baked = nav_grid.bake(source_hash, agent_radii);
+
+    println!(
+        "Baked navigation grid {}x{} with {} inflation layers",
+        baked.width,
+        baked.height,
+        baked.inflations.len()
+    );
+    Ok(baked)
+}
+
 fn print_map_summary(map: &MapDefinition, output_filename: &str) -> MinionResult<()> {
     let maps_dir = MapDefinition::get_maps_dir()?;
     let full_path = maps_dir.join(output_filename);
@@ -194,8 +406,9 @@ fn print_map_summary(map: &MapDefinition, output_filename: &str) -> MinionResult
 
     for (i, zone) in map.enemy_zones.iter().enumerate() {
         println!(
-            "    Zone {}: center={}, radius={}, max_enemies={}, types={:?}",
+            "    Zone {}: tier={}, center={}, radius={}, max_enemies={}, types={:?}",
             i + 1,
+            zone.tier,
             zone.center,
             zone.radius,
             zone.max_enemies,
@@ -230,19 +443,41 @@ mod tests {
             output: Some("test_output.bin".to_string()),
             player_spawn: "16.0,1.0,16.0".to_string(),
             terrain_type: "flat".to_string(),
+            recipe: None,
             seed: Some(12345),
             amplitude: 10.0,
             frequency: 0.1,
             octaves: 4,
+            persistence: 0.5,
+            lacunarity: 2.0,
+            warp: 0.0,
             objects: 0.1,
             object_types: "tree,rock".to_string(),
-            object_scale: "0.8,1.2".to_string(),
+            object_scale: None,
             scale: 0.5,
             biomes: false,
             biome_regions: 6,
+            climate: false,
             paths: false,
             main_roads: 3,
             trails_per_biome: 2,
+            carve_roads: false,
+            towns: 0,
+            camps: 0,
+            safe_radius: 12.0,
+            enemy_types: "dark-knight".to_string(),
+            erosion_iterations: 0,
+            erosion_inertia: 0.05,
+            erosion_capacity: 4.0,
+            erosion_evaporation: 0.01,
+            filters: vec![],
+            stamps: vec![],
+            rivers: 0,
+            lakes: false,
+            sea_level: None,
+            dungeon: None,
+            nav_radii: "0.5".to_string(),
+            no_nav_bake: false,
         };
 
         // Test parsing
@@ -254,7 +489,7 @@ mod tests {
 
         // Test terrain builder
         let generator = TerrainBuilder::new(args.terrain_type.clone())
-            .seed(args.seed)
+            .seed(args.seed.unwrap_or_default())
             .build()
             .unwrap();
         assert_eq!(generator.seed, 12345);
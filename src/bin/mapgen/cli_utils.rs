@@ -1,5 +1,10 @@
 use bevy::prelude::*;
 use minion::game_logic::errors::{MinionError, MinionResult};
+use minion::game_logic::spawning::{get_enemies_dir, has_enemy_scenes};
+use minion::terrain_generation::{
+    BlendMode, DungeonStyle, FilterStep, HeightBand, StampImage, StampShape, StampSource,
+    TerrainFilter, TerrainStamp,
+};
 
 /// Generic parser for delimited strings that return tuples
 pub fn parse_delimited<T, const N: usize>(
@@ -84,6 +89,206 @@ pub fn parse_object_types(types_str: &str) -> Vec<String> {
         .collect()
 }
 
+/// Parse comma-separated agent radii for navigation baking
+pub fn parse_radii(radii_str: &str) -> MinionResult<Vec<f32>> {
+    radii_str
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| match s.parse::<f32>() {
+            Ok(radius) if radius >= 0.0 => Ok(radius),
+            _ => Err(MinionError::InvalidMapData {
+                reason: format!("Invalid agent radius: '{s}'"),
+            }),
+        })
+        .collect()
+}
+
+/// Parse a terrain filter spec such as "terrace:steps=6,sharpness=0.8,above=5".
+///
+/// Kinds are thermal, terrace, clamp, and curve. Curve points are written as
+/// "curve:0/0,0.5/0.2,1/1". The optional `above`, `below`, and `falloff` keys
+/// restrict any filter to a height band.
+pub fn parse_filter(spec: &str) -> MinionResult<FilterStep> {
+    let invalid = |reason: String| MinionError::InvalidMapData { reason };
+    let (kind, params) = spec.split_once(':').unwrap_or((spec, ""));
+
+    let mut values = std::collections::HashMap::new();
+    let mut points = Vec::new();
+    for param in params.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        if let Some((input, output)) = param.split_once('/') {
+            let parse = |value: &str| {
+                value
+                    .trim()
+                    .parse::<f32>()
+                    .map_err(|_| invalid(format!("Invalid curve point '{param}'")))
+            };
+            points.push([parse(input)?, parse(output)?]);
+        } else if let Some((key, value)) = param.split_once('=') {
+            let value: f32 = value
+                .trim()
+                .parse()
+                .map_err(|_| invalid(format!("Invalid value for '{key}' in filter '{spec}'")))?;
+            values.insert(key.trim().to_string(), value);
+        } else {
+            return Err(invalid(format!(
+                "Invalid parameter '{param}' in filter '{spec}'"
+            )));
+        }
+    }
+
+    let mut take = |key: &str| values.remove(key);
+    let band = match (take("above"), take("below")) {
+        (None, None) => None,
+        (min, max) => Some(HeightBand {
+            min: min.unwrap_or(f32::MIN),
+            max: max.unwrap_or(f32::MAX),
+            falloff: take("falloff").unwrap_or(0.0),
+        }),
+    };
+
+    let filter = match kind.trim() {
+        "thermal" => TerrainFilter::Thermal {
+            iterations: take("iterations").unwrap_or(50.0) as u32,
+            talus_angle: take("talus").unwrap_or(35.0),
+            strength: take("strength").unwrap_or(0.5),
+        },
+        "terrace" => TerrainFilter::Terrace {
+            steps: take("steps").unwrap_or(6.0) as u32,
+            sharpness: take("sharpness").unwrap_or(0.7),
+        },
+        "clamp" => TerrainFilter::Clamp {
+            min: take("min").unwrap_or(f32::MIN),
+            max: take("max").unwrap_or(f32::MAX),
+        },
+        "curve" => TerrainFilter::Curve {
+            points: std::mem::take(&mut points),
+        },
+        other => {
+            return Err(invalid(format!(
+                "Unknown filter '{other}'. Available: thermal, terrace, clamp, curve"
+            )));
+        }
+    };
+
+    if let Some(key) = values.keys().next() {
+        return Err(invalid(format!(
+            "Unknown parameter '{key}' in filter '{spec}'"
+        )));
+    }
+    if !points.is_empty() {
+        return Err(invalid(format!(
+            "Only curve filters take points, got '{spec}'"
+        )));
+    }
+    filter.validate()?;
+
+    Ok(FilterStep { filter, mask: band })
+}
+
+/// Parse a stamp spec `type@x,z,radius[:key=value,...]`.
+///
+/// The type is a shape name or the path of a grayscale image. Optional keys
+/// are `height`, `blend`, `falloff`, and `rotation` (degrees).
+pub fn parse_stamp(spec: &str) -> MinionResult<TerrainStamp> {
+    let invalid = |reason: String| MinionError::InvalidMapData { reason };
+    let (kind, placement) = spec
+        .rsplit_once('@')
+        .ok_or_else(|| invalid(format!("Invalid stamp '{spec}'. Expected type@x,z,radius")))?;
+    let (position, params) = placement.split_once(':').unwrap_or((placement, ""));
+    let [x, z, radius] =
+        parse_delimited::<f32, 3>(position, ',', "stamp position", |s| s.trim().parse())?;
+
+    let source = match kind.trim() {
+        "cone" => StampSource::Shape(StampShape::Cone),
+        "dome" => StampSource::Shape(StampShape::Dome),
+        "plateau" => StampSource::Shape(StampShape::Plateau),
+        "crater" => StampSource::Shape(StampShape::Crater),
+        "mesa" => StampSource::Shape(StampShape::Mesa),
+        "volcano" => StampSource::Shape(StampShape::Volcano),
+        "arena" => StampSource::Shape(StampShape::Arena),
+        "ramp" => StampSource::Ramp,
+        path if path.ends_with(".png") || path.ends_with(".jpg") || path.ends_with(".jpeg") => {
+            StampSource::Image(StampImage::load(std::path::Path::new(path))?)
+        }
+        other => {
+            return Err(invalid(format!(
+                "Unknown stamp '{other}'. Available: cone, dome, plateau, crater, mesa, \
+                 volcano, arena, ramp, or a .png/.jpg image"
+            )));
+        }
+    };
+
+    let mut stamp = TerrainStamp::new(source, x, z, radius);
+    for param in params.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let (key, value) = param
+            .split_once('=')
+            .ok_or_else(|| invalid(format!("Invalid parameter '{param}' in stamp '{spec}'")))?;
+        let number = || {
+            value
+                .trim()
+                .parse::<f32>()
+                .map_err(|_| invalid(format!("Invalid value for '{key}' in stamp '{spec}'")))
+        };
+        match key.trim() {
+            "height" => stamp.height = number()?,
+            "falloff" => stamp.falloff = number()?,
+            "rotation" => stamp.rotation = number()?,
+            "blend" => stamp.blend = parse_blend_mode(value)?,
+            other => {
+                return Err(invalid(format!(
+                    "Unknown parameter '{other}' in stamp '{spec}'"
+                )));
+            }
+        }
+    }
+    stamp.validate()?;
+
+    Ok(stamp)
+}
+
+/// Parse a blend mode name
+pub fn parse_blend_mode(blend: &str) -> MinionResult<BlendMode> {
+    match blend.trim() {
+        "add" => Ok(BlendMode::Add),
+        "subtract" => Ok(BlendMode::Subtract),
+        "multiply" => Ok(BlendMode::Multiply),
+        "max" => Ok(BlendMode::Max),
+        "min" => Ok(BlendMode::Min),
+        "replace" => Ok(BlendMode::Replace),
+        other => Err(MinionError::InvalidMapData {
+            reason: format!(
+                "Unknown blend mode '{other}'. Available: add, subtract, multiply, max, min, replace"
+            ),
+        }),
+    }
+}
+
+/// Parse a dungeon style name
+pub fn parse_dungeon_style(style: &str) -> MinionResult<DungeonStyle> {
+    match style.trim() {
+        "rooms" => Ok(DungeonStyle::Rooms),
+        "caves" => Ok(DungeonStyle::Caves),
+        other => Err(MinionError::InvalidMapData {
+            reason: format!("Unknown dungeon style '{other}'. Available: rooms, caves"),
+        }),
+    }
+}
+
+/// Check that every enemy type has scenes in `assets/enemies`
+pub fn validate_enemy_types(enemy_types: &[String]) -> MinionResult<()> {
+    let dir = get_enemies_dir()?;
+    match enemy_types
+        .iter()
+        .find(|enemy_type| !has_enemy_scenes(&dir, enemy_type))
+    {
+        Some(missing) => Err(MinionError::InvalidConfig {
+            reason: format!("Enemy type '{missing}' has no scenes in {}", dir.display()),
+        }),
+        None => Ok(()),
+    }
+}
+
 /// Validate object density and clamp to valid range
 pub fn validate_density(density: f32) -> f32 {
     if !(0.0..=1.0).contains(&density) {
@@ -100,6 +305,12 @@ pub fn validate_density(density: f32) -> f32 {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_validate_enemy_types() {
+        assert!(validate_enemy_types(&["dark-knight".to_string()]).is_ok());
+        assert!(validate_enemy_types(&["dark-knight".to_string(), "ogre".to_string()]).is_err());
+    }
+
     #[test]
     fn test_parse_size() {
         assert_eq!(parse_size("64x64").unwrap(), (64, 64));
@@ -146,6 +357,77 @@ mod tests {
         assert_eq!(parse_object_types(""), Vec::<String>::new());
     }
 
+    #[test]
+    fn test_parse_radii() {
+        assert_eq!(parse_radii("0.3,0.5, 1.0").unwrap(), vec![0.3, 0.5, 1.0]);
+        assert_eq!(parse_radii("").unwrap(), Vec::<f32>::new());
+
+        assert!(parse_radii("0.5,abc").is_err());
+        assert!(parse_radii("-1.0").is_err());
+    }
+
+    #[test]
+    fn test_parse_filter() {
+        let step = parse_filter("terrace:steps=4,sharpness=0.9").unwrap();
+        assert_eq!(
+            step.filter,
+            TerrainFilter::Terrace {
+                steps: 4,
+                sharpness: 0.9
+            }
+        );
+        assert!(step.mask.is_none());
+
+        let step = parse_filter("thermal:iterations=20,above=5,falloff=2").unwrap();
+        assert!(matches!(
+            step.filter,
+            TerrainFilter::Thermal { iterations: 20, .. }
+        ));
+        let band = step.mask.unwrap();
+        assert_eq!((band.min, band.max, band.falloff), (5.0, f32::MAX, 2.0));
+
+        let step = parse_filter("curve:0/0, 0.5/0.2, 1/1").unwrap();
+        assert_eq!(
+            step.filter,
+            TerrainFilter::Curve {
+                points: vec![[0.0, 0.0], [0.5, 0.2], [1.0, 1.0]]
+            }
+        );
+
+        assert!(parse_filter("blur:radius=2").is_err());
+        assert!(parse_filter("clamp:min=1,bogus=2").is_err());
+        assert!(parse_filter("terrace:steps=0").is_err());
+        assert!(parse_filter("clamp:0/1").is_err());
+    }
+
+    #[test]
+    fn test_parse_stamp() {
+        let stamp = parse_stamp("crater@10,-5,8").unwrap();
+        assert_eq!(stamp.source, StampSource::Shape(StampShape::Crater));
+        assert_eq!((stamp.x, stamp.z, stamp.radius), (10.0, -5.0, 8.0));
+        assert_eq!(stamp.blend, BlendMode::Add);
+
+        let stamp = parse_stamp("ramp@0,0,6:height=3,blend=replace,rotation=45").unwrap();
+        assert_eq!(stamp.source, StampSource::Ramp);
+        assert_eq!((stamp.height, stamp.rotation), (3.0, 45.0));
+        assert_eq!(stamp.blend, BlendMode::Replace);
+
+        assert!(parse_stamp("crater").is_err());
+        assert!(parse_stamp("crater@1,2").is_err());
+        assert!(parse_stamp("canyon@0,0,5").is_err());
+        assert!(parse_stamp("mesa@0,0,-5").is_err());
+        assert!(parse_stamp("mesa@0,0,5:blend=screen").is_err());
+        assert!(parse_stamp("mesa@0,0,5:width=2").is_err());
+        assert!(parse_stamp("missing.png@0,0,5").is_err());
+    }
+
+    #[test]
+    fn test_parse_dungeon_style() {
+        assert_eq!(parse_dungeon_style("rooms").unwrap(), DungeonStyle::Rooms);
+        assert_eq!(parse_dungeon_style(" caves").unwrap(), DungeonStyle::Caves);
+        assert!(parse_dungeon_style("maze").is_err());
+    }
+
     #[test]
     fn test_validate_density() {
         assert_eq!(validate_density(0.5), 0.5);
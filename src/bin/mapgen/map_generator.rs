@@ -1,12 +1,22 @@
 use bevy::prelude::*;
 use minion::game_logic::errors::MinionResult;
-use minion::map::{EnvironmentObject, MapDefinition, SpawnZone, TerrainData};
+use minion::map::{EnvironmentObject, MapDefinition, SpawnZone, TerrainData, road_surface_map};
 use minion::terrain::biome_integration::BiomeIntegration;
-use minion::terrain::biomes::BiomeType;
+use minion::terrain::biomes::{BiomeMap, BiomeType, ObjectRule, create_default_biomes};
+use minion::terrain::climate::ClimateConfig;
+use minion::terrain::constants::DEFAULT_OBJECT_DENSITY;
 use minion::terrain::path_generator::PathGenerationConfig;
-use minion::terrain_generation::{TerrainGenerator, is_suitable_for_spawning};
-use rand::{Rng, SeedableRng};
-use rand_pcg::Pcg64;
+use minion::terrain::road_carving::{RoadCarvingConfig, carve_path_network};
+use minion::terrain::scatter::{ScatterConfig, ScatterRules, scatter_objects};
+use minion::terrain::settlements::{
+    Settlement, SettlementConfig, generate_settlements, in_safe_zone,
+};
+use minion::terrain_generation::{
+    DifficultyConfig, DungeonConfig, FilterStep, HydraulicErosionConfig, HydrologyConfig, MapSeed,
+    SeedStream, TerrainGenerator, TerrainRecipe, TerrainStamp, apply_filters, apply_stamps,
+    generate_difficulty_zones, generate_dungeon, generate_hydrology, hydraulic_erosion,
+};
+use std::collections::BTreeMap;
 
 pub struct MapGenerationConfig {
     pub name: String,
@@ -14,15 +24,36 @@ pub struct MapGenerationConfig {
     pub height: u32,
     pub player_spawn: Vec3,
     pub generator: TerrainGenerator,
+    /// Replaces the generator's algorithm; its seed still drives the recipe
+    pub recipe: Option<TerrainRecipe>,
     pub object_density: f32,
     pub object_types: Vec<String>,
-    pub scale_range: (f32, f32),
+    /// Overrides every object rule's scale range; rules keep their own when `None`
+    pub scale_range: Option<(f32, f32)>,
     pub terrain_scale: f32,
     pub enable_biomes: bool,
     pub biome_regions: u32,
+    /// Climate-driven biome assignment; Voronoi regions when `None`
+    pub climate: Option<ClimateConfig>,
     pub enable_paths: bool,
     pub main_roads: u32,
     pub trails_per_biome: u32,
+    /// Flatten paths into the terrain and record them as road surface
+    pub carve_roads: bool,
+    /// Hydraulic erosion pass applied after noise generation
+    pub erosion: Option<HydraulicErosionConfig>,
+    /// Heightmap filters applied in order after erosion
+    pub filters: Vec<FilterStep>,
+    /// Hand-placed features stamped in order after filtering
+    pub stamps: Vec<TerrainStamp>,
+    /// Rivers, lakes, and sea carved after filtering
+    pub hydrology: Option<HydrologyConfig>,
+    /// Towns and camps placed beside roads after carving
+    pub settlements: Option<SettlementConfig>,
+    /// Build an indoor dungeon instead of outdoor terrain
+    pub dungeon: Option<DungeonConfig>,
+    /// Safe radius, tiers, and enemy roster for spawn zones
+    pub difficulty: DifficultyConfig,
 }
 
 pub struct MapGenerator;
@@ -39,8 +70,13 @@ impl MapGenerator {
         Ok(Vec3::new(spawn_pos.x, terrain_height + 1.0, spawn_pos.z))
     }
 
+    /// Generate a map; every stage seeds from the generator seed through [`MapSeed`]
     pub fn generate(config: MapGenerationConfig) -> MinionResult<MapDefinition> {
         println!("Generating map: {name}", name = config.name);
+        if let Some(ref dungeon) = config.dungeon {
+            return Self::generate_dungeon_map(&config, dungeon);
+        }
+        let seeds = MapSeed(config.generator.seed);
         println!(
             "Terrain size: {width}x{height} grid cells",
             width = config.width,
@@ -53,15 +89,60 @@ impl MapGenerator {
         );
 
         // Generate terrain
-        let terrain =
-            config
+        let mut terrain = match config.recipe {
+            Some(ref recipe) => {
+                println!(
+                    "Evaluating terrain recipe with {} steps",
+                    recipe.steps.len()
+                );
+                recipe.generate(
+                    config.width,
+                    config.height,
+                    config.terrain_scale,
+                    config.generator.seed,
+                )?
+            }
+            None => config
                 .generator
-                .generate(config.width, config.height, config.terrain_scale)?;
+                .generate(config.width, config.height, config.terrain_scale)?,
+        };
         println!(
             "Generated terrain with {} height points",
             terrain.heights.len()
         );
 
+        let erosion_maps = config.erosion.as_ref().map(|erosion| {
+            println!(
+                "Applying hydraulic erosion with {} droplets",
+                erosion.iterations
+            );
+            hydraulic_erosion(&mut terrain, erosion, seeds.child(SeedStream::Erosion))
+        });
+
+        if !config.filters.is_empty() {
+            println!("Applying {} terrain filters", config.filters.len());
+            apply_filters(&mut terrain, &config.filters)?;
+        }
+
+        if !config.stamps.is_empty() {
+            println!("Applying {} terrain stamps", config.stamps.len());
+            apply_stamps(&mut terrain, &config.stamps)?;
+        }
+
+        let water_bodies = match config.hydrology {
+            Some(ref hydrology) => {
+                let bodies = generate_hydrology(
+                    &mut terrain,
+                    hydrology,
+                    seeds.child(SeedStream::Hydrology),
+                    erosion_maps.as_ref(),
+                );
+                println!("Generated {} water bodies", bodies.len());
+                bodies
+            }
+            None => Vec::new(),
+        };
+
         // Fix player spawn height to match terrain elevation
         let corrected_player_spawn = Self::correct_spawn_height(&terrain, config.player_spawn)?;
         println!(
@@ -70,19 +151,30 @@ impl MapGenerator {
         );
 
         // Generate biome data if enabled
-        let biome_data = if config.enable_biomes {
-            println!("Generating biome map with {} regions", config.biome_regions);
-            Some(BiomeIntegration::generate_biome_data_for_terrain(
-                &terrain,
-                config.generator.seed.wrapping_add(1337),
-                Some(config.biome_regions),
-            )?)
-        } else {
-            None
+        let biome_seed = seeds.child_u32(SeedStream::Biomes);
+        let biome_data = match (config.enable_biomes, config.climate.clone()) {
+            (false, _) => None,
+            (true, Some(climate)) => {
+                println!("Generating biome map from climate");
+                Some(BiomeIntegration::generate_climate_biome_data_for_terrain(
+                    &terrain,
+                    &water_bodies,
+                    biome_seed,
+                    climate,
+                )?)
+            }
+            (true, None) => {
+                println!("Generating biome map with {} regions", config.biome_regions);
+                Some(BiomeIntegration::generate_biome_data_for_terrain(
+                    &terrain,
+                    biome_seed,
+                    Some(config.biome_regions),
+                )?)
+            }
         };
 
         // Generate path network if enabled
-        let _path_network = if config.enable_paths {
+        let path_network = if config.enable_paths {
             println!(
                 "Generating path network with {} main roads, {} trails per biome",
                 config.main_roads, config.trails_per_biome
@@ -97,625 +189,462 @@ impl MapGenerator {
             Some(BiomeIntegration::generate_path_network(
                 &terrain,
                 biome_data.as_ref(),
-                config.generator.seed.wrapping_add(2022) as u64,
+                seeds.child(SeedStream::Paths),
                 Some(path_config),
             )?)
         } else {
             None
         };
 
-        // Generate spawn zones (biome-aware if biomes enabled)
-        let enemy_zones = if let Some(ref biome_data) = biome_data {
-            Self::generate_biome_aware_spawn_zones(
-                &terrain,
-                &biome_data.blend_map,
-                corrected_player_spawn,
-                5,
-            )?
-        } else {
-            Self::generate_spawn_zones(&terrain, corrected_player_spawn, 5)?
+        let road_surfaces = match path_network {
+            Some(ref network) if config.carve_roads => {
+                let roads =
+                    carve_path_network(&mut terrain, network, &RoadCarvingConfig::default());
+                println!("Carved {} roads into the terrain", roads.len());
+                roads
+            }
+            _ => Vec::new(),
+        };
+
+        let settlements = match config.settlements {
+            Some(ref settlement_config) => {
+                let settlements = generate_settlements(
+                    &terrain,
+                    path_network.as_ref(),
+                    &water_bodies,
+                    settlement_config,
+                    seeds.child(SeedStream::Settlements),
+                );
+                println!("Placed {} settlements", settlements.len());
+                settlements
+            }
+            None => Vec::new(),
         };
+
+        // Generate spawn zones, avoiding wet biomes and settlements
+        let enemy_zones = Self::generate_spawn_zones(
+            &terrain,
+            biome_data.as_ref().map(|data| &data.blend_map),
+            corrected_player_spawn,
+            &settlements,
+            &config.difficulty,
+            seeds.child(SeedStream::Spawns),
+        )?;
         println!(
             "Generated {count} enemy spawn zones",
             count = enemy_zones.len()
         );
 
         // Generate environment objects (biome-aware if biomes enabled)
-        let object_seed = config.generator.seed.wrapping_add(42);
-        let environment_objects = if let Some(ref biome_data) = biome_data {
-            Self::generate_biome_aware_objects(
-                &terrain,
-                &biome_data.blend_map,
-                corrected_player_spawn,
-                &enemy_zones,
-                config.object_density,
-                &config.object_types,
-                config.scale_range,
-                object_seed,
-            )?
-        } else {
-            Self::generate_objects(
-                &terrain,
-                corrected_player_spawn,
-                &enemy_zones,
-                config.object_density,
-                &config.object_types,
-                config.scale_range,
-                object_seed,
-            )?
-        };
+        let mut environment_objects = Self::generate_objects(
+            &terrain,
+            biome_data.as_ref().map(|data| &data.blend_map),
+            corrected_player_spawn,
+            &enemy_zones,
+            &settlements,
+            &config,
+        );
 
-        Ok(MapDefinition::new(
+        // Keep roads clear of props
+        if !road_surfaces.is_empty() {
+            let paved = road_surface_map(&terrain, &road_surfaces);
+            environment_objects.retain(|object| {
+                let (x, z) = minion::terrain::coordinates::world_to_grid(
+                    &terrain,
+                    object.position.x,
+                    object.position.z,
+                );
+                let index = (z.round() as u32).min(terrain.height - 1) * terrain.width
+                    + (x.round() as u32).min(terrain.width - 1);
+                paved[index as usize].is_none()
+            });
+        }
+        environment_objects.extend(settlements.into_iter().flat_map(|town| town.objects));
+
+        let mut map = MapDefinition::new(
             config.name,
             terrain,
             corrected_player_spawn,
             enemy_zones,
             environment_objects,
-        )?)
+        )?;
+        map.water_bodies = water_bodies;
+        map.road_surfaces = road_surfaces;
+        map.biome_map = biome_data.map(|data| data.blend_map);
+        Ok(map)
     }
 
+    /// Indoor level: carved floor plan, wall objects, and zones by depth
+    fn generate_dungeon_map(
+        config: &MapGenerationConfig,
+        dungeon: &DungeonConfig,
+    ) -> MinionResult<MapDefinition> {
+        println!(
+            "Generating {style:?} dungeon of {width}x{height} tiles (seed: {seed})",
+            style = dungeon.style,
+            width = config.width,
+            height = config.height,
+            seed = config.generator.seed
+        );
+        let map = generate_dungeon(
+            config.name.clone(),
+            config.width,
+            config.height,
+            config.terrain_scale,
+            dungeon,
+            &config.difficulty,
+            MapSeed(config.generator.seed).child(SeedStream::Dungeon),
+        )?;
+        println!(
+            "Placed {walls} wall segments and {zones} enemy spawn zones",
+            walls = map.environment_objects.len(),
+            zones = map.enemy_zones.len()
+        );
+        Ok(map)
+    }
+
+    /// Spawn zones graded by walking distance from the player spawn
     fn generate_spawn_zones(
         terrain: &TerrainData,
+        biome_map: Option<&BiomeMap>,
         player_spawn: Vec3,
-        num_zones: u32,
+        settlements: &[Settlement],
+        difficulty: &DifficultyConfig,
+        seed: u64,
     ) -> MinionResult<Vec<SpawnZone>> {
-        let mut zones = Vec::new();
-        let (base_radius, base_max_enemies, max_slope) = (3.0, 2, 0.3);
-        let mut attempts = 0;
-        const MAX_ATTEMPTS: u32 = 1000;
-
-        println!("Analyzing terrain for suitable spawn locations...");
-
-        while zones.len() < num_zones as usize && attempts < MAX_ATTEMPTS {
-            let i = zones.len() as u32;
-            let position = Self::calculate_ring_position(player_spawn, i);
-
-            if is_suitable_for_spawning(terrain, position.x, position.z, max_slope) {
-                let terrain_height = minion::terrain::coordinates::get_height_at_world_nearest(
-                    terrain, position.x, position.z,
-                )
-                .unwrap_or(0.0);
-
-                let final_center = Vec3::new(position.x, terrain_height + 1.0, position.z);
-                let radius = base_radius + (i % 3) as f32 * 0.5;
-                let max_enemies = base_max_enemies + (i % 3);
-
-                zones.push(SpawnZone::new(
-                    final_center,
-                    radius,
-                    max_enemies,
-                    vec!["dark-knight".to_string()],
-                )?);
-
-                println!(
-                    "Found suitable spawn location at ({:.1}, {:.1}, {:.1})",
-                    final_center.x, final_center.y, final_center.z
-                );
-            }
-            attempts += 1;
-        }
-
-        if zones.len() < num_zones as usize {
+        println!("Grading spawn locations by walking distance from the player spawn...");
+        let zones = generate_difficulty_zones(
+            terrain,
+            player_spawn,
+            difficulty,
+            seed,
+            |center, radius| {
+                // Avoid water and swamp biomes, and keep whole zones out of settlements
+                let wet = biome_map
+                    .and_then(|map| map.get_blend_at_world(center.x, center.z))
+                    .and_then(|blend| blend.dominant_biome())
+                    .is_some_and(|biome| matches!(biome, BiomeType::Ocean | BiomeType::Swamp));
+                !wet && !in_safe_zone(settlements, center, radius)
+            },
+        )?;
+
+        let requested: u32 = difficulty.tiers.iter().map(|tier| tier.zones).sum();
+        if zones.len() < requested as usize {
             println!(
                 "Warning: Only found {} suitable spawn locations out of {} requested",
                 zones.len(),
-                num_zones
+                requested
             );
         }
-
         Ok(zones)
     }
 
-    fn calculate_ring_position(player_spawn: Vec3, index: u32) -> Vec3 {
-        let angle = (index as f32 * 2.3) % (2.0 * std::f32::consts::PI);
-        let distance = 8.0 + (index % 4) as f32 * 2.0; // Reduced from 10.0 to ensure positions are within terrain
-        player_spawn + Vec3::new(angle.cos() * distance, 0.0, angle.sin() * distance)
-    }
-
+    /// Scatter environment objects with Poisson-disk sampling.
+    ///
+    /// Biome object rules decide what grows where when a biome map exists;
+    /// otherwise the requested types are spread uniformly using plains rules.
     fn generate_objects(
         terrain: &TerrainData,
+        biome_map: Option<&BiomeMap>,
         player_spawn: Vec3,
         enemy_zones: &[SpawnZone],
-        density: f32,
-        object_types: &[String],
-        scale_range: (f32, f32),
-        seed: u32,
-    ) -> MinionResult<Vec<EnvironmentObject>> {
-        if density <= 0.0 {
-            return Ok(Vec::new());
-        }
-
-        let mut rng = Pcg64::seed_from_u64(seed as u64);
-        let mut objects = Vec::new();
-
-        let terrain_area =
-            (terrain.width as f32 * terrain.scale) * (terrain.height as f32 * terrain.scale);
-        let max_objects = 200.min((terrain_area * density * 0.01) as u32);
-
-        let constraints = ObjectPlacementConstraints {
-            min_distance_from_spawn: 2.0,
-            min_distance_from_enemies: 2.0,
-            min_distance_between_objects: 1.5,
-            max_slope: 0.4,
-        };
-
-        println!("Placing up to {max_objects} environment objects (density: {density})...");
-
-        let mut attempts = 0;
-        let max_attempts = max_objects * 10;
-
-        while objects.len() < max_objects as usize && attempts < max_attempts {
-            if let Some(obj) = Self::try_place_object(
-                terrain,
-                player_spawn,
-                enemy_zones,
-                &objects,
-                object_types,
-                scale_range,
-                &constraints,
-                &mut rng,
-            )? {
-                objects.push(obj);
-            }
-            attempts += 1;
-        }
-
-        println!(
-            "Placed {} environment objects (attempted {} placements)",
-            objects.len(),
-            attempts
+        settlements: &[Settlement],
+        config: &MapGenerationConfig,
+    ) -> Vec<EnvironmentObject> {
+        let mut exclusions = vec![(player_spawn, 3.0)];
+        exclusions.extend(
+            enemy_zones
+                .iter()
+                .map(|zone| (zone.center, zone.radius + 2.0)),
         );
-
-        Ok(objects)
-    }
-
-    fn try_place_object(
-        terrain: &TerrainData,
-        player_spawn: Vec3,
-        enemy_zones: &[SpawnZone],
-        existing_objects: &[EnvironmentObject],
-        object_types: &[String],
-        scale_range: (f32, f32),
-        constraints: &ObjectPlacementConstraints,
-        rng: &mut Pcg64,
-    ) -> MinionResult<Option<EnvironmentObject>> {
-        let world_x = rng.gen_range(1.0..(terrain.width as f32 - 1.0) * terrain.scale);
-        let world_z = rng.gen_range(1.0..(terrain.height as f32 - 1.0) * terrain.scale);
-
-        let terrain_height = match minion::terrain::coordinates::get_height_at_world_nearest(
-            terrain, world_x, world_z,
-        ) {
-            Some(height) => height,
-            None => return Ok(None),
+        exclusions.extend(
+            settlements
+                .iter()
+                .map(|town| (town.center, town.radius + 2.0)),
+        );
+        let scatter_config = ScatterConfig {
+            seed: MapSeed(config.generator.seed).child(SeedStream::Objects),
+            density_multiplier: config.object_density / DEFAULT_OBJECT_DENSITY,
+            object_types: Some(config.object_types.clone()),
+            exclusions,
+            max_objects: None,
         };
-        let candidate_pos = Vec3::new(world_x, terrain_height, world_z);
-
-        if !Self::is_valid_placement(
-            &candidate_pos,
-            player_spawn,
-            enemy_zones,
-            existing_objects,
-            terrain,
-            constraints,
-        ) {
-            return Ok(None);
-        }
 
-        let object_type = object_types[rng.gen_range(0..object_types.len())].clone();
-        let rotation = Vec3::new(0.0, rng.gen_range(0.0..2.0 * std::f32::consts::PI), 0.0);
-        let scale_factor = rng.gen_range(scale_range.0..=scale_range.1);
-        let scale = Vec3::splat(scale_factor);
-
-        Ok(Some(EnvironmentObject::new(
-            object_type,
-            candidate_pos,
-            rotation,
-            scale,
-        )))
-    }
-
-    fn is_valid_placement(
-        candidate_pos: &Vec3,
-        player_spawn: Vec3,
-        enemy_zones: &[SpawnZone],
-        existing_objects: &[EnvironmentObject],
-        terrain: &TerrainData,
-        constraints: &ObjectPlacementConstraints,
-    ) -> bool {
-        // Check distance constraints
-        candidate_pos.distance(player_spawn) >= constraints.min_distance_from_spawn
-            && enemy_zones.iter().all(|zone| {
-                candidate_pos.distance(zone.center)
-                    >= zone.radius + constraints.min_distance_from_enemies
-            })
-            && existing_objects.iter().all(|obj| {
-                candidate_pos.distance(obj.position) >= constraints.min_distance_between_objects
+        let mut biome_configs = create_default_biomes();
+        // Requested types no biome places get a plain rule in every biome
+        let unruled: Vec<&String> = config
+            .object_types
+            .iter()
+            .filter(|object_type| {
+                !biome_configs
+                    .values()
+                    .flat_map(|biome| &biome.objects)
+                    .any(|rule| &rule.object_type == *object_type)
             })
-            && is_suitable_for_spawning(
-                terrain,
-                candidate_pos.x,
-                candidate_pos.z,
-                constraints.max_slope,
-            )
-    }
-}
-
-struct ObjectPlacementConstraints {
-    min_distance_from_spawn: f32,
-    min_distance_from_enemies: f32,
-    min_distance_between_objects: f32,
-    max_slope: f32,
-}
-
-impl MapGenerator {
-    /// Generate biome-aware spawn zones
-    fn generate_biome_aware_spawn_zones(
-        terrain: &TerrainData,
-        biome_map: &minion::terrain::biomes::BiomeMap,
-        player_spawn: Vec3,
-        max_zones: usize,
-    ) -> MinionResult<Vec<SpawnZone>> {
-        use minion::terrain::biomes::BiomeType;
-
-        let mut zones = Vec::new();
-        let mut attempts = 0;
-        let max_attempts = max_zones * 10;
-
-        while zones.len() < max_zones && attempts < max_attempts {
-            attempts += 1;
-
-            // Calculate position using ring-based placement
-            let position = Self::calculate_ring_position(player_spawn, attempts as u32);
-
-            // Check biome suitability
-            if let Some(blend) = biome_map.get_blend_at_world(position.x, position.z) {
-                if let Some(dominant_biome) = blend.dominant_biome() {
-                    // Avoid placing spawns in water or swamp biomes
-                    if matches!(dominant_biome, BiomeType::Ocean | BiomeType::Swamp) {
-                        continue;
-                    }
+            .collect();
+        for biome in biome_configs.values_mut() {
+            biome.objects.extend(
+                unruled
+                    .iter()
+                    .map(|object_type| ObjectRule::new(*object_type, 1.0, 1.5)),
+            );
+            if let Some(scale_range) = config.scale_range {
+                for rule in &mut biome.objects {
+                    rule.scale_range = scale_range;
                 }
             }
-
-            // Use existing spawn zone logic for terrain checks
-            if is_suitable_for_spawning(terrain, position.x, position.z, 0.3) {
-                // Get terrain height and spawn 1.0 unit above it
-                let terrain_height = minion::terrain::coordinates::get_height_at_world_nearest(
-                    terrain, position.x, position.z,
-                )
-                .unwrap_or(0.0);
-                let corrected_position = Vec3::new(position.x, terrain_height + 1.0, position.z);
-
-                let zone = SpawnZone::new(
-                    corrected_position,
-                    3.0 + (attempts as f32 * 0.1).min(2.0), // Variable radius
-                    (2 + (attempts % 3)) as u32,            // Variable enemy count
-                    vec!["dark-knight".to_string()],
-                )?;
-                zones.push(zone);
-            }
         }
 
-        if zones.is_empty() {
-            // Fallback to non-biome-aware generation
-            Self::generate_spawn_zones(terrain, player_spawn, max_zones as u32)
-        } else {
-            Ok(zones)
-        }
-    }
-
-    /// Generate biome-aware environment objects with size variation
-    fn generate_biome_aware_objects(
-        terrain: &TerrainData,
-        biome_map: &minion::terrain::biomes::BiomeMap,
-        player_spawn: Vec3,
-        enemy_zones: &[SpawnZone],
-        density: f32,
-        object_types: &[String],
-        scale_range: (f32, f32),
-        seed: u32,
-    ) -> MinionResult<Vec<EnvironmentObject>> {
-        let mut objects = Vec::new();
-        let mut rng = Pcg64::seed_from_u64(seed as u64);
-
-        let constraints = ObjectPlacementConstraints {
-            min_distance_from_spawn: 3.0,
-            min_distance_from_enemies: 2.0,
-            min_distance_between_objects: 1.5,
-            max_slope: 0.4,
-        };
-
-        let world_width = terrain.width as f32 * terrain.scale;
-        let world_height = terrain.height as f32 * terrain.scale;
-        let area = world_width * world_height;
-        let target_objects = (area * density).round() as usize;
-
-        println!(
-            "Placing up to {} biome-aware environment objects (density: {})...",
-            target_objects, density
-        );
-
-        let mut attempts = 0;
-        let max_attempts = target_objects * 10;
-
-        while objects.len() < target_objects && attempts < max_attempts {
-            attempts += 1;
-
-            // Generate random world position
-            let x = rng.gen_range(-world_width / 2.0..world_width / 2.0);
-            let z = rng.gen_range(-world_height / 2.0..world_height / 2.0);
-
-            // Get biome at this location
-            let biome_objects = if let Some(blend) = biome_map.get_blend_at_world(x, z) {
-                Self::get_biome_appropriate_objects(blend.dominant_biome(), object_types)
-            } else {
-                object_types.to_vec()
-            };
-
-            if biome_objects.is_empty() {
-                continue;
-            }
-
-            // Select object type based on biome
-            let object_type = &biome_objects[rng.gen_range(0..biome_objects.len())];
-
-            // Get biome-specific scale based on object type
-            let scale = Self::get_biome_object_scale(
-                biome_map
-                    .get_blend_at_world(x, z)
-                    .and_then(|b| b.dominant_biome()),
-                object_type,
-                scale_range,
-                &mut rng,
-            );
-
-            // Use existing object placement validation logic
-            if let Some(obj) = Self::try_place_biome_object(
-                terrain,
-                player_spawn,
-                enemy_zones,
-                &objects,
-                object_type,
-                scale,
-                &constraints,
-                x,
-                z,
-                &mut rng,
-            )? {
-                objects.push(obj);
+        let uniform_rules: Vec<ObjectRule>;
+        let rules = match biome_map {
+            Some(map) => ScatterRules::Biomes {
+                map,
+                configs: &biome_configs,
+            },
+            None => {
+                let plains = &biome_configs[&BiomeType::Plains].objects;
+                uniform_rules = config
+                    .object_types
+                    .iter()
+                    .map(|object_type| {
+                        plains
+                            .iter()
+                            .find(|rule| &rule.object_type == object_type)
+                            .cloned()
+                            .unwrap_or_else(|| ObjectRule::new(object_type, 1.0, 1.5))
+                    })
+                    .collect();
+                ScatterRules::Uniform(&uniform_rules)
             }
-        }
+        };
 
         println!(
-            "Placed {} biome-aware environment objects (attempted {} placements)",
-            objects.len(),
-            attempts
+            "Scattering environment objects (density: {density})...",
+            density = config.object_density
         );
+        let objects = scatter_objects(terrain, rules, &scatter_config, &[]);
 
-        // Print object summary
-        let mut object_counts = std::collections::HashMap::new();
+        let mut object_counts = BTreeMap::new();
         for obj in &objects {
             *object_counts.entry(&obj.object_type).or_insert(0) += 1;
         }
-
-        if !object_counts.is_empty() {
-            println!("  Object types:");
-            for (obj_type, count) in object_counts {
-                println!("    {}: {} objects", obj_type, count);
-            }
+        println!("Placed {} environment objects", objects.len());
+        for (obj_type, count) in object_counts {
+            println!("    {obj_type}: {count} objects");
         }
 
-        Ok(objects)
-    }
-
-    /// Get objects appropriate for a specific biome
-    fn get_biome_appropriate_objects(
-        biome: Option<BiomeType>,
-        available_types: &[String],
-    ) -> Vec<String> {
-        use BiomeType::*;
-
-        match biome {
-            Some(Forest) => {
-                // Forest: Lots of trees, some rocks
-                let mut objects = Vec::new();
-                for obj_type in available_types {
-                    match obj_type.as_str() {
-                        "tree" => {
-                            // 3x more trees in forest
-                            objects.extend(vec![obj_type.clone(); 3]);
-                        }
-                        "rock" => objects.push(obj_type.clone()),
-                        _ => objects.push(obj_type.clone()),
-                    }
-                }
-                objects
-            }
-            Some(Mountains) => {
-                // Mountains: Lots of rocks, few trees
-                let mut objects = Vec::new();
-                for obj_type in available_types {
-                    match obj_type.as_str() {
-                        "rock" => {
-                            // 3x more rocks in mountains
-                            objects.extend(vec![obj_type.clone(); 3]);
-                        }
-                        "tree" => {
-                            // Fewer trees in mountains (only if included)
-                            if rand::random::<f32>() < 0.3 {
-                                objects.push(obj_type.clone());
-                            }
-                        }
-                        _ => objects.push(obj_type.clone()),
-                    }
-                }
-                objects
-            }
-            Some(Desert) => {
-                // Desert: Mostly rocks, no trees
-                available_types
-                    .iter()
-                    .filter(|obj| obj.as_str() != "tree")
-                    .cloned()
-                    .collect()
-            }
-            Some(Plains) => {
-                // Plains: Balanced mix
-                available_types.to_vec()
-            }
-            Some(Ocean) | Some(Swamp) => {
-                // Water biomes: No objects
-                Vec::new()
-            }
-            Some(Tundra) => {
-                // Tundra: Some rocks, very few trees
-                let mut objects = Vec::new();
-                for obj_type in available_types {
-                    match obj_type.as_str() {
-                        "rock" => objects.push(obj_type.clone()),
-                        "tree" => {
-                            if rand::random::<f32>() < 0.1 {
-                                objects.push(obj_type.clone());
-                            }
-                        }
-                        _ => objects.push(obj_type.clone()),
-                    }
-                }
-                objects
-            }
-            None => available_types.to_vec(),
-        }
+        objects
     }
+}
 
-    /// Get biome-appropriate scale for objects
-    fn get_biome_object_scale(
-        biome: Option<BiomeType>,
-        object_type: &str,
-        base_scale_range: (f32, f32),
-        rng: &mut Pcg64,
-    ) -> f32 {
-        use BiomeType::*;
-
-        let scale_modifier = match (biome, object_type) {
-            // Trees
-            (Some(Forest), "tree") => rng.gen_range(1.2..2.0), // Large forest trees
-            (Some(Mountains), "tree") => rng.gen_range(0.6..1.0), // Smaller mountain trees
-            (Some(Plains), "tree") => rng.gen_range(0.8..1.4), // Medium plains trees
-            (Some(Tundra), "tree") => rng.gen_range(0.4..0.8), // Small tundra trees
-
-            // Rocks with size distribution
-            (Some(Mountains), "rock") => {
-                // Mountains have larger rocks with log-normal distribution
-                let base = rng.gen_range(0.0..1.0_f32).powf(0.3); // Bias toward larger rocks
-                0.8 + base * 2.5 // Range: 0.8 to 3.3 (boulders to massive rocks)
-            }
-            (Some(Desert), "rock") => {
-                // Desert has medium-sized rocks
-                let base = rng.gen_range(0.0..1.0_f32).powf(0.5);
-                0.6 + base * 1.8 // Range: 0.6 to 2.4
-            }
-            (Some(Plains), "rock") => {
-                // Plains have smaller, scattered rocks
-                let base = rng.gen_range(0.0..1.0_f32).powf(0.7);
-                0.3 + base * 1.2 // Range: 0.3 to 1.5
-            }
-            (Some(Forest), "rock") => {
-                // Forest has medium rocks
-                let base = rng.gen_range(0.0..1.0_f32).powf(0.6);
-                0.4 + base * 1.4 // Range: 0.4 to 1.8
-            }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use minion::terrain::settlements::SettlementKind;
+    use minion::terrain_generation::{SpawnTier, get_terrain_preset};
+    use std::path::Path;
 
-            // Default cases
-            _ => rng.gen_range(base_scale_range.0..base_scale_range.1),
-        };
+    #[test]
+    fn test_generate_spawn_zones() {
+        let player_spawn = Vec3::new(32.0, 1.0, 32.0);
+        let terrain = TerrainData::create_flat(64, 64, 1.0, 0.0).unwrap();
 
-        scale_modifier
-    }
+        // Test that the function runs without error, terrain suitability depends on complex logic
+        let zones = MapGenerator::generate_spawn_zones(
+            &terrain,
+            None,
+            player_spawn,
+            &[],
+            &DifficultyConfig::default(),
+            1,
+        )
+        .unwrap();
 
-    /// Try to place a biome-specific object at given coordinates
-    fn try_place_biome_object(
-        terrain: &TerrainData,
-        player_spawn: Vec3,
-        enemy_zones: &[SpawnZone],
-        existing_objects: &[EnvironmentObject],
-        object_type: &str,
-        scale: f32,
-        constraints: &ObjectPlacementConstraints,
-        x: f32,
-        z: f32,
-        rng: &mut Pcg64,
-    ) -> MinionResult<Option<EnvironmentObject>> {
-        let candidate_pos = Vec3::new(x, 0.0, z);
-
-        // Check distance from player spawn
-        if candidate_pos.distance(player_spawn) < constraints.min_distance_from_spawn {
-            return Ok(None);
+        // Test any zones that were created have correct properties
+        for zone in &zones {
+            assert!(zone.radius >= 3.0);
+            assert!(zone.max_enemies >= 2);
+            assert_eq!(zone.enemy_types.len(), 1);
+            assert_eq!(zone.enemy_types[0], "dark-knight");
         }
+    }
 
-        // Check distance from enemy zones
-        for zone in enemy_zones {
-            if candidate_pos.distance(zone.center) < constraints.min_distance_from_enemies {
-                return Ok(None);
+    #[test]
+    fn test_generate_objects_applies_scale_to_every_type() {
+        let terrain = TerrainData::create_flat(48, 48, 1.0, 0.0).unwrap();
+        let biome_map = BiomeMap::uniform(48, 48, BiomeType::Plains, 1.0);
+        let mut config = golden_config("objects");
+        config.object_types = vec!["tree".to_string(), "totem".to_string()];
+        config.scale_range = Some((2.0, 2.0));
+
+        for biome_map in [Some(&biome_map), None] {
+            let objects =
+                MapGenerator::generate_objects(&terrain, biome_map, Vec3::ZERO, &[], &[], &config);
+
+            // A type without a biome rule is still placed
+            for object_type in &config.object_types {
+                assert!(objects.iter().any(|obj| &obj.object_type == object_type));
             }
+            assert!(objects.iter().all(|obj| obj.scale == Vec3::splat(2.0)));
         }
+    }
 
-        // Check distance from existing objects
-        for obj in existing_objects {
-            if candidate_pos.distance(obj.position) < constraints.min_distance_between_objects {
-                return Ok(None);
-            }
+    #[test]
+    fn test_large_spawn_zones_stay_out_of_towns() {
+        let terrain = TerrainData::create_flat(64, 64, 1.0, 0.0).unwrap();
+        let town = Settlement {
+            kind: SettlementKind::Town,
+            center: Vec3::new(10.0, 0.0, 10.0),
+            radius: 6.0,
+            safe_radius: 8.0,
+            objects: Vec::new(),
+        };
+        let difficulty = DifficultyConfig {
+            tiers: vec![SpawnTier::new(6, 10.0, 3)],
+            ..Default::default()
         }
-
-        // Check slope
-        if !is_suitable_for_spawning(terrain, x, z, constraints.max_slope) {
-            return Ok(None);
+        .with_safe_radius(4.0);
+
+        let zones = MapGenerator::generate_spawn_zones(
+            &terrain,
+            None,
+            Vec3::new(-24.0, 1.0, -24.0),
+            std::slice::from_ref(&town),
+            &difficulty,
+            5,
+        )
+        .unwrap();
+
+        assert!(!zones.is_empty());
+        for zone in &zones {
+            // The zone's edge, not just its center, stays outside the safe radius
+            let gap = zone.center.xz().distance(town.center.xz());
+            assert!(gap >= town.safe_radius + zone.radius);
         }
-
-        // Get terrain height
-        let height = minion::terrain::coordinates::get_height_at_world_interpolated(terrain, x, z)
-            .unwrap_or(0.0);
-
-        Ok(Some(EnvironmentObject::new(
-            object_type.to_string(),
-            Vec3::new(x, height, z),
-            Vec3::new(0.0, rng.gen_range(0.0..std::f32::consts::TAU), 0.0),
-            Vec3::splat(scale),
-        )))
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
 
     #[test]
-    fn test_calculate_ring_position() {
-        let player_spawn = Vec3::new(32.0, 1.0, 32.0);
-        let pos1 = MapGenerator::calculate_ring_position(player_spawn, 0);
-        let pos2 = MapGenerator::calculate_ring_position(player_spawn, 1);
+    fn test_generate_objects_keeps_rule_scale_without_override() {
+        let terrain = TerrainData::create_flat(48, 48, 1.0, 0.0).unwrap();
+        let biome_map = BiomeMap::uniform(48, 48, BiomeType::Plains, 1.0);
+        let mut config = golden_config("objects");
+        config.object_types = vec!["tree".to_string()];
+        config.scale_range = None;
+
+        let objects = MapGenerator::generate_objects(
+            &terrain,
+            Some(&biome_map),
+            Vec3::ZERO,
+            &[],
+            &[],
+            &config,
+        );
 
-        // Positions should be different
-        assert_ne!(pos1, pos2);
+        // Plains trees scale between 0.8 and 1.4
+        assert!(!objects.is_empty());
+        assert!(objects.iter().any(|obj| obj.scale.x > 1.2));
+        assert!(objects.iter().all(|obj| (0.8..=1.4).contains(&obj.scale.x)));
+    }
 
-        // Should be roughly the expected distance from spawn (8.0 to 14.0 based on new logic)
-        let distance1 = pos1.distance(player_spawn);
-        assert!(distance1 >= 7.0 && distance1 <= 15.0);
+    /// Small maps that together exercise every seeded stage
+    fn golden_config(name: &str) -> MapGenerationConfig {
+        let mut config = MapGenerationConfig {
+            name: name.to_string(),
+            width: 48,
+            height: 48,
+            player_spawn: Vec3::new(0.0, 1.0, 0.0),
+            generator: get_terrain_preset("hills", 1234).unwrap(),
+            recipe: None,
+            object_density: DEFAULT_OBJECT_DENSITY,
+            object_types: vec!["tree".to_string(), "rock".to_string()],
+            scale_range: Some((0.8, 1.2)),
+            terrain_scale: 1.0,
+            enable_biomes: true,
+            biome_regions: 4,
+            climate: None,
+            enable_paths: true,
+            main_roads: 2,
+            trails_per_biome: 1,
+            carve_roads: true,
+            erosion: Some(HydraulicErosionConfig {
+                iterations: 500,
+                ..Default::default()
+            }),
+            filters: Vec::new(),
+            stamps: Vec::new(),
+            hydrology: Some(HydrologyConfig {
+                rivers: 1,
+                lakes: true,
+                ..Default::default()
+            }),
+            settlements: Some(SettlementConfig {
+                towns: 1,
+                camps: 1,
+                ..Default::default()
+            }),
+            dungeon: None,
+            difficulty: DifficultyConfig::default(),
+        };
+        match name {
+            "climate" => config.climate = Some(ClimateConfig::default()),
+            "dungeon" => config.dungeon = Some(DungeonConfig::default()),
+            _ => {}
+        }
+        config
+    }
+
+    /// Generate a map twice and compare its bytes against `tests/golden/<name>.fnv`.
+    ///
+    /// Set `UPDATE_GOLDEN=1` to write the hashes after an intended change to
+    /// generation; without it a missing hash file fails the test.
+    fn check_golden(name: &str) {
+        let bytes = || {
+            MapGenerator::generate(golden_config(name))
+                .unwrap()
+                .to_bytes(None)
+                .unwrap()
+        };
+        let first = bytes();
+        assert!(first == bytes(), "{name} map differs between runs");
+
+        // FNV-1a, stable across platforms and Rust versions
+        let hash = first.iter().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ *byte as u64).wrapping_mul(0x0100_0000_01b3)
+        });
+        let hash = format!("{hash:016x}\n");
+
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/golden")
+            .join(format!("{name}.fnv"));
+        if std::env::var_os("UPDATE_GOLDEN").is_some() {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, hash).unwrap();
+            return;
+        }
+        let expected = std::fs::read_to_string(&path).unwrap_or_else(|_| {
+            panic!(
+                "missing {}; run with UPDATE_GOLDEN=1 to create it",
+                path.display()
+            )
+        });
+        assert_eq!(
+            expected, hash,
+            "{name} map changed; rerun with UPDATE_GOLDEN=1 if that was intended"
+        );
     }
 
     #[test]
-    fn test_generate_spawn_zones() {
-        let player_spawn = Vec3::new(32.0, 1.0, 32.0);
-        let terrain = TerrainData::create_flat(64, 64, 1.0, 0.0).unwrap();
+    fn test_golden_outdoor_map() {
+        check_golden("outdoor");
+    }
 
-        // Test that the function runs without error, terrain suitability depends on complex logic
-        let zones = MapGenerator::generate_spawn_zones(&terrain, player_spawn, 3).unwrap();
+    #[test]
+    fn test_golden_climate_map() {
+        check_golden("climate");
+    }
 
-        // Test any zones that were created have correct properties
-        for zone in &zones {
-            assert!(zone.radius >= 3.0);
-            assert!(zone.max_enemies >= 2);
-            assert_eq!(zone.enemy_types.len(), 1);
-            assert_eq!(zone.enemy_types[0], "dark-knight");
-        }
+    #[test]
+    fn test_golden_dungeon_map() {
+        check_golden("dungeon");
     }
 }
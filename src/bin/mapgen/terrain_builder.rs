@@ -1,26 +1,34 @@
 use minion::game_logic::errors::{MinionError, MinionResult};
-use minion::terrain_generation::{TerrainAlgorithm, TerrainGenerator, get_terrain_preset};
+use minion::terrain_generation::{
+    NoiseBasis, TerrainAlgorithm, TerrainGenerator, get_terrain_preset,
+};
 
 pub struct TerrainBuilder {
     terrain_type: String,
-    seed: Option<u32>,
+    seed: u32,
     amplitude: f32,
     frequency: f32,
     octaves: u32,
+    persistence: f32,
+    lacunarity: f32,
+    warp_strength: f32,
 }
 
 impl TerrainBuilder {
     pub fn new(terrain_type: String) -> Self {
         Self {
             terrain_type,
-            seed: None,
+            seed: 0,
             amplitude: 10.0,
             frequency: 0.1,
             octaves: 4,
+            persistence: 0.5,
+            lacunarity: 2.0,
+            warp_strength: 0.0,
         }
     }
 
-    pub fn seed(mut self, seed: Option<u32>) -> Self {
+    pub fn seed(mut self, seed: u32) -> Self {
         self.seed = seed;
         self
     }
@@ -40,13 +48,65 @@ impl TerrainBuilder {
         self
     }
 
+    pub fn persistence(mut self, persistence: f32) -> Self {
+        self.persistence = persistence;
+        self
+    }
+
+    pub fn lacunarity(mut self, lacunarity: f32) -> Self {
+        self.lacunarity = lacunarity;
+        self
+    }
+
+    /// Domain warp strength in world units; 0 disables warping
+    pub fn warp_strength(mut self, warp_strength: f32) -> Self {
+        self.warp_strength = warp_strength;
+        self
+    }
+
     /// Check if any manual parameters differ from defaults
     fn has_manual_parameters(&self) -> bool {
-        self.amplitude != 10.0 || self.frequency != 0.1 || self.octaves != 4
+        self.amplitude != 10.0
+            || self.frequency != 0.1
+            || self.octaves != 4
+            || self.persistence != 0.5
+            || self.lacunarity != 2.0
+    }
+
+    fn fbm(&self, basis: NoiseBasis) -> TerrainAlgorithm {
+        TerrainAlgorithm::Fbm {
+            basis,
+            amplitude: self.amplitude,
+            frequency: self.frequency,
+            octaves: self.octaves,
+            persistence: self.persistence,
+            lacunarity: self.lacunarity,
+        }
+    }
+
+    /// Wrap an algorithm in a domain warp when a warp strength is set
+    fn apply_warp(&self, algorithm: TerrainAlgorithm) -> TerrainAlgorithm {
+        if self.warp_strength <= 0.0 {
+            return algorithm;
+        }
+        if let TerrainAlgorithm::DomainWarp {
+            base, frequency, ..
+        } = algorithm
+        {
+            return TerrainAlgorithm::DomainWarp {
+                base,
+                strength: self.warp_strength,
+                frequency,
+            };
+        }
+        TerrainAlgorithm::DomainWarp {
+            base: Box::new(algorithm),
+            strength: self.warp_strength,
+            frequency: self.frequency * 0.5, // Warp features larger than the terrain features
+        }
     }
 
     pub fn build(self) -> MinionResult<TerrainGenerator> {
-        let seed = self.seed.unwrap_or_else(rand::random);
         let manual_params = self.has_manual_parameters();
 
         // Try preset first, then fall back to custom algorithm
@@ -54,6 +114,7 @@ impl TerrainBuilder {
             if manual_params {
                 generator.algorithm = self.override_preset_params(generator.algorithm)?;
             }
+            generator.algorithm = self.apply_warp(generator.algorithm);
             return Ok(generator);
         }
 
@@ -69,17 +130,26 @@ impl TerrainBuilder {
                 frequency: self.frequency,
                 octaves: self.octaves,
             },
+            "fbm" => self.fbm(NoiseBasis::Perlin),
+            "simplex" => self.fbm(NoiseBasis::Simplex),
+            "billow" => self.fbm(NoiseBasis::Billow),
+            "worley" => TerrainAlgorithm::Worley {
+                amplitude: self.amplitude,
+                frequency: self.frequency,
+                jitter: 1.0,
+                edges: false,
+            },
             _ => {
                 return Err(MinionError::InvalidMapData {
                     reason: format!(
-                        "Unknown terrain type: '{}'. Available presets: flat, hills, mountains, valleys. Custom algorithms: perlin, ridged",
+                        "Unknown terrain type: '{}'. Available presets: flat, hills, mountains, valleys, canyons, mesas, dunes. Custom algorithms: perlin, ridged, fbm, simplex, billow, worley",
                         self.terrain_type
                     ),
                 });
             }
         };
 
-        Ok(TerrainGenerator::new(seed, algorithm))
+        Ok(TerrainGenerator::new(self.seed, self.apply_warp(algorithm)))
     }
 
     fn override_preset_params(
@@ -115,6 +185,39 @@ impl TerrainBuilder {
                     octaves: self.octaves,
                 })
             }
+            TerrainAlgorithm::Fbm { basis, .. } => {
+                println!(
+                    "Using custom parameters with '{}' terrain type: amplitude={}, frequency={}, octaves={}, persistence={}, lacunarity={}",
+                    self.terrain_type,
+                    self.amplitude,
+                    self.frequency,
+                    self.octaves,
+                    self.persistence,
+                    self.lacunarity
+                );
+                Ok(self.fbm(basis))
+            }
+            TerrainAlgorithm::Worley { jitter, edges, .. } => {
+                println!(
+                    "Using custom parameters with '{}' terrain type: amplitude={}, frequency={}",
+                    self.terrain_type, self.amplitude, self.frequency
+                );
+                Ok(TerrainAlgorithm::Worley {
+                    amplitude: self.amplitude,
+                    frequency: self.frequency,
+                    jitter,
+                    edges,
+                })
+            }
+            TerrainAlgorithm::DomainWarp {
+                base,
+                strength,
+                frequency,
+            } => Ok(TerrainAlgorithm::DomainWarp {
+                base: Box::new(self.override_preset_params(*base)?),
+                strength,
+                frequency,
+            }),
         }
     }
 }
@@ -125,7 +228,7 @@ mod tests {
 
     #[test]
     fn test_terrain_builder_default() {
-        let builder = TerrainBuilder::new("hills".to_string()).seed(Some(12345));
+        let builder = TerrainBuilder::new("hills".to_string()).seed(12345);
 
         let generator = builder.build().unwrap();
         assert_eq!(generator.seed, 12345);
@@ -134,7 +237,7 @@ mod tests {
     #[test]
     fn test_terrain_builder_with_manual_params() {
         let builder = TerrainBuilder::new("hills".to_string())
-            .seed(Some(12345))
+            .seed(12345)
             .amplitude(100.0)
             .frequency(0.2)
             .octaves(6);
@@ -159,7 +262,7 @@ mod tests {
     #[test]
     fn test_terrain_builder_custom_algorithm() {
         let builder = TerrainBuilder::new("perlin".to_string())
-            .seed(Some(12345))
+            .seed(12345)
             .amplitude(25.0)
             .frequency(0.05)
             .octaves(8);
@@ -181,6 +284,67 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_terrain_builder_fbm_types() {
+        let generator = TerrainBuilder::new("billow".to_string())
+            .seed(1)
+            .persistence(0.3)
+            .lacunarity(2.5)
+            .build()
+            .unwrap();
+
+        match generator.algorithm {
+            TerrainAlgorithm::Fbm {
+                basis,
+                persistence,
+                lacunarity,
+                ..
+            } => {
+                assert_eq!(basis, NoiseBasis::Billow);
+                assert_eq!(persistence, 0.3);
+                assert_eq!(lacunarity, 2.5);
+            }
+            _ => panic!("Expected Fbm algorithm"),
+        }
+    }
+
+    #[test]
+    fn test_terrain_builder_warp() {
+        let generator = TerrainBuilder::new("simplex".to_string())
+            .seed(1)
+            .warp_strength(5.0)
+            .build()
+            .unwrap();
+
+        match generator.algorithm {
+            TerrainAlgorithm::DomainWarp { base, strength, .. } => {
+                assert_eq!(strength, 5.0);
+                assert!(matches!(
+                    *base,
+                    TerrainAlgorithm::Fbm {
+                        basis: NoiseBasis::Simplex,
+                        ..
+                    }
+                ));
+            }
+            _ => panic!("Expected DomainWarp algorithm"),
+        }
+
+        // Presets that already warp keep a single warp layer
+        let canyons = TerrainBuilder::new("canyons".to_string())
+            .seed(1)
+            .warp_strength(3.0)
+            .build()
+            .unwrap();
+        match canyons.algorithm {
+            TerrainAlgorithm::DomainWarp { base, strength, .. } => {
+                assert_eq!(strength, 3.0);
+                assert!(matches!(*base, TerrainAlgorithm::Worley { .. }));
+            }
+            _ => panic!("Expected DomainWarp algorithm"),
+        }
+    }
+
     #[test]
     fn test_terrain_builder_unknown_type() {
         let builder = TerrainBuilder::new("unknown".to_string());
@@ -0,0 +1,400 @@
+//! Headless pathfinding benchmark and scenario runner.
+//!
+//! Loads a map from `assets/maps`, builds its navigation grid, and runs a set
+//! of start/goal queries through every pathfinding backend. Results are
+//! printed as JSON so they can be diffed between commits.
+//!
+//! # Example Usage
+//! ```bash
+//! # 200 seeded queries on the hills map
+//! cargo run --release --bin pathbench -- --map hills.bin --pairs 200 --seed 7
+//!
+//! # Fixed queries from a scenario file, report written to disk
+//! cargo run --release --bin pathbench -- --map complex.bin --scenario scenarios/complex.toml --output complex.json
+//! ```
+
+use bevy::prelude::Vec3;
+use clap::Parser;
+use minion::game_logic::errors::{MinionError, MinionResult};
+use minion::map::MapDefinition;
+use minion::pathfinding::grid_blocking::block_deep_water;
+use minion::pathfinding::{
+    GridNode, NavigationGrid, PathSearch, PathfindingConfig, find_path_traced,
+};
+use rand::{Rng, SeedableRng};
+use rand_pcg::Pcg64;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+#[derive(Parser)]
+#[command(name = "pathbench")]
+#[command(about = "Benchmark pathfinding on a map and report results as JSON")]
+struct Args {
+    /// Map file (in assets/maps/ directory)
+    #[arg(long)]
+    map: String,
+
+    /// TOML scenario file with explicit start/goal pairs (overrides --pairs)
+    #[arg(long)]
+    scenario: Option<PathBuf>,
+
+    /// Number of random start/goal pairs to generate
+    #[arg(long, default_value = "100")]
+    pairs: usize,
+
+    /// Random seed for pair generation
+    #[arg(long, default_value = "42")]
+    seed: u64,
+
+    /// Agent radius used for obstacle inflation
+    #[arg(long, default_value = "0.5")]
+    agent_radius: f32,
+
+    /// Minimum straight-line distance between generated start and goal
+    #[arg(long, default_value = "5.0")]
+    min_distance: f32,
+
+    /// Navigation cell size in world units (defaults to terrain scale)
+    #[arg(long)]
+    cell_size: Option<f32>,
+
+    /// Write the JSON report to this file instead of stdout
+    #[arg(long)]
+    output: Option<PathBuf>,
+}
+
+/// A single start/goal query
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct PathPair {
+    start: [f32; 3],
+    goal: [f32; 3],
+    /// Overrides the command-line agent radius for this query
+    #[serde(default)]
+    agent_radius: Option<f32>,
+}
+
+/// Scenario file layout: a list of `[[pair]]` tables
+#[derive(Debug, Default, Deserialize)]
+struct Scenario {
+    #[serde(default, rename = "pair")]
+    pairs: Vec<PathPair>,
+}
+
+/// Pathfinding implementations that can be benchmarked against each other
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Backend {
+    AStar,
+    JumpPoint,
+}
+
+impl Backend {
+    const ALL: [Backend; 2] = [Backend::AStar, Backend::JumpPoint];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Backend::AStar => "astar",
+            Backend::JumpPoint => "jps",
+        }
+    }
+
+    fn search(&self) -> PathSearch {
+        match self {
+            Backend::AStar => PathSearch::AStar,
+            Backend::JumpPoint => PathSearch::JumpPoint,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct BackendReport {
+    backend: String,
+    queries: usize,
+    successes: usize,
+    success_rate: f64,
+    /// Mean of path length divided by straight-line distance, over successful queries
+    mean_path_ratio: f64,
+    mean_nodes_expanded: f64,
+    max_nodes_expanded: usize,
+    p50_ms: f64,
+    p99_ms: f64,
+    total_ms: f64,
+}
+
+#[derive(Debug, Serialize)]
+struct BenchmarkReport {
+    map: String,
+    grid_width: u32,
+    grid_height: u32,
+    cell_size: f32,
+    seed: Option<u64>,
+    backends: Vec<BackendReport>,
+}
+
+fn load_scenario(path: &Path) -> MinionResult<Vec<PathPair>> {
+    if !path.exists() {
+        return Err(MinionError::ConfigFileNotFound {
+            path: path.to_path_buf(),
+        });
+    }
+    let content = std::fs::read_to_string(path).map_err(MinionError::ConfigDirCreationFailed)?;
+    let scenario: Scenario = toml::from_str(&content)?;
+    if scenario.pairs.is_empty() {
+        return Err(MinionError::InvalidConfig {
+            reason: format!("Scenario {} contains no [[pair]] entries", path.display()),
+        });
+    }
+    Ok(scenario.pairs)
+}
+
+/// Pick random walkable start/goal pairs at least `min_distance` apart
+fn generate_pairs(
+    nav_grid: &NavigationGrid,
+    count: usize,
+    seed: u64,
+    min_distance: f32,
+) -> MinionResult<Vec<PathPair>> {
+    let walkable: Vec<Vec3> = (0..nav_grid.height)
+        .flat_map(|z| (0..nav_grid.width).map(move |x| (x, z)))
+        .filter(|&(x, z)| nav_grid.is_walkable(x, z))
+        .map(|(x, z)| nav_grid.grid_to_world(GridNode::new(x, z)))
+        .collect();
+
+    if walkable.len() < 2 {
+        return Err(MinionError::InvalidMapData {
+            reason: "Map has fewer than two walkable cells".to_string(),
+        });
+    }
+
+    let mut rng = Pcg64::seed_from_u64(seed);
+    let mut pairs = Vec::with_capacity(count);
+    let max_attempts = count.saturating_mul(50).max(100);
+
+    for _ in 0..max_attempts {
+        if pairs.len() == count {
+            break;
+        }
+        let start = walkable[rng.gen_range(0..walkable.len())];
+        let goal = walkable[rng.gen_range(0..walkable.len())];
+        if start.distance(goal) >= min_distance {
+            pairs.push(PathPair {
+                start: start.to_array(),
+                goal: goal.to_array(),
+                agent_radius: None,
+            });
+        }
+    }
+
+    if pairs.len() < count {
+        return Err(MinionError::InvalidConfig {
+            reason: format!(
+                "Only found {} of {} pairs at least {} apart",
+                pairs.len(),
+                count,
+                min_distance
+            ),
+        });
+    }
+
+    Ok(pairs)
+}
+
+fn path_length(start: Vec3, path: &[Vec3]) -> f32 {
+    std::iter::once(start)
+        .chain(path.iter().copied())
+        .collect::<Vec<_>>()
+        .windows(2)
+        .map(|w| w[0].distance(w[1]))
+        .sum()
+}
+
+/// Nearest-rank percentile of already sorted samples
+fn percentile(sorted: &[f64], pct: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = ((pct / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+fn run_backend(
+    backend: Backend,
+    nav_grid: &NavigationGrid,
+    pairs: &[PathPair],
+    default_radius: f32,
+) -> BackendReport {
+    let mut timings_ms = Vec::with_capacity(pairs.len());
+    let mut ratios = Vec::new();
+    let mut nodes_expanded = Vec::with_capacity(pairs.len());
+    let mut successes = 0;
+
+    let mut nav_grid = nav_grid.clone();
+    nav_grid.config.search = backend.search();
+
+    for pair in pairs {
+        let start = Vec3::from_array(pair.start);
+        let goal = Vec3::from_array(pair.goal);
+        let radius = pair.agent_radius.unwrap_or(default_radius);
+
+        let started = Instant::now();
+        let query = find_path_traced(&nav_grid, start, goal, radius);
+        timings_ms.push(started.elapsed().as_secs_f64() * 1000.0);
+        nodes_expanded.push(query.explored.len());
+
+        if let Some(path) = &query.path {
+            successes += 1;
+            let straight = start.distance(goal);
+            if straight > f32::EPSILON {
+                ratios.push((path_length(start, path) / straight) as f64);
+            }
+        }
+    }
+
+    let queries = pairs.len();
+    let total_ms = timings_ms.iter().sum();
+    timings_ms.sort_by(f64::total_cmp);
+
+    BackendReport {
+        backend: backend.name().to_string(),
+        queries,
+        successes,
+        success_rate: successes as f64 / queries.max(1) as f64,
+        mean_path_ratio: ratios.iter().sum::<f64>() / ratios.len().max(1) as f64,
+        mean_nodes_expanded: nodes_expanded.iter().sum::<usize>() as f64 / queries.max(1) as f64,
+        max_nodes_expanded: nodes_expanded.iter().copied().max().unwrap_or(0),
+        p50_ms: percentile(&timings_ms, 50.0),
+        p99_ms: percentile(&timings_ms, 99.0),
+        total_ms,
+    }
+}
+
+fn main() -> MinionResult<()> {
+    let args = Args::parse();
+
+    let map = MapDefinition::load_from_file(&args.map)?;
+    let config = PathfindingConfig {
+        cell_size: args.cell_size,
+        ..Default::default()
+    };
+    let mut nav_grid =
+        NavigationGrid::from_terrain_and_objects(&map.terrain, &map.environment_objects, config)?;
+    block_deep_water(&mut nav_grid, &map.terrain, &map.water_bodies);
+    nav_grid.set_road_surfaces(&map.terrain, &map.road_surfaces);
+
+    let (pairs, seed) = match &args.scenario {
+        Some(path) => (load_scenario(path)?, None),
+        None => (
+            generate_pairs(&nav_grid, args.pairs, args.seed, args.min_distance)?,
+            Some(args.seed),
+        ),
+    };
+
+    let report = BenchmarkReport {
+        map: args.map.clone(),
+        grid_width: nav_grid.width,
+        grid_height: nav_grid.height,
+        cell_size: nav_grid.cell_size,
+        seed,
+        backends: Backend::ALL
+            .iter()
+            .map(|backend| run_backend(*backend, &nav_grid, &pairs, args.agent_radius))
+            .collect(),
+    };
+
+    let json = serde_json::to_string_pretty(&report).map_err(|e| MinionError::InvalidConfig {
+        reason: format!("Failed to serialize report: {e}"),
+    })?;
+
+    match &args.output {
+        Some(path) => {
+            std::fs::write(path, json).map_err(MinionError::ConfigDirCreationFailed)?;
+            eprintln!("Report written to {}", path.display());
+        }
+        None => println!("{json}"),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use minion::map::TerrainData;
+
+    fn flat_grid() -> NavigationGrid {
+        let terrain = TerrainData::create_flat(32, 32, 1.0, 0.0).unwrap();
+        NavigationGrid::from_terrain(&terrain, PathfindingConfig::default()).unwrap()
+    }
+
+    #[test]
+    fn test_generate_pairs_is_seeded() {
+        let nav_grid = flat_grid();
+        let a = generate_pairs(&nav_grid, 20, 9, 5.0).unwrap();
+        let b = generate_pairs(&nav_grid, 20, 9, 5.0).unwrap();
+
+        assert_eq!(a.len(), 20);
+        for (pa, pb) in a.iter().zip(&b) {
+            assert_eq!((pa.start, pa.goal), (pb.start, pb.goal));
+            assert!(Vec3::from_array(pa.start).distance(Vec3::from_array(pa.goal)) >= 5.0);
+        }
+    }
+
+    #[test]
+    fn test_percentile() {
+        let samples: Vec<f64> = (1..=100).map(f64::from).collect();
+        assert_eq!(percentile(&samples, 50.0), 50.0);
+        assert_eq!(percentile(&samples, 99.0), 99.0);
+        assert_eq!(percentile(&[], 50.0), 0.0);
+    }
+
+    #[test]
+    fn test_run_backend_on_open_grid() {
+        let nav_grid = flat_grid();
+        let pairs = generate_pairs(&nav_grid, 10, 1, 8.0).unwrap();
+        let report = run_backend(Backend::AStar, &nav_grid, &pairs, 0.5);
+
+        assert_eq!(report.queries, 10);
+        assert_eq!(report.success_rate, 1.0);
+        assert!(
+            report.mean_path_ratio >= 1.0,
+            "Paths can't beat a straight line"
+        );
+        assert!(report.mean_nodes_expanded > 0.0);
+        assert!(report.p50_ms <= report.p99_ms);
+    }
+
+    #[test]
+    fn test_backends_agree_on_flat_grid() {
+        let nav_grid = flat_grid();
+        let pairs = generate_pairs(&nav_grid, 10, 3, 8.0).unwrap();
+        let astar = run_backend(Backend::AStar, &nav_grid, &pairs, 0.5);
+        let jps = run_backend(Backend::JumpPoint, &nav_grid, &pairs, 0.5);
+
+        assert_eq!(astar.successes, jps.successes);
+        assert!(jps.mean_nodes_expanded < astar.mean_nodes_expanded);
+        for report in [&astar, &jps] {
+            assert!(report.total_ms > 0.0);
+            assert!(report.p50_ms <= report.p99_ms && report.p99_ms <= report.total_ms);
+        }
+    }
+
+    #[test]
+    fn test_scenario_parsing() {
+        let scenario: Scenario = toml::from_str(
+            r#"
+            [[pair]]
+            start = [0.0, 0.0, 0.0]
+            goal = [5.0, 0.0, 5.0]
+
+            [[pair]]
+            start = [1.0, 0.0, 1.0]
+            goal = [-4.0, 0.0, 2.0]
+            agent_radius = 1.0
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(scenario.pairs.len(), 2);
+        assert_eq!(scenario.pairs[1].agent_radius, Some(1.0));
+    }
+}
@@ -242,6 +242,9 @@ impl HasResources for Player {
 #[derive(Component)]
 pub struct Ground;
 
+#[derive(Component)]
+pub struct Water;
+
 #[derive(Component)]
 pub struct SceneLight;
 
@@ -336,6 +339,17 @@ pub enum LodEntityType {
     Enemy,
 }
 
+/// One terrain chunk with a mesh per LOD level
+#[derive(Component)]
+pub struct TerrainChunk {
+    pub bounds: crate::terrain::chunks::ChunkBounds,
+    pub current_level: LodLevel,
+    pub high_handle: Option<Handle<Mesh>>, // Each level is built the first time it is shown
+    pub med_handle: Option<Handle<Mesh>>,
+    pub low_handle: Option<Handle<Mesh>>,
+    pub collider: Option<bevy_rapier3d::prelude::Collider>, // Built the first time something comes near
+}
+
 // Unified resource display system
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ResourceType {
@@ -547,6 +561,12 @@ pub struct PathfindingAgent {
     pub max_path_distance: f32,
     /// Agent physical radius used for planning (per-agent)
     pub agent_radius: f32,
+    /// Velocity the movement system wants this frame, before local avoidance
+    pub preferred_velocity: Vec3,
+    /// Maximum speed local avoidance may assign (0 disables steering for this agent)
+    pub max_speed: f32,
+    /// Collision-free velocity chosen by local avoidance on the last update
+    pub avoidance_velocity: Vec3,
 }
 
 impl PathfindingAgent {
@@ -560,6 +580,9 @@ impl PathfindingAgent {
             waypoint_reach_distance: 1.0, // 1.0 units - works better with spaced waypoints
             max_path_distance: 50.0,      // Replan if destination changes by more than 50 units
             agent_radius: 0.5,            // Default per-agent radius
+            preferred_velocity: Vec3::ZERO,
+            max_speed: 0.0, // Not steered until a movement system sets a speed
+            avoidance_velocity: Vec3::ZERO,
         }
     }
 }
@@ -595,6 +618,127 @@ impl PathfindingAgent {
     pub fn advance_waypoint(&mut self) {
         self.nav_path.advance();
     }
+
+    /// Record the desired horizontal velocity for local avoidance
+    pub fn set_preferred_velocity(&mut self, velocity: Vec3, max_speed: f32) {
+        self.preferred_velocity = Vec3::new(velocity.x, 0.0, velocity.z);
+        self.max_speed = max_speed;
+    }
+}
+
+/// Where an idle enemy goes when it is not chasing the player
+#[derive(Debug, Clone, PartialEq)]
+pub enum PatrolMode {
+    /// Walk a waypoint graph, never doubling back unless at a dead end
+    Route {
+        points: Vec<Vec3>,
+        neighbors: Vec<Vec<usize>>,
+        current: usize,
+        previous: Option<usize>,
+    },
+    /// Pick random destinations inside a circle
+    Wander { center: Vec3, radius: f32 },
+}
+
+/// Idle patrol state for enemies, resumed after they lose the player
+#[derive(Component, Debug, Clone)]
+pub struct Patrol {
+    pub mode: PatrolMode,
+    /// Current destination on the ground plane
+    pub target: Vec3,
+    /// Seconds to wait after reaching each destination
+    pub pause_duration: f32,
+    pub pause_remaining: f32,
+    /// Fraction of chase speed used while patrolling
+    pub speed_factor: f32,
+    /// Seconds spent heading to the current destination
+    pub leg_elapsed: f32,
+}
+
+impl Patrol {
+    /// Give up on a destination after this long so blocked enemies keep moving
+    pub const MAX_LEG_SECONDS: f32 = 20.0;
+
+    /// Patrol a waypoint graph starting at its first point
+    pub fn route(
+        points: Vec<Vec3>,
+        neighbors: Vec<Vec<usize>>,
+        pause_duration: f32,
+        speed_factor: f32,
+    ) -> Self {
+        let target = points.first().copied().unwrap_or(Vec3::ZERO);
+        Self {
+            mode: PatrolMode::Route {
+                points,
+                neighbors,
+                current: 0,
+                previous: None,
+            },
+            target,
+            pause_duration,
+            pause_remaining: 0.0,
+            speed_factor,
+            leg_elapsed: 0.0,
+        }
+    }
+
+    /// Wander around `center`, starting by holding position
+    pub fn wander(center: Vec3, radius: f32, pause_duration: f32, speed_factor: f32) -> Self {
+        Self {
+            mode: PatrolMode::Wander { center, radius },
+            target: center,
+            pause_duration,
+            pause_remaining: pause_duration,
+            speed_factor,
+            leg_elapsed: 0.0,
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.pause_remaining > 0.0
+    }
+
+    /// Start the pause and choose the next destination
+    pub fn arrive(&mut self, rng: &mut impl rand::Rng, is_walkable: impl Fn(Vec3) -> bool) {
+        self.pause_remaining = self.pause_duration;
+        self.leg_elapsed = 0.0;
+
+        match &mut self.mode {
+            PatrolMode::Route {
+                points,
+                neighbors,
+                current,
+                previous,
+            } => {
+                let options = &neighbors[*current];
+                let forward: Vec<usize> = options
+                    .iter()
+                    .copied()
+                    .filter(|&n| Some(n) != *previous)
+                    .collect();
+                let candidates = if forward.is_empty() {
+                    options
+                } else {
+                    &forward
+                };
+                if let Some(&next) = candidates.get(rng.gen_range(0..candidates.len().max(1))) {
+                    *previous = Some(*current);
+                    *current = next;
+                    self.target = points[next];
+                }
+            }
+            PatrolMode::Wander { center, radius } => {
+                self.target = (0..8)
+                    .map(|_| {
+                        let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+                        let distance = radius.max(0.0) * rng.gen_range(0.0f32..1.0).sqrt();
+                        *center + Vec3::new(angle.cos(), 0.0, angle.sin()) * distance
+                    })
+                    .find(|&candidate| is_walkable(candidate))
+                    .unwrap_or(*center);
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -853,4 +997,53 @@ mod tests {
         assert!(!agent.has_path());
         assert_eq!(agent.current_waypoint(), None);
     }
+
+    #[test]
+    fn test_pathfinding_agent_preferred_velocity() {
+        let mut agent = PathfindingAgent::new();
+        assert_eq!(agent.max_speed, 0.0, "New agents are not steered");
+
+        // Vertical component is dropped; avoidance works in the XZ plane
+        agent.set_preferred_velocity(Vec3::new(3.0, -9.8, 4.0), 5.0);
+        assert_eq!(agent.preferred_velocity, Vec3::new(3.0, 0.0, 4.0));
+        assert_eq!(agent.max_speed, 5.0);
+    }
+
+    #[test]
+    fn test_patrol_route_avoids_doubling_back() {
+        use rand::SeedableRng;
+        let mut rng = rand_pcg::Pcg64::seed_from_u64(1);
+        let points = vec![Vec3::ZERO, Vec3::X, Vec3::Z];
+        // Line graph 0 - 1 - 2
+        let neighbors = vec![vec![1], vec![0, 2], vec![1]];
+        let mut patrol = Patrol::route(points, neighbors, 1.5, 0.5);
+        assert_eq!(patrol.target, Vec3::ZERO);
+
+        let mut visited = Vec::new();
+        for _ in 0..4 {
+            patrol.arrive(&mut rng, |_| true);
+            visited.push(patrol.target);
+        }
+        assert_eq!(visited, vec![Vec3::X, Vec3::Z, Vec3::X, Vec3::ZERO]);
+        assert_eq!(patrol.pause_remaining, 1.5);
+    }
+
+    #[test]
+    fn test_patrol_wander_stays_in_area() {
+        use rand::SeedableRng;
+        let mut rng = rand_pcg::Pcg64::seed_from_u64(2);
+        let center = Vec3::new(10.0, 0.0, -4.0);
+        let mut patrol = Patrol::wander(center, 3.0, 2.0, 0.5);
+        assert!(patrol.is_paused(), "Wanderers start by idling");
+
+        for _ in 0..20 {
+            patrol.arrive(&mut rng, |p| p.x >= 10.0);
+            assert!(patrol.target.distance(center) <= 3.0 + 1e-4);
+            assert!(patrol.target.x >= 10.0);
+        }
+
+        // Nothing walkable falls back to the center
+        patrol.arrive(&mut rng, |_| false);
+        assert_eq!(patrol.target, center);
+    }
 }
@@ -79,6 +79,7 @@ pub fn calculate_flocking_forces(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::map::ZoneStats;
 
     #[test]
     fn test_enemy_spawn_position_deterministic() {
@@ -87,6 +88,8 @@ mod tests {
             radius: 5.0,
             max_enemies: 8,
             enemy_types: vec!["dark-knight".to_string()],
+            tier: 0,
+            stats: ZoneStats::default(),
         };
 
         let pos1 = calculate_enemy_spawn_position(&spawn_zone, 0);
@@ -107,6 +110,8 @@ mod tests {
             radius: 10.0,
             max_enemies: 20,
             enemy_types: vec!["dark-knight".to_string()],
+            tier: 0,
+            stats: ZoneStats::default(),
         };
 
         for i in 0..20 {
@@ -131,6 +136,8 @@ mod tests {
             radius: 10.0,
             max_enemies: 16,
             enemy_types: vec!["dark-knight".to_string()],
+            tier: 0,
+            stats: ZoneStats::default(),
         };
 
         let positions: Vec<Vec3> = (0..16)
@@ -240,6 +247,8 @@ mod tests {
             radius: 8.0,
             max_enemies: 50,
             enemy_types: vec!["dark-knight".to_string()],
+            tier: 0,
+            stats: ZoneStats::default(),
         };
 
         for i in 0..50 {
@@ -1,10 +1,12 @@
 use crate::components::*;
+use crate::game_logic::errors::{MinionError, MinionResult};
 use crate::game_logic::names::generate_dark_name;
 use crate::map::SpawnZone;
 use crate::resources::*;
 use bevy::prelude::*;
 use bevy_rapier3d::prelude::*;
 use std::f32::consts::TAU;
+use std::path::{Path, PathBuf};
 
 /// Generate a random position within a spawn zone using a counter for deterministic placement
 pub fn generate_zone_position(zone: &SpawnZone, counter: u32) -> Vec3 {
@@ -20,18 +22,76 @@ pub fn generate_zone_position(zone: &SpawnZone, counter: u32) -> Vec3 {
     )
 }
 
+/// Enemy type used when there is no zone or the zone lists none
+pub const DEFAULT_ENEMY_TYPE: &str = "dark-knight";
+
+/// LOD suffixes every enemy type ships a scene for, highest detail first
+pub const ENEMY_LOD_SUFFIXES: [&str; 3] = ["high", "med", "low"];
+
+/// Scene path of one LOD of an enemy type, relative to the enemies directory
+pub fn enemy_scene_file(enemy_type: &str, lod_suffix: &str) -> String {
+    format!("{enemy_type}-{lod_suffix}.glb")
+}
+
+/// Get the enemy scenes directory
+pub fn get_enemies_dir() -> MinionResult<PathBuf> {
+    std::env::current_dir()
+        .map_err(MinionError::ConfigDirCreationFailed)
+        .map(|dir| dir.join("assets").join("enemies"))
+}
+
+/// Whether `dir` holds a scene for every LOD of `enemy_type`
+pub fn has_enemy_scenes(dir: &Path, enemy_type: &str) -> bool {
+    ENEMY_LOD_SUFFIXES
+        .iter()
+        .all(|suffix| dir.join(enemy_scene_file(enemy_type, suffix)).is_file())
+}
+
+/// Pick the enemy type for the `counter`-th spawn in a zone, cycling through its roster
+pub fn zone_enemy_type(zone: Option<&SpawnZone>, counter: u32) -> &str {
+    zone.filter(|zone| !zone.enemy_types.is_empty())
+        .map(|zone| zone.enemy_types[counter as usize % zone.enemy_types.len()].as_str())
+        .unwrap_or(DEFAULT_ENEMY_TYPE)
+}
+
+/// Build the enemy stats for a zone, scaled by the multipliers stored with it
+pub fn zone_enemy(zone: Option<&SpawnZone>, game_config: &GameConfig) -> Enemy {
+    let stats = zone.map(|zone| zone.stats).unwrap_or_default();
+    let settings = &game_config.settings;
+
+    Enemy {
+        speed: Speed::new(settings.enemy_movement_speed.get() * stats.speed_scale),
+        health: HealthPool::new_full(settings.enemy_max_health.get() * stats.health_scale),
+        mana: ManaPool::new_full(settings.enemy_max_mana.get()),
+        energy: EnergyPool::new_full(settings.enemy_max_energy.get()),
+        chase_distance: Distance::new(settings.enemy_chase_distance.get()),
+        is_dying: false,
+    }
+}
+
 /// Spawn a single enemy entity with all required components
-/// This function eliminates duplication between initial spawning and respawning logic
+/// This function eliminates duplication between initial spawning and respawning logic.
+/// The zone, if any, picks the enemy type and scales its stats by tier.
 pub fn spawn_enemy_entity(
     commands: &mut Commands,
     asset_server: &Res<AssetServer>,
     position: Vec3,
+    zone: Option<&SpawnZone>,
+    counter: u32,
     game_config: &GameConfig,
 ) {
-    // Load all LOD levels for enemies
-    let high_scene = asset_server.load("enemies/dark-knight-high.glb#Scene0");
-    let med_scene = asset_server.load("enemies/dark-knight-med.glb#Scene0");
-    let low_scene = asset_server.load("enemies/dark-knight-low.glb#Scene0");
+    // Load all LOD levels for enemies, falling back when a type has no scenes
+    let mut enemy_type = zone_enemy_type(zone, counter);
+    if !get_enemies_dir().is_ok_and(|dir| has_enemy_scenes(&dir, enemy_type)) {
+        warn!("No scenes for enemy type '{enemy_type}', spawning {DEFAULT_ENEMY_TYPE}");
+        enemy_type = DEFAULT_ENEMY_TYPE;
+    }
+    let [high_scene, med_scene, low_scene] = ENEMY_LOD_SUFFIXES.map(|suffix| {
+        asset_server.load(format!(
+            "enemies/{}#Scene0",
+            enemy_scene_file(enemy_type, suffix)
+        ))
+    });
 
     // Inline LOD level determination with fallback
     let starting_level =
@@ -56,14 +116,7 @@ pub fn spawn_enemy_entity(
             apply_impulse_to_dynamic_bodies: true, // Better physics interaction
             ..default()
         },
-        Enemy {
-            speed: Speed::new(game_config.settings.enemy_movement_speed.get()),
-            health: HealthPool::new_full(game_config.settings.enemy_max_health.get()),
-            mana: ManaPool::new_full(game_config.settings.enemy_max_mana.get()),
-            energy: EnergyPool::new_full(game_config.settings.enemy_max_energy.get()),
-            chase_distance: Distance::new(game_config.settings.enemy_chase_distance.get()),
-            is_dying: false,
-        },
+        zone_enemy(zone, game_config),
         PathfindingAgent::default(),
         LodEntity {
             current_level: starting_level,
@@ -79,6 +132,7 @@ pub fn spawn_enemy_entity(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::map::ZoneStats;
 
     #[test]
     fn test_zone_position_generation() {
@@ -87,6 +141,8 @@ mod tests {
             radius: 5.0,
             max_enemies: 10,
             enemy_types: vec!["dark-knight".to_string()],
+            tier: 0,
+            stats: ZoneStats::default(),
         };
 
         let pos1 = generate_zone_position(&zone, 0);
@@ -118,6 +174,8 @@ mod tests {
             radius: 10.0,
             max_enemies: 8,
             enemy_types: vec!["dark-knight".to_string()],
+            tier: 0,
+            stats: ZoneStats::default(),
         };
 
         let positions: Vec<Vec3> = (0..8).map(|i| generate_zone_position(&zone, i)).collect();
@@ -158,6 +216,47 @@ mod tests {
         assert_eq!(game_config.settings.enemy_max_health.get(), 3.0); // Default health
     }
 
+    #[test]
+    fn test_deeper_zones_spawn_stronger_enemies() {
+        let game_config = GameConfig::default();
+        let zone = |tier, health_scale, speed_scale| {
+            SpawnZone::new(
+                Vec3::ZERO,
+                5.0,
+                4,
+                vec!["dark-knight".to_string(), "dark-champion".to_string()],
+            )
+            .unwrap()
+            .with_tier(tier)
+            .with_stats(ZoneStats {
+                health_scale,
+                speed_scale,
+            })
+        };
+        let (easy_zone, hard_zone) = (zone(0, 1.0, 1.0), zone(3, 2.5, 1.3));
+
+        let easy = zone_enemy(Some(&easy_zone), &game_config);
+        let hard = zone_enemy(Some(&hard_zone), &game_config);
+
+        assert_eq!(easy.health.max, game_config.settings.enemy_max_health.get());
+        assert!(hard.health.max > easy.health.max);
+        assert!(hard.speed.0 > easy.speed.0);
+
+        // Spawns cycle through the zone's roster
+        assert_eq!(zone_enemy_type(Some(&hard_zone), 0), "dark-knight");
+        assert_eq!(zone_enemy_type(Some(&hard_zone), 1), "dark-champion");
+        assert_eq!(zone_enemy_type(None, 1), DEFAULT_ENEMY_TYPE);
+    }
+
+    #[test]
+    fn test_enemy_scene_lookup() {
+        let dir = get_enemies_dir().unwrap();
+
+        assert!(has_enemy_scenes(&dir, DEFAULT_ENEMY_TYPE));
+        assert!(!has_enemy_scenes(&dir, "dark-champion"));
+        assert_eq!(enemy_scene_file("goblin", "med"), "goblin-med.glb");
+    }
+
     #[test]
     fn test_spawn_enemy_lod_level_selection() {
         let mut game_config = GameConfig::default();
@@ -2,6 +2,7 @@ pub mod components;
 pub mod config;
 pub mod game_logic;
 pub mod map;
+pub mod objects;
 pub mod pathfinding;
 pub mod plugins;
 pub mod resources;
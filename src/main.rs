@@ -23,6 +23,7 @@ fn main() {
             EnemyPlugin,
             CombatPlugin,
             TooltipPlugin,
+            PathfindingDebugPlugin,
         ))
         .run();
 }
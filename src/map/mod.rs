@@ -1,9 +1,20 @@
 use crate::game_logic::errors::{MinionError, MinionResult};
+use crate::pathfinding::BakedNavigation;
+use crate::terrain::biomes::BiomeMap;
 use bevy::prelude::*;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use validator::Validate;
 
+pub mod patrol;
+pub mod roads;
+pub mod water;
+
+pub use patrol::{MapPatrols, PatrolRouteDefinition};
+pub use roads::{RoadSurface, road_surface_map};
+pub use water::{WaterBody, WaterKind, water_depth_map, water_surface_map};
+
 /// Core map definition containing all map data
 #[derive(Debug, Clone, Serialize, Deserialize, Validate, Resource)]
 pub struct MapDefinition {
@@ -12,6 +23,15 @@ pub struct MapDefinition {
     pub player_spawn: Vec3,
     pub enemy_zones: Vec<SpawnZone>,
     pub environment_objects: Vec<EnvironmentObject>,
+    /// Stored in a trailing file section so maps without water keep their layout
+    #[serde(skip)]
+    pub water_bodies: Vec<WaterBody>,
+    /// Carved roads, also stored in a trailing section
+    #[serde(skip)]
+    pub road_surfaces: Vec<RoadSurface>,
+    /// Biome blends used to paint the terrain, also stored in a trailing section
+    #[serde(skip)]
+    pub biome_map: Option<BiomeMap>,
 }
 
 /// Terrain heightmap data for procedural terrain generation
@@ -35,6 +55,28 @@ pub struct SpawnZone {
     #[validate(range(min = 1, max = 100))]
     pub max_enemies: u32,
     pub enemy_types: Vec<String>, // ["dark-knight", etc.]
+    /// Difficulty tier, 0 is the easiest; stored in a trailing section
+    #[serde(skip)]
+    pub tier: u32,
+    /// Enemy stat multipliers for this zone's tier, also in a trailing section
+    #[serde(skip)]
+    pub stats: ZoneStats,
+}
+
+/// Multipliers applied to the stats of enemies spawned in a zone
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ZoneStats {
+    pub health_scale: f32,
+    pub speed_scale: f32,
+}
+
+impl Default for ZoneStats {
+    fn default() -> Self {
+        Self {
+            health_scale: 1.0,
+            speed_scale: 1.0,
+        }
+    }
 }
 
 /// Environment objects (trees, rocks, decorations, etc.)
@@ -46,6 +88,112 @@ pub struct EnvironmentObject {
     pub scale: Vec3,
 }
 
+/// Marks the optional baked navigation section that follows the map data
+const NAVIGATION_SECTION_MAGIC: &[u8; 4] = b"MNAV";
+
+/// Marks the optional water body section that follows the map data
+const WATER_SECTION_MAGIC: &[u8; 4] = b"MWAT";
+
+/// Marks the optional road surface section that follows the map data
+const ROAD_SECTION_MAGIC: &[u8; 4] = b"MROD";
+
+/// Marks the optional biome map section that follows the map data
+const BIOME_SECTION_MAGIC: &[u8; 4] = b"MBIO";
+
+/// Marks the optional spawn zone tier section that follows the map data
+const ZONE_TIER_SECTION_MAGIC: &[u8; 4] = b"MTIR";
+
+/// Marks the optional per-zone enemy stat section that follows the map data
+const ZONE_STATS_SECTION_MAGIC: &[u8; 4] = b"MZST";
+
+/// Optional data stored after the map blob; older files simply have none
+#[derive(Default)]
+struct MapSections {
+    navigation: Option<BakedNavigation>,
+    water_bodies: Vec<WaterBody>,
+    road_surfaces: Vec<RoadSurface>,
+    biome_map: Option<BiomeMap>,
+    zone_tiers: Vec<u32>,
+    zone_stats: Vec<ZoneStats>,
+}
+
+fn decode_section<T: DeserializeOwned>(body: &[u8], name: &str) -> Option<(T, usize)> {
+    match bincode::serde::decode_from_slice(body, bincode::config::standard()) {
+        Ok(decoded) => Some(decoded),
+        Err(e) => {
+            warn!("Ignoring unreadable {name} section: {e}");
+            None
+        }
+    }
+}
+
+/// Decode the trailing sections, stopping at the first one that cannot be read
+fn decode_sections(mut trailing: &[u8]) -> MapSections {
+    let mut sections = MapSections::default();
+
+    while trailing.len() > 4 {
+        let (magic, body) = trailing.split_at(4);
+        let consumed = if magic == NAVIGATION_SECTION_MAGIC {
+            decode_section(body, "baked navigation").map(|(navigation, len)| {
+                sections.navigation = Some(navigation);
+                len
+            })
+        } else if magic == WATER_SECTION_MAGIC {
+            decode_section(body, "water").map(|(water_bodies, len)| {
+                sections.water_bodies = water_bodies;
+                len
+            })
+        } else if magic == ROAD_SECTION_MAGIC {
+            decode_section(body, "road").map(|(road_surfaces, len)| {
+                sections.road_surfaces = road_surfaces;
+                len
+            })
+        } else if magic == BIOME_SECTION_MAGIC {
+            decode_section(body, "biome").map(|(biome_map, len)| {
+                sections.biome_map = Some(biome_map);
+                len
+            })
+        } else if magic == ZONE_TIER_SECTION_MAGIC {
+            decode_section(body, "zone tier").map(|(zone_tiers, len)| {
+                sections.zone_tiers = zone_tiers;
+                len
+            })
+        } else if magic == ZONE_STATS_SECTION_MAGIC {
+            decode_section(body, "zone stats").map(|(zone_stats, len)| {
+                sections.zone_stats = zone_stats;
+                len
+            })
+        } else {
+            warn!("Ignoring unknown map section {magic:?}");
+            None
+        };
+
+        let Some(consumed) = consumed else {
+            break;
+        };
+        trailing = &body[consumed..];
+    }
+
+    sections
+}
+
+fn encode_section<T: Serialize>(
+    data: &mut Vec<u8>,
+    magic: &[u8; 4],
+    value: &T,
+    name: &str,
+) -> MinionResult<()> {
+    let section =
+        bincode::serde::encode_to_vec(value, bincode::config::standard()).map_err(|e| {
+            MinionError::InvalidMapData {
+                reason: format!("Failed to serialize {name}: {e}"),
+            }
+        })?;
+    data.extend_from_slice(magic);
+    data.extend_from_slice(&section);
+    Ok(())
+}
+
 impl MapDefinition {
     /// Create a new map definition with validation
     pub fn new(
@@ -61,6 +209,9 @@ impl MapDefinition {
             player_spawn,
             enemy_zones,
             environment_objects,
+            water_bodies: Vec::new(),
+            road_surfaces: Vec::new(),
+            biome_map: None,
         };
 
         map.validate().map_err(|_| MinionError::InvalidMapData {
@@ -79,6 +230,13 @@ impl MapDefinition {
 
     /// Load a map from the maps directory
     pub fn load_from_file<P: AsRef<Path>>(filename: P) -> MinionResult<Self> {
+        Self::load_with_navigation(filename).map(|(map, _)| map)
+    }
+
+    /// Load a map and its baked navigation section, if the file has one
+    pub fn load_with_navigation<P: AsRef<Path>>(
+        filename: P,
+    ) -> MinionResult<(Self, Option<BakedNavigation>)> {
         let maps_dir = Self::get_maps_dir()?;
         let file_path = maps_dir.join(filename);
 
@@ -88,7 +246,7 @@ impl MapDefinition {
 
         let data = std::fs::read(&file_path).map_err(MinionError::ConfigDirCreationFailed)?;
 
-        let (map, _): (MapDefinition, usize) =
+        let (mut map, map_len): (MapDefinition, usize) =
             bincode::serde::decode_from_slice(&data, bincode::config::standard()).map_err(|e| {
                 MinionError::CorruptedMapFile {
                     reason: format!("Failed to deserialize map data: {e}"),
@@ -112,11 +270,38 @@ impl MapDefinition {
             }
         })?;
 
-        Ok(map)
+        let sections = decode_sections(&data[map_len..]);
+        map.water_bodies = sections.water_bodies;
+        map.road_surfaces = sections.road_surfaces;
+        map.biome_map = sections.biome_map;
+        if sections.zone_tiers.len() == map.enemy_zones.len() {
+            for (zone, tier) in map.enemy_zones.iter_mut().zip(sections.zone_tiers) {
+                zone.tier = tier;
+            }
+        } else if !sections.zone_tiers.is_empty() {
+            warn!("Ignoring zone tiers that do not match the spawn zones");
+        }
+        if sections.zone_stats.len() == map.enemy_zones.len() {
+            for (zone, stats) in map.enemy_zones.iter_mut().zip(sections.zone_stats) {
+                zone.stats = stats;
+            }
+        } else if !sections.zone_stats.is_empty() {
+            warn!("Ignoring zone stats that do not match the spawn zones");
+        }
+        Ok((map, sections.navigation))
     }
 
     /// Save the map to the maps directory
     pub fn save_to_file<P: AsRef<Path>>(&self, filename: P) -> MinionResult<()> {
+        self.save_with_navigation(filename, None)
+    }
+
+    /// Save the map, appending baked navigation data as an optional trailing section
+    pub fn save_with_navigation<P: AsRef<Path>>(
+        &self,
+        filename: P,
+        navigation: Option<&BakedNavigation>,
+    ) -> MinionResult<()> {
         // Validate before saving
         self.validate().map_err(|_| MinionError::InvalidMapData {
             reason: "Map validation failed before save".to_string(),
@@ -130,16 +315,62 @@ impl MapDefinition {
             std::fs::create_dir_all(parent).map_err(MinionError::ConfigDirCreationFailed)?;
         }
 
-        let data =
+        let data = self.to_bytes(navigation)?;
+        std::fs::write(&file_path, data).map_err(MinionError::ConfigDirCreationFailed)?;
+
+        Ok(())
+    }
+
+    /// Encode the map and its trailing sections exactly as `save_with_navigation` writes them
+    pub fn to_bytes(&self, navigation: Option<&BakedNavigation>) -> MinionResult<Vec<u8>> {
+        let mut data =
             bincode::serde::encode_to_vec(self, bincode::config::standard()).map_err(|e| {
                 MinionError::InvalidMapData {
                     reason: format!("Failed to serialize map: {e}"),
                 }
             })?;
 
-        std::fs::write(&file_path, data).map_err(MinionError::ConfigDirCreationFailed)?;
+        if !self.water_bodies.is_empty() {
+            encode_section(
+                &mut data,
+                WATER_SECTION_MAGIC,
+                &self.water_bodies,
+                "water bodies",
+            )?;
+        }
+        if !self.road_surfaces.is_empty() {
+            encode_section(
+                &mut data,
+                ROAD_SECTION_MAGIC,
+                &self.road_surfaces,
+                "road surfaces",
+            )?;
+        }
+        if let Some(biome_map) = &self.biome_map {
+            encode_section(&mut data, BIOME_SECTION_MAGIC, biome_map, "biome map")?;
+        }
+        if self.enemy_zones.iter().any(|zone| zone.tier != 0) {
+            let tiers: Vec<u32> = self.enemy_zones.iter().map(|zone| zone.tier).collect();
+            encode_section(&mut data, ZONE_TIER_SECTION_MAGIC, &tiers, "zone tiers")?;
+        }
+        if self
+            .enemy_zones
+            .iter()
+            .any(|zone| zone.stats != ZoneStats::default())
+        {
+            let stats: Vec<ZoneStats> = self.enemy_zones.iter().map(|zone| zone.stats).collect();
+            encode_section(&mut data, ZONE_STATS_SECTION_MAGIC, &stats, "zone stats")?;
+        }
+        if let Some(navigation) = navigation {
+            encode_section(
+                &mut data,
+                NAVIGATION_SECTION_MAGIC,
+                navigation,
+                "baked navigation",
+            )?;
+        }
 
-        Ok(())
+        Ok(data)
     }
 
     /// Get the height at a specific grid position
@@ -214,6 +445,8 @@ impl SpawnZone {
             radius,
             max_enemies,
             enemy_types,
+            tier: 0,
+            stats: ZoneStats::default(),
         };
 
         zone.validate().map_err(|_| MinionError::InvalidMapData {
@@ -222,6 +455,18 @@ impl SpawnZone {
 
         Ok(zone)
     }
+
+    /// Set the difficulty tier
+    pub fn with_tier(mut self, tier: u32) -> Self {
+        self.tier = tier;
+        self
+    }
+
+    /// Set the enemy stat multipliers
+    pub fn with_stats(mut self, stats: ZoneStats) -> Self {
+        self.stats = stats;
+        self
+    }
 }
 
 impl EnvironmentObject {
@@ -277,6 +522,9 @@ mod tests {
             player_spawn: Vec3::ZERO,
             enemy_zones: vec![],
             environment_objects: vec![],
+            water_bodies: vec![],
+            road_surfaces: vec![],
+            biome_map: None,
         };
 
         assert_eq!(map.get_height_at_grid(0, 0), Some(0.0));
@@ -308,6 +556,9 @@ mod tests {
             player_spawn: Vec3::ZERO,
             enemy_zones: vec![],
             environment_objects: vec![],
+            water_bodies: vec![],
+            road_surfaces: vec![],
+            biome_map: None,
         };
 
         // Test center position: world (0,0) should map to grid (1.5, 1.5)
@@ -377,4 +628,53 @@ mod tests {
         assert_eq!(map.name, "test_map");
         assert_eq!(map.enemy_zones.len(), 1);
     }
+
+    #[test]
+    fn test_trailing_section_decoding() {
+        use crate::pathfinding::{NavigationGrid, PathfindingConfig};
+
+        // Files written before baking existed have nothing after the map
+        assert!(decode_sections(&[]).navigation.is_none());
+        assert!(decode_sections(b"MNAV\xff\xff").navigation.is_none());
+
+        let terrain = TerrainData::create_flat(8, 8, 1.0, 0.0).unwrap();
+        let grid = NavigationGrid::from_terrain(&terrain, PathfindingConfig::default()).unwrap();
+        let baked = grid.bake(42, &[0.5]);
+        let mut lake = WaterBody::new(WaterKind::Lake);
+        lake.push(3, 1.5);
+        let road = RoadSurface {
+            path_type: crate::terrain::path_generator::PathType::MainRoad,
+            cells: vec![4, 5],
+        };
+
+        let mut data = Vec::new();
+        encode_section(&mut data, WATER_SECTION_MAGIC, &vec![lake.clone()], "water").unwrap();
+        encode_section(&mut data, ROAD_SECTION_MAGIC, &vec![road.clone()], "roads").unwrap();
+        let biomes = BiomeMap::uniform(8, 8, crate::terrain::biomes::BiomeType::Desert, 1.0);
+        encode_section(&mut data, BIOME_SECTION_MAGIC, &biomes, "biomes").unwrap();
+        encode_section(&mut data, ZONE_TIER_SECTION_MAGIC, &vec![0u32, 2], "tiers").unwrap();
+        let hard = ZoneStats {
+            health_scale: 2.0,
+            speed_scale: 1.2,
+        };
+        let stats = vec![ZoneStats::default(), hard];
+        encode_section(&mut data, ZONE_STATS_SECTION_MAGIC, &stats, "stats").unwrap();
+        encode_section(&mut data, NAVIGATION_SECTION_MAGIC, &baked, "navigation").unwrap();
+
+        let sections = decode_sections(&data);
+        let decoded = sections.navigation.expect("Section should decode");
+        assert_eq!(decoded.source_hash, 42);
+        assert_eq!(decoded.walkable, baked.walkable);
+        assert_eq!(decoded.inflations, baked.inflations);
+        assert_eq!(sections.water_bodies, vec![lake]);
+        assert_eq!(sections.road_surfaces, vec![road]);
+        assert_eq!(sections.zone_tiers, vec![0, 2]);
+        assert_eq!(sections.zone_stats, stats);
+        let decoded_biomes = sections.biome_map.expect("Biome section should decode");
+        assert_eq!(decoded_biomes.blends.len(), 64);
+        assert_eq!(
+            decoded_biomes.blends[10].dominant_biome(),
+            Some(crate::terrain::biomes::BiomeType::Desert)
+        );
+    }
 }
@@ -0,0 +1,229 @@
+//! Patrol routes and wander areas for idle enemies.
+//!
+//! Routes live in a TOML file next to the map binary, e.g. `hills.bin` reads
+//! `hills.patrol.toml`. Spawn zones without a route wander inside their radius.
+//!
+//! ```toml
+//! wander_pause = 3.0
+//!
+//! [[route]]
+//! zone = 0
+//! points = [[0.0, 0.0, 0.0], [10.0, 0.0, 0.0], [10.0, 0.0, 10.0]]
+//! pause = 2.0
+//! looped = true
+//! # Optional waypoint graph; without links points are visited in order
+//! links = [[0, 1], [1, 2], [2, 0]]
+//! ```
+
+use crate::game_logic::errors::{MinionError, MinionResult};
+use crate::map::MapDefinition;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+fn default_pause() -> f32 {
+    2.0
+}
+
+fn default_looped() -> bool {
+    true
+}
+
+fn default_speed_factor() -> f32 {
+    0.5
+}
+
+/// A patrol route shared by the enemies of one spawn zone
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatrolRouteDefinition {
+    /// Index into `MapDefinition::enemy_zones`
+    pub zone: usize,
+    pub points: Vec<[f32; 3]>,
+    /// Waypoint graph edges; empty means visit points in order
+    #[serde(default)]
+    pub links: Vec<[usize; 2]>,
+    /// Whether an ordered route wraps from the last point back to the first
+    #[serde(default = "default_looped")]
+    pub looped: bool,
+    /// Seconds to wait at each waypoint
+    #[serde(default = "default_pause")]
+    pub pause: f32,
+}
+
+impl PatrolRouteDefinition {
+    /// Waypoint positions in world space
+    pub fn positions(&self) -> Vec<Vec3> {
+        self.points.iter().copied().map(Vec3::from_array).collect()
+    }
+
+    /// Adjacency list for the waypoint graph
+    pub fn neighbors(&self) -> Vec<Vec<usize>> {
+        let count = self.points.len();
+        let mut neighbors = vec![Vec::new(); count];
+
+        let mut link = |a: usize, b: usize| {
+            if a != b && !neighbors[a].contains(&b) {
+                neighbors[a].push(b);
+                neighbors[b].push(a);
+            }
+        };
+
+        if self.links.is_empty() {
+            for i in 1..count {
+                link(i - 1, i);
+            }
+            if self.looped && count > 2 {
+                link(count - 1, 0);
+            }
+        } else {
+            for &[a, b] in &self.links {
+                link(a, b);
+            }
+        }
+
+        neighbors
+    }
+}
+
+/// Patrol data for the loaded map
+#[derive(Debug, Clone, Serialize, Deserialize, Resource)]
+pub struct MapPatrols {
+    /// Seconds wandering enemies wait between destinations
+    #[serde(default = "default_pause")]
+    pub wander_pause: f32,
+    /// Fraction of chase speed used while patrolling
+    #[serde(default = "default_speed_factor")]
+    pub speed_factor: f32,
+    #[serde(default, rename = "route")]
+    pub routes: Vec<PatrolRouteDefinition>,
+}
+
+impl Default for MapPatrols {
+    fn default() -> Self {
+        Self {
+            wander_pause: default_pause(),        // Brief look around
+            speed_factor: default_speed_factor(), // Stroll at half speed
+            routes: Vec::new(),                   // Every zone wanders
+        }
+    }
+}
+
+impl MapPatrols {
+    /// Path of the patrol file that accompanies a map file
+    pub fn sidecar_path(map_file: &str) -> MinionResult<PathBuf> {
+        let map_path = MapDefinition::get_maps_dir()?.join(map_file);
+        Ok(map_path.with_extension("patrol.toml"))
+    }
+
+    /// Load the patrol file for a map, or defaults if the map has none
+    pub fn load_for_map(map_file: &str) -> MinionResult<Self> {
+        let path = Self::sidecar_path(map_file)?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content =
+            std::fs::read_to_string(&path).map_err(MinionError::ConfigDirCreationFailed)?;
+        Self::from_toml_str(&content)
+    }
+
+    /// Parse and validate patrol data
+    pub fn from_toml_str(content: &str) -> MinionResult<Self> {
+        let patrols: Self = toml::from_str(content)?;
+
+        if patrols.wander_pause < 0.0 || patrols.speed_factor <= 0.0 {
+            return Err(MinionError::InvalidConfig {
+                reason: "Patrol pause must be non-negative and speed factor positive".to_string(),
+            });
+        }
+
+        for route in &patrols.routes {
+            if route.points.is_empty() {
+                return Err(MinionError::InvalidConfig {
+                    reason: format!("Patrol route for zone {} has no points", route.zone),
+                });
+            }
+            if route.pause < 0.0 {
+                return Err(MinionError::InvalidConfig {
+                    reason: format!("Patrol route for zone {} has a negative pause", route.zone),
+                });
+            }
+            let out_of_range = route
+                .links
+                .iter()
+                .flatten()
+                .find(|&&index| index >= route.points.len());
+            if let Some(index) = out_of_range {
+                return Err(MinionError::InvalidConfig {
+                    reason: format!(
+                        "Patrol route for zone {} links to missing point {}",
+                        route.zone, index
+                    ),
+                });
+            }
+        }
+
+        Ok(patrols)
+    }
+
+    /// Route assigned to a spawn zone, if any
+    pub fn route_for_zone(&self, zone: usize) -> Option<&PatrolRouteDefinition> {
+        self.routes.iter().find(|route| route.zone == zone)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_patrols() {
+        let patrols = MapPatrols::from_toml_str(
+            r#"
+            wander_pause = 4.0
+
+            [[route]]
+            zone = 1
+            points = [[0.0, 0.0, 0.0], [5.0, 0.0, 0.0]]
+            pause = 1.0
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(patrols.wander_pause, 4.0);
+        assert_eq!(patrols.speed_factor, 0.5);
+        let route = patrols.route_for_zone(1).unwrap();
+        assert!(route.looped);
+        assert_eq!(route.positions()[1], Vec3::new(5.0, 0.0, 0.0));
+        assert!(patrols.route_for_zone(0).is_none());
+    }
+
+    #[test]
+    fn test_invalid_patrols_rejected() {
+        let empty = "[[route]]\nzone = 0\npoints = []";
+        assert!(MapPatrols::from_toml_str(empty).is_err());
+
+        let bad_link = "[[route]]\nzone = 0\npoints = [[0.0, 0.0, 0.0]]\nlinks = [[0, 3]]";
+        assert!(MapPatrols::from_toml_str(bad_link).is_err());
+    }
+
+    #[test]
+    fn test_route_neighbors() {
+        let mut route = PatrolRouteDefinition {
+            zone: 0,
+            points: vec![[0.0; 3]; 4],
+            links: Vec::new(),
+            looped: true,
+            pause: 0.0,
+        };
+        assert_eq!(route.neighbors()[0], vec![1, 3]);
+
+        // Open routes ping-pong between their ends
+        route.looped = false;
+        assert_eq!(route.neighbors()[0], vec![1]);
+        assert_eq!(route.neighbors()[3], vec![2]);
+
+        // Explicit links form a graph
+        route.links = vec![[0, 2], [2, 3], [2, 1]];
+        assert_eq!(route.neighbors()[2], vec![0, 3, 1]);
+    }
+}
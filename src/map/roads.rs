@@ -0,0 +1,53 @@
+//! Road surfaces recorded on a map.
+//!
+//! Carved roads and trails mark the heightmap samples they cover so the
+//! renderer can paint them and navigation can make them cheaper to walk.
+
+use crate::map::TerrainData;
+use crate::terrain::path_generator::PathType;
+use serde::{Deserialize, Serialize};
+
+/// Heightmap samples paved by one path
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoadSurface {
+    pub path_type: PathType,
+    /// Heightmap indices (row-major) covered by the road
+    pub cells: Vec<u32>,
+}
+
+/// Road type at each heightmap sample, `None` off-road
+pub fn road_surface_map(terrain: &TerrainData, roads: &[RoadSurface]) -> Vec<Option<PathType>> {
+    let mut surfaces = vec![None; terrain.heights.len()];
+    for road in roads {
+        for &cell in &road.cells {
+            if let Some(slot) = surfaces.get_mut(cell as usize) {
+                *slot = Some(road.path_type);
+            }
+        }
+    }
+    surfaces
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_road_surface_map() {
+        let terrain = TerrainData::create_flat(3, 1, 1.0, 0.0).unwrap();
+        let trail = RoadSurface {
+            path_type: PathType::Trail,
+            cells: vec![0, 1, 7],
+        };
+        let road = RoadSurface {
+            path_type: PathType::MainRoad,
+            cells: vec![1],
+        };
+
+        // Later roads win and out of range cells are ignored
+        assert_eq!(
+            road_surface_map(&terrain, &[trail, road]),
+            vec![Some(PathType::Trail), Some(PathType::MainRoad), None]
+        );
+    }
+}
@@ -0,0 +1,94 @@
+//! Rivers, lakes, and seas recorded on a map.
+//!
+//! Water is stored per heightmap sample: each body lists the samples it
+//! covers and the water surface height at each one, so rivers can slope
+//! downhill while lakes and seas stay level.
+
+use crate::map::TerrainData;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WaterKind {
+    River,
+    Lake,
+    Ocean,
+}
+
+/// A connected body of water
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WaterBody {
+    pub kind: WaterKind,
+    /// Heightmap indices (row-major) covered by this body
+    pub cells: Vec<u32>,
+    /// Water surface height for each entry in `cells`
+    pub surface: Vec<f32>,
+}
+
+impl WaterBody {
+    pub fn new(kind: WaterKind) -> Self {
+        Self {
+            kind,
+            cells: Vec::new(),
+            surface: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, cell: u32, surface: f32) {
+        self.cells.push(cell);
+        self.surface.push(surface);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+}
+
+/// Highest water surface at each heightmap sample, `None` where it is dry
+pub fn water_surface_map(terrain: &TerrainData, bodies: &[WaterBody]) -> Vec<Option<f32>> {
+    let mut surfaces = vec![None; terrain.heights.len()];
+    for body in bodies {
+        for (&cell, &surface) in body.cells.iter().zip(&body.surface) {
+            let Some(slot) = surfaces.get_mut(cell as usize) else {
+                continue;
+            };
+            let terrain_height = terrain.heights[cell as usize];
+            if surface > terrain_height {
+                *slot = Some(slot.map_or(surface, |s: f32| s.max(surface)));
+            }
+        }
+    }
+    surfaces
+}
+
+/// Water depth at each heightmap sample, 0 where it is dry
+pub fn water_depth_map(terrain: &TerrainData, bodies: &[WaterBody]) -> Vec<f32> {
+    water_surface_map(terrain, bodies)
+        .iter()
+        .zip(&terrain.heights)
+        .map(|(surface, height)| surface.map_or(0.0, |s| s - height))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_water_maps() {
+        let terrain = TerrainData::new(3, 1, vec![0.0, 1.0, 3.0], 1.0).unwrap();
+
+        let mut lake = WaterBody::new(WaterKind::Lake);
+        lake.push(0, 2.0);
+        lake.push(1, 2.0);
+        // Surface below the ground is ignored
+        lake.push(2, 2.0);
+        let mut river = WaterBody::new(WaterKind::River);
+        river.push(0, 2.5);
+
+        let surfaces = water_surface_map(&terrain, &[lake, river]);
+        assert_eq!(surfaces, vec![Some(2.5), Some(2.0), None]);
+
+        let depths = water_depth_map(&terrain, &[]);
+        assert_eq!(depths, vec![0.0; 3]);
+    }
+}
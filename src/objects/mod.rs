@@ -0,0 +1,433 @@
+//! Data-driven environment object definitions loaded from assets/objects
+
+use crate::game_logic::errors::{MinionError, MinionResult};
+use crate::pathfinding::CollisionShape;
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::Collider;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Built-in definitions; files in the assets directory are layered on top
+const BUILTIN_DEFINITIONS: &str = include_str!("../../assets/objects/core.toml");
+
+/// Blocking priority for object types without a definition
+pub const DEFAULT_BLOCKING_PRIORITY: u8 = 100;
+
+/// Complete description of one environment object type
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjectDefinition {
+    /// Matches `EnvironmentObject::object_type`
+    pub name: String,
+    /// Priority for overlapping obstacles (higher = more important)
+    pub blocking_priority: u8,
+    pub model: ModelDefinition,
+    pub collider: ColliderDefinition,
+    /// Footprint used to block the navigation grid
+    pub nav_shape: NavShapeDefinition,
+}
+
+/// Visual representation of an object
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelDefinition {
+    /// Scene asset path (e.g. "props/tree.glb#Scene0"); the placeholder is used when absent
+    #[serde(default)]
+    pub asset: Option<String>,
+    pub placeholder: PlaceholderMesh,
+    /// Placeholder color as sRGB components
+    pub color: [f32; 3],
+}
+
+/// Primitive mesh used when no model asset is available
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PlaceholderMesh {
+    Cylinder { radius: f32, height: f32 },
+    Sphere { radius: f32 },
+    Cuboid { size: Vec3 },
+}
+
+/// Physics collider, sized in local units before object scale is applied
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ColliderDefinition {
+    /// Half height scales with scale.y, radius with scale.x
+    Cylinder {
+        half_height: f32,
+        radius: f32,
+    },
+    /// Radius scales with scale.x
+    Ball {
+        radius: f32,
+    },
+    Cuboid {
+        half_extents: Vec3,
+    },
+    None,
+}
+
+/// Pathfinding footprint, sized in local units before object scale is applied
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum NavShapeDefinition {
+    /// Radius scales with scale.x
+    Circle {
+        radius: f32,
+    },
+    Rectangle {
+        half_extents: Vec3,
+    },
+    /// Convex XZ footprint; vertices scale with scale.x and scale.z
+    Polygon {
+        vertices: Vec<Vec2>,
+    },
+    None,
+}
+
+impl ObjectDefinition {
+    /// Definition used for object types missing from the registry
+    pub fn fallback(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            blocking_priority: DEFAULT_BLOCKING_PRIORITY,
+            model: ModelDefinition {
+                asset: None,
+                placeholder: PlaceholderMesh::Cuboid { size: Vec3::ONE },
+                color: [0.6, 0.3, 0.6],
+            },
+            collider: ColliderDefinition::Cuboid {
+                half_extents: Vec3::splat(0.5),
+            },
+            nav_shape: NavShapeDefinition::Rectangle {
+                half_extents: Vec3::splat(0.5),
+            },
+        }
+    }
+
+    /// Check the definition for values that cannot produce a usable object
+    fn validate(&self) -> MinionResult<()> {
+        let invalid = |reason: String| {
+            Err(MinionError::InvalidConfig {
+                reason: format!("Object definition '{}': {reason}", self.name),
+            })
+        };
+
+        if self.name.is_empty() {
+            return invalid("name must not be empty".to_string());
+        }
+        match &self.nav_shape {
+            NavShapeDefinition::Polygon { vertices } if vertices.len() < 3 => invalid(format!(
+                "polygon footprint needs at least 3 vertices, got {}",
+                vertices.len()
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl PlaceholderMesh {
+    /// Build the placeholder mesh
+    pub fn to_mesh(&self) -> Mesh {
+        match self {
+            PlaceholderMesh::Cylinder { radius, height } => {
+                Mesh::from(Cylinder::new(*radius, *height))
+            }
+            PlaceholderMesh::Sphere { radius } => Sphere::new(*radius).mesh().uv(8, 6),
+            PlaceholderMesh::Cuboid { size } => Mesh::from(Cuboid::new(size.x, size.y, size.z)),
+        }
+    }
+
+    /// Height of the mesh center above its base, before object scale is applied
+    pub fn half_height(&self) -> f32 {
+        match self {
+            PlaceholderMesh::Cylinder { height, .. } => height / 2.0,
+            PlaceholderMesh::Sphere { radius } => *radius,
+            PlaceholderMesh::Cuboid { size } => size.y / 2.0,
+        }
+    }
+}
+
+impl ColliderDefinition {
+    /// Build a physics collider for an object with the given scale
+    pub fn to_collider(&self, scale: Vec3) -> Option<Collider> {
+        match self {
+            ColliderDefinition::Cylinder {
+                half_height,
+                radius,
+            } => Some(Collider::cylinder(scale.y * half_height, scale.x * radius)),
+            ColliderDefinition::Ball { radius } => Some(Collider::ball(scale.x * radius)),
+            ColliderDefinition::Cuboid { half_extents } => {
+                let half = *half_extents * scale;
+                Some(Collider::cuboid(half.x, half.y, half.z))
+            }
+            ColliderDefinition::None => None,
+        }
+    }
+}
+
+impl NavShapeDefinition {
+    /// Build the pathfinding collision shape for an object with the given scale
+    pub fn to_collision_shape(&self, scale: Vec3) -> CollisionShape {
+        match self {
+            NavShapeDefinition::Circle { radius } if scale.x > 0.0 => CollisionShape::Circle {
+                radius: scale.x * radius,
+            },
+            NavShapeDefinition::Rectangle { half_extents } if scale.x > 0.0 && scale.z > 0.0 => {
+                CollisionShape::Rectangle {
+                    half_extents: *half_extents * scale,
+                }
+            }
+            NavShapeDefinition::Polygon { vertices } if scale.x > 0.0 && scale.z > 0.0 => {
+                CollisionShape::ConvexPolygon {
+                    vertices: vertices
+                        .iter()
+                        .map(|v| Vec2::new(v.x * scale.x, v.y * scale.z))
+                        .collect(),
+                }
+            }
+            _ => CollisionShape::None,
+        }
+    }
+}
+
+/// On-disk layout of a definitions file
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct DefinitionsFile {
+    #[serde(default)]
+    object: Vec<ObjectDefinition>,
+}
+
+/// Registry of object definitions keyed by object type name
+#[derive(Debug, Clone, Default, Resource)]
+pub struct ObjectRegistry {
+    definitions: Vec<ObjectDefinition>,
+}
+
+impl ObjectRegistry {
+    /// Get the object definitions directory
+    pub fn get_objects_dir() -> MinionResult<PathBuf> {
+        std::env::current_dir()
+            .map_err(MinionError::ConfigDirCreationFailed)
+            .map(|dir| dir.join("assets").join("objects"))
+    }
+
+    /// Parse definitions from a TOML string
+    pub fn from_toml_str(contents: &str) -> MinionResult<Self> {
+        let mut registry = Self::default();
+        registry.merge_toml_str(contents)?;
+        Ok(registry)
+    }
+
+    /// Registry containing only the built-in definitions
+    pub fn builtin() -> Self {
+        Self::from_toml_str(BUILTIN_DEFINITIONS).expect("built-in object definitions are valid")
+    }
+
+    /// Load every *.toml file in a directory, in file name order
+    pub fn load_from_dir<P: AsRef<Path>>(dir: P) -> MinionResult<Self> {
+        let dir = dir.as_ref();
+        if !dir.is_dir() {
+            return Err(MinionError::ConfigFileNotFound {
+                path: dir.to_path_buf(),
+            });
+        }
+
+        let mut registry = Self::default();
+        registry.merge_dir(dir)?;
+        Ok(registry)
+    }
+
+    /// Shared registry: the built-in definitions overridden and extended by assets/objects
+    pub fn global() -> &'static ObjectRegistry {
+        static REGISTRY: OnceLock<ObjectRegistry> = OnceLock::new();
+        REGISTRY.get_or_init(|| {
+            let mut registry = Self::builtin();
+            let dir = Self::get_objects_dir();
+            if let Err(err) = dir.and_then(|dir| registry.merge_dir(&dir)) {
+                warn!("Failed to load object definitions ({err}), using built-in definitions");
+                registry = Self::builtin();
+            }
+            info!("Loaded {} object definitions", registry.len());
+            registry
+        })
+    }
+
+    /// Add every *.toml file in a directory, in file name order
+    fn merge_dir(&mut self, dir: &Path) -> MinionResult<()> {
+        let mut files: Vec<PathBuf> = std::fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+            .collect();
+        files.sort();
+
+        for file in files {
+            let contents = std::fs::read_to_string(&file)?;
+            self.merge_toml_str(&contents)?;
+        }
+        Ok(())
+    }
+
+    /// Add definitions from a TOML string, replacing existing entries with the same name
+    fn merge_toml_str(&mut self, contents: &str) -> MinionResult<()> {
+        let file: DefinitionsFile = toml::from_str(contents)?;
+        for definition in file.object {
+            self.insert(definition)?;
+        }
+        Ok(())
+    }
+
+    /// Add or replace a definition
+    pub fn insert(&mut self, definition: ObjectDefinition) -> MinionResult<()> {
+        definition.validate()?;
+        match self
+            .definitions
+            .iter_mut()
+            .find(|existing| existing.name == definition.name)
+        {
+            Some(existing) => *existing = definition,
+            None => self.definitions.push(definition),
+        }
+        Ok(())
+    }
+
+    /// Get the definition for an object type
+    pub fn get(&self, name: &str) -> Option<&ObjectDefinition> {
+        self.definitions.iter().find(|def| def.name == name)
+    }
+
+    /// Get the definition for an object type, or the generic fallback
+    pub fn get_or_fallback(&self, name: &str) -> std::borrow::Cow<'_, ObjectDefinition> {
+        match self.get(name) {
+            Some(def) => std::borrow::Cow::Borrowed(def),
+            None => std::borrow::Cow::Owned(ObjectDefinition::fallback(name)),
+        }
+    }
+
+    /// Iterate over all definitions
+    pub fn iter(&self) -> impl Iterator<Item = &ObjectDefinition> {
+        self.definitions.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.definitions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.definitions.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_definitions_parse() {
+        let registry = ObjectRegistry::builtin();
+
+        for name in ["tree", "rock", "boulder", "grass"] {
+            assert!(
+                registry.get(name).is_some(),
+                "Missing definition for {name}"
+            );
+        }
+        assert_eq!(registry.get("tree").unwrap().blocking_priority, 150);
+        assert_eq!(registry.get("boulder").unwrap().blocking_priority, 200);
+        assert!(registry.get("castle").is_none());
+    }
+
+    #[test]
+    fn test_fallback_definition() {
+        let registry = ObjectRegistry::builtin();
+        let def = registry.get_or_fallback("castle");
+
+        assert_eq!(def.name, "castle");
+        assert_eq!(def.blocking_priority, DEFAULT_BLOCKING_PRIORITY);
+        assert!(matches!(
+            def.nav_shape.to_collision_shape(Vec3::new(4.0, 6.0, 4.0)),
+            CollisionShape::Rectangle { half_extents } if half_extents == Vec3::new(2.0, 3.0, 2.0)
+        ));
+    }
+
+    #[test]
+    fn test_nav_shape_scaling() {
+        let circle = NavShapeDefinition::Circle { radius: 0.6 };
+        assert!(matches!(
+            circle.to_collision_shape(Vec3::splat(2.0)),
+            CollisionShape::Circle { radius } if radius == 1.2
+        ));
+
+        let polygon = NavShapeDefinition::Polygon {
+            vertices: vec![
+                Vec2::new(-1.0, -1.0),
+                Vec2::new(1.0, -1.0),
+                Vec2::new(0.0, 1.0),
+            ],
+        };
+        match polygon.to_collision_shape(Vec3::new(2.0, 1.0, 3.0)) {
+            CollisionShape::ConvexPolygon { vertices } => {
+                assert_eq!(vertices[0], Vec2::new(-2.0, -3.0));
+                assert_eq!(vertices[2], Vec2::new(0.0, 3.0));
+            }
+            other => panic!("Expected polygon, got {other:?}"),
+        }
+
+        // Degenerate scale produces no footprint
+        let rect = NavShapeDefinition::Rectangle {
+            half_extents: Vec3::splat(0.5),
+        };
+        assert!(matches!(
+            rect.to_collision_shape(Vec3::new(0.0, 1.0, 0.0)),
+            CollisionShape::None
+        ));
+    }
+
+    #[test]
+    fn test_later_definitions_replace_earlier() {
+        let mut registry = ObjectRegistry::builtin();
+        let count = registry.len();
+
+        registry
+            .merge_toml_str(
+                r#"
+                [[object]]
+                name = "tree"
+                blocking_priority = 90
+                model = { asset = "props/pine.glb#Scene0", placeholder = { type = "sphere", radius = 1.0 }, color = [0.0, 0.5, 0.0] }
+                collider = { type = "none" }
+                nav_shape = { type = "circle", radius = 0.4 }
+                "#,
+            )
+            .unwrap();
+
+        assert_eq!(registry.len(), count, "Override should not add an entry");
+        let tree = registry.get("tree").unwrap();
+        assert_eq!(tree.blocking_priority, 90);
+        assert_eq!(tree.model.asset.as_deref(), Some("props/pine.glb#Scene0"));
+        assert!(tree.collider.to_collider(Vec3::ONE).is_none());
+    }
+
+    #[test]
+    fn test_invalid_definitions_rejected() {
+        let bad_polygon = r#"
+            [[object]]
+            name = "hut"
+            blocking_priority = 255
+            model = { placeholder = { type = "cuboid", size = [2.0, 2.0, 2.0] }, color = [0.5, 0.4, 0.3] }
+            collider = { type = "cuboid", half_extents = [1.0, 1.0, 1.0] }
+            nav_shape = { type = "polygon", vertices = [[0.0, 0.0], [1.0, 0.0]] }
+        "#;
+        assert!(ObjectRegistry::from_toml_str(bad_polygon).is_err());
+
+        assert!(ObjectRegistry::from_toml_str("[[object]]\nname = 5").is_err());
+        assert!(ObjectRegistry::load_from_dir("/nonexistent/objects").is_err());
+    }
+
+    #[test]
+    fn test_assets_directory_matches_builtin() {
+        // Running from the crate root, the assets directory should load the same entries
+        let registry =
+            ObjectRegistry::load_from_dir(ObjectRegistry::get_objects_dir().unwrap()).unwrap();
+        assert_eq!(registry.len(), ObjectRegistry::builtin().len());
+    }
+}
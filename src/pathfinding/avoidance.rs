@@ -0,0 +1,546 @@
+//! ORCA (optimal reciprocal collision avoidance) local steering for pathfinding agents
+
+use crate::components::PathfindingAgent;
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use std::collections::HashMap;
+
+/// Tolerance for parallel line checks in the linear programs
+const ORCA_EPSILON: f32 = 1e-5;
+
+/// Tuning for local avoidance between agents
+#[derive(Debug, Clone, Resource)]
+pub struct AvoidanceConfig {
+    /// How far ahead (seconds) agents look for potential collisions
+    pub time_horizon: f32,
+    /// Maximum distance at which other agents are considered
+    pub neighbor_distance: f32,
+    /// Maximum number of neighbors considered per agent
+    pub max_neighbors: usize,
+}
+
+impl Default for AvoidanceConfig {
+    fn default() -> Self {
+        Self {
+            time_horizon: 1.5,      // Seconds of lookahead
+            neighbor_distance: 6.0, // World units
+            max_neighbors: 8,       // Closest agents only
+        }
+    }
+}
+
+/// Snapshot of one agent's state in the XZ plane
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AvoidanceAgentState {
+    pub position: Vec2,
+    /// Velocity chosen on the previous update
+    pub velocity: Vec2,
+    pub preferred_velocity: Vec2,
+    pub radius: f32,
+    pub max_speed: f32,
+}
+
+/// Half-plane of permitted velocities: everything to the left of `direction` through `point`
+#[derive(Debug, Clone, Copy)]
+struct OrcaLine {
+    point: Vec2,
+    direction: Vec2,
+}
+
+/// Uniform grid for neighbor queries
+#[derive(Debug, Clone)]
+pub struct SpatialHash {
+    cell_size: f32,
+    cells: HashMap<(i32, i32), Vec<usize>>,
+}
+
+impl SpatialHash {
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size: cell_size.max(ORCA_EPSILON),
+            cells: HashMap::new(),
+        }
+    }
+
+    fn cell_of(&self, position: Vec2) -> (i32, i32) {
+        (
+            (position.x / self.cell_size).floor() as i32,
+            (position.y / self.cell_size).floor() as i32,
+        )
+    }
+
+    /// Insert an item index at a position
+    pub fn insert(&mut self, index: usize, position: Vec2) {
+        let cell = self.cell_of(position);
+        self.cells.entry(cell).or_default().push(index);
+    }
+
+    /// Get indices of items in cells overlapping a square around the position
+    pub fn query(&self, position: Vec2, radius: f32) -> Vec<usize> {
+        let (min_x, min_z) = self.cell_of(position - Vec2::splat(radius));
+        let (max_x, max_z) = self.cell_of(position + Vec2::splat(radius));
+
+        let mut result = Vec::new();
+        for z in min_z..=max_z {
+            for x in min_x..=max_x {
+                if let Some(items) = self.cells.get(&(x, z)) {
+                    result.extend_from_slice(items);
+                }
+            }
+        }
+        result
+    }
+}
+
+/// Compute a collision-free velocity for one agent given its neighbors
+///
+/// # Arguments
+/// * `agent` - The agent being steered
+/// * `neighbors` - Nearby agents, excluding the agent itself
+/// * `time_horizon` - Lookahead time (seconds) for collision checks
+/// * `delta_time` - Simulation step, used to resolve existing overlaps
+///
+/// # Returns
+/// The permitted velocity closest to the agent's preferred velocity
+pub fn compute_avoidance_velocity(
+    agent: &AvoidanceAgentState,
+    neighbors: &[AvoidanceAgentState],
+    time_horizon: f32,
+    delta_time: f32,
+) -> Vec2 {
+    let inv_time_horizon = 1.0 / time_horizon.max(ORCA_EPSILON);
+    let inv_time_step = 1.0 / delta_time.max(ORCA_EPSILON);
+
+    let lines: Vec<OrcaLine> = neighbors
+        .iter()
+        .map(|other| {
+            let relative_position = other.position - agent.position;
+            let relative_velocity = agent.velocity - other.velocity;
+            let dist_sq = relative_position.length_squared();
+            let combined_radius = agent.radius + other.radius;
+            let combined_radius_sq = combined_radius * combined_radius;
+
+            let (direction, u) = if dist_sq > combined_radius_sq {
+                // No collision yet; vector from cutoff center to relative velocity
+                let w = relative_velocity - inv_time_horizon * relative_position;
+                let w_length_sq = w.length_squared();
+                let dot_product = w.dot(relative_position);
+
+                if dot_product < 0.0 && dot_product * dot_product > combined_radius_sq * w_length_sq
+                {
+                    // Project on the cutoff circle
+                    let w_length = w_length_sq.sqrt();
+                    let unit_w = w / w_length;
+                    (
+                        Vec2::new(unit_w.y, -unit_w.x),
+                        (combined_radius * inv_time_horizon - w_length) * unit_w,
+                    )
+                } else {
+                    // Project on the nearest leg of the velocity obstacle cone
+                    let leg = (dist_sq - combined_radius_sq).sqrt();
+                    let direction = if relative_position.perp_dot(w) > 0.0 {
+                        Vec2::new(
+                            relative_position.x * leg - relative_position.y * combined_radius,
+                            relative_position.x * combined_radius + relative_position.y * leg,
+                        ) / dist_sq
+                    } else {
+                        -Vec2::new(
+                            relative_position.x * leg + relative_position.y * combined_radius,
+                            -relative_position.x * combined_radius + relative_position.y * leg,
+                        ) / dist_sq
+                    };
+                    let u = relative_velocity.dot(direction) * direction - relative_velocity;
+                    (direction, u)
+                }
+            } else {
+                // Already overlapping; push apart within one time step
+                let w = relative_velocity - inv_time_step * relative_position;
+                let w_length = w.length();
+                let unit_w = if w_length > ORCA_EPSILON {
+                    w / w_length
+                } else {
+                    // Exactly coincident: pick any separating direction
+                    Vec2::X
+                };
+                (
+                    Vec2::new(unit_w.y, -unit_w.x),
+                    (combined_radius * inv_time_step - w_length) * unit_w,
+                )
+            };
+
+            // Share responsibility with agents that also steer; avoid inactive ones fully
+            let responsibility = if other.max_speed > 0.0 { 0.5 } else { 1.0 };
+            OrcaLine {
+                point: agent.velocity + responsibility * u,
+                direction,
+            }
+        })
+        .collect();
+
+    let mut result = Vec2::ZERO;
+    let line_fail = linear_program_2(
+        &lines,
+        agent.max_speed,
+        agent.preferred_velocity,
+        false,
+        &mut result,
+    );
+    if line_fail < lines.len() {
+        linear_program_3(&lines, line_fail, agent.max_speed, &mut result);
+    }
+    result
+}
+
+/// Solve a 1D linear program on one ORCA line, subject to the lines before it
+fn linear_program_1(
+    lines: &[OrcaLine],
+    line_no: usize,
+    radius: f32,
+    opt_velocity: Vec2,
+    direction_opt: bool,
+    result: &mut Vec2,
+) -> bool {
+    let line = lines[line_no];
+    let dot_product = line.point.dot(line.direction);
+    let discriminant = dot_product * dot_product + radius * radius - line.point.length_squared();
+    if discriminant < 0.0 {
+        // Max speed circle fully invalidates this line
+        return false;
+    }
+
+    let sqrt_discriminant = discriminant.sqrt();
+    let mut t_left = -dot_product - sqrt_discriminant;
+    let mut t_right = -dot_product + sqrt_discriminant;
+
+    for previous in &lines[..line_no] {
+        let denominator = line.direction.perp_dot(previous.direction);
+        let numerator = previous.direction.perp_dot(line.point - previous.point);
+
+        if denominator.abs() <= ORCA_EPSILON {
+            // Lines are (almost) parallel
+            if numerator < 0.0 {
+                return false;
+            }
+            continue;
+        }
+
+        let t = numerator / denominator;
+        if denominator >= 0.0 {
+            t_right = t_right.min(t);
+        } else {
+            t_left = t_left.max(t);
+        }
+        if t_left > t_right {
+            return false;
+        }
+    }
+
+    *result = if direction_opt {
+        if opt_velocity.dot(line.direction) > 0.0 {
+            line.point + t_right * line.direction
+        } else {
+            line.point + t_left * line.direction
+        }
+    } else {
+        let t = line.direction.dot(opt_velocity - line.point);
+        line.point + t.clamp(t_left, t_right) * line.direction
+    };
+    true
+}
+
+/// Solve the 2D linear program; returns the index of the first failing line, or lines.len()
+fn linear_program_2(
+    lines: &[OrcaLine],
+    radius: f32,
+    opt_velocity: Vec2,
+    direction_opt: bool,
+    result: &mut Vec2,
+) -> usize {
+    *result = if direction_opt {
+        opt_velocity * radius
+    } else if opt_velocity.length_squared() > radius * radius {
+        opt_velocity.normalize() * radius
+    } else {
+        opt_velocity
+    };
+
+    for (i, line) in lines.iter().enumerate() {
+        if line.direction.perp_dot(line.point - *result) > 0.0 {
+            // Result violates this constraint
+            let previous_result = *result;
+            if !linear_program_1(lines, i, radius, opt_velocity, direction_opt, result) {
+                *result = previous_result;
+                return i;
+            }
+        }
+    }
+    lines.len()
+}
+
+/// Find the velocity that minimizes the maximum constraint violation when the 2D program fails
+fn linear_program_3(lines: &[OrcaLine], begin_line: usize, radius: f32, result: &mut Vec2) {
+    let mut distance = 0.0;
+
+    for (i, line) in lines.iter().enumerate().skip(begin_line) {
+        if line.direction.perp_dot(line.point - *result) <= distance {
+            continue;
+        }
+
+        let mut projected_lines = Vec::with_capacity(i);
+        for other in &lines[..i] {
+            let determinant = line.direction.perp_dot(other.direction);
+            let point = if determinant.abs() <= ORCA_EPSILON {
+                if line.direction.dot(other.direction) > 0.0 {
+                    // Same direction; the other line is redundant
+                    continue;
+                }
+                0.5 * (line.point + other.point)
+            } else {
+                line.point
+                    + (other.direction.perp_dot(line.point - other.point) / determinant)
+                        * line.direction
+            };
+            projected_lines.push(OrcaLine {
+                point,
+                direction: (other.direction - line.direction).normalize_or_zero(),
+            });
+        }
+
+        let previous_result = *result;
+        let perpendicular = Vec2::new(-line.direction.y, line.direction.x);
+        if linear_program_2(&projected_lines, radius, perpendicular, true, result)
+            < projected_lines.len()
+        {
+            // Only fails due to floating point error; keep the previous result
+            *result = previous_result;
+        }
+        distance = line.direction.perp_dot(line.point - *result);
+    }
+}
+
+/// Adjust every agent's controller translation to a collision-free velocity
+///
+/// Movement systems record each agent's preferred velocity on its `PathfindingAgent`;
+/// this system replaces the horizontal part of the controller translation and keeps
+/// any vertical component (gravity) untouched.
+pub fn apply_local_avoidance(
+    config: Res<AvoidanceConfig>,
+    time: Res<Time>,
+    mut agents: Query<(
+        &Transform,
+        &mut PathfindingAgent,
+        &mut KinematicCharacterController,
+    )>,
+) {
+    let delta_time = time.delta_secs();
+    if delta_time <= 0.0 {
+        return;
+    }
+
+    let states: Vec<AvoidanceAgentState> = agents
+        .iter()
+        .map(|(transform, agent, _)| AvoidanceAgentState {
+            position: Vec2::new(transform.translation.x, transform.translation.z),
+            velocity: Vec2::new(agent.avoidance_velocity.x, agent.avoidance_velocity.z),
+            preferred_velocity: Vec2::new(agent.preferred_velocity.x, agent.preferred_velocity.z),
+            radius: agent.agent_radius,
+            max_speed: agent.max_speed,
+        })
+        .collect();
+
+    let mut spatial_hash = SpatialHash::new(config.neighbor_distance);
+    for (index, state) in states.iter().enumerate() {
+        spatial_hash.insert(index, state.position);
+    }
+
+    for (index, (_, mut agent, mut controller)) in agents.iter_mut().enumerate() {
+        let state = &states[index];
+        if state.max_speed <= 0.0 {
+            // Inactive agents act as obstacles but are not steered
+            agent.avoidance_velocity = Vec3::ZERO;
+            continue;
+        }
+
+        let mut neighbors: Vec<AvoidanceAgentState> = spatial_hash
+            .query(state.position, config.neighbor_distance)
+            .into_iter()
+            .filter(|&other| other != index)
+            .map(|other| states[other])
+            .filter(|other| {
+                other.position.distance_squared(state.position)
+                    <= config.neighbor_distance * config.neighbor_distance
+            })
+            .collect();
+        neighbors.sort_by(|a, b| {
+            a.position
+                .distance_squared(state.position)
+                .total_cmp(&b.position.distance_squared(state.position))
+        });
+        neighbors.truncate(config.max_neighbors);
+
+        let velocity =
+            compute_avoidance_velocity(state, &neighbors, config.time_horizon, delta_time);
+        agent.avoidance_velocity = Vec3::new(velocity.x, 0.0, velocity.y);
+
+        let vertical = controller.translation.map_or(0.0, |t| t.y);
+        controller.translation = Some(Vec3::new(
+            velocity.x * delta_time,
+            vertical,
+            velocity.y * delta_time,
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn agent(position: Vec2, preferred_velocity: Vec2) -> AvoidanceAgentState {
+        AvoidanceAgentState {
+            position,
+            velocity: preferred_velocity,
+            preferred_velocity,
+            radius: 0.5,
+            max_speed: 2.0,
+        }
+    }
+
+    /// Step a set of agents forward, returning the minimum separation seen
+    fn simulate(agents: &mut [AvoidanceAgentState], steps: usize, dt: f32) -> f32 {
+        let mut min_distance = f32::INFINITY;
+        for _ in 0..steps {
+            let velocities: Vec<Vec2> = (0..agents.len())
+                .map(|i| {
+                    let neighbors: Vec<_> = agents
+                        .iter()
+                        .enumerate()
+                        .filter(|(j, _)| *j != i)
+                        .map(|(_, a)| *a)
+                        .collect();
+                    compute_avoidance_velocity(&agents[i], &neighbors, 2.0, dt)
+                })
+                .collect();
+
+            for (agent, velocity) in agents.iter_mut().zip(velocities) {
+                agent.velocity = velocity;
+                agent.position += velocity * dt;
+            }
+
+            for (i, a) in agents.iter().enumerate() {
+                for b in &agents[i + 1..] {
+                    min_distance = min_distance.min(a.position.distance(b.position));
+                }
+            }
+        }
+        min_distance
+    }
+
+    #[test]
+    fn test_no_neighbors_keeps_preferred_velocity() {
+        let state = agent(Vec2::ZERO, Vec2::new(1.5, 0.0));
+        let velocity = compute_avoidance_velocity(&state, &[], 2.0, 1.0 / 60.0);
+        assert_eq!(velocity, Vec2::new(1.5, 0.0));
+    }
+
+    #[test]
+    fn test_preferred_velocity_clamped_to_max_speed() {
+        let state = agent(Vec2::ZERO, Vec2::new(10.0, 0.0));
+        let velocity = compute_avoidance_velocity(&state, &[], 2.0, 1.0 / 60.0);
+        assert!((velocity.length() - state.max_speed).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_head_on_agents_deflect() {
+        let left = agent(Vec2::new(-3.0, 0.0), Vec2::new(2.0, 0.0));
+        let right = agent(Vec2::new(3.0, 0.0), Vec2::new(-2.0, 0.0));
+
+        let velocity = compute_avoidance_velocity(&left, &[right], 2.0, 1.0 / 60.0);
+        assert!(
+            velocity.y.abs() > 0.01 || velocity.x < 2.0,
+            "Agent should sidestep or slow down, got {velocity:?}"
+        );
+    }
+
+    #[test]
+    fn test_head_on_simulation_avoids_collision() {
+        let mut agents = [
+            agent(Vec2::new(-5.0, 0.01), Vec2::new(2.0, 0.0)),
+            agent(Vec2::new(5.0, -0.01), Vec2::new(-2.0, 0.0)),
+        ];
+        let min_distance = simulate(&mut agents, 400, 1.0 / 60.0);
+
+        assert!(
+            min_distance >= 0.95,
+            "Agents should not overlap (combined radius 1.0), min distance {min_distance}"
+        );
+        assert!(agents[0].position.x > 3.0, "Left agent should get past");
+        assert!(agents[1].position.x < -3.0, "Right agent should get past");
+    }
+
+    #[test]
+    fn test_crowd_crossing_avoids_collision() {
+        // Four agents swapping places through the center
+        let mut agents: Vec<_> = (0..4)
+            .map(|i| {
+                let angle = i as f32 * std::f32::consts::FRAC_PI_2 + 0.05;
+                let position = Vec2::new(angle.cos(), angle.sin()) * 5.0;
+                agent(position, -position.normalize() * 2.0)
+            })
+            .collect();
+        let min_distance = simulate(&mut agents, 300, 1.0 / 60.0);
+
+        assert!(
+            min_distance >= 0.9,
+            "Crowd should stay separated, min distance {min_distance}"
+        );
+    }
+
+    #[test]
+    fn test_overlapping_agents_separate() {
+        let a = agent(Vec2::new(0.0, 0.0), Vec2::ZERO);
+        let b = agent(Vec2::new(0.3, 0.0), Vec2::ZERO);
+
+        let velocity = compute_avoidance_velocity(&a, &[b], 2.0, 1.0 / 60.0);
+        assert!(velocity.x < 0.0, "Should move away from overlapping agent");
+    }
+
+    #[test]
+    fn test_stationary_neighbor_is_avoided() {
+        let mut moving = agent(Vec2::new(-4.0, 0.05), Vec2::new(2.0, 0.0));
+        let blocker = AvoidanceAgentState {
+            max_speed: 0.0,
+            ..agent(Vec2::ZERO, Vec2::ZERO)
+        };
+
+        let dt = 1.0 / 60.0;
+        let mut min_distance = f32::INFINITY;
+        for _ in 0..300 {
+            let velocity = compute_avoidance_velocity(&moving, &[blocker], 2.0, dt);
+            moving.velocity = velocity;
+            moving.position += velocity * dt;
+            min_distance = min_distance.min(moving.position.distance(blocker.position));
+        }
+
+        // Inactive neighbors are avoided with full responsibility
+        assert!(min_distance >= 0.9, "Min distance {min_distance}");
+        assert!(
+            moving.position.x > 2.0,
+            "Agent should get around the blocker"
+        );
+    }
+
+    #[test]
+    fn test_spatial_hash_query() {
+        let mut hash = SpatialHash::new(2.0);
+        hash.insert(0, Vec2::new(0.5, 0.5));
+        hash.insert(1, Vec2::new(3.0, 0.5));
+        hash.insert(2, Vec2::new(20.0, 20.0));
+
+        let nearby = hash.query(Vec2::ZERO, 2.0);
+        assert!(nearby.contains(&0));
+        assert!(!nearby.contains(&2));
+
+        let wide = hash.query(Vec2::ZERO, 4.0);
+        assert!(wide.contains(&1));
+    }
+}
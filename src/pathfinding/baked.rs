@@ -0,0 +1,380 @@
+//! Precomputed navigation data that can be stored alongside a map file.
+//!
+//! Baking captures the finished grid (walkability, heights, obstacle
+//! priorities), inflated walkability for a set of agent radii, and connected
+//! region labels. The data is keyed by a hash of the terrain, objects, water,
+//! and pathfinding config so stale bakes are detected and rebuilt.
+
+use super::grid_blocking::block_deep_water;
+use super::{GridNode, NavigationGrid, PathfindingConfig};
+use crate::game_logic::errors::{MinionError, MinionResult};
+use crate::map::{EnvironmentObject, TerrainData, WaterBody};
+use crate::pathfinding::obstacles::{EnvironmentObstacle, Obstacle};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Bumped whenever the baked layout or grid generation changes meaning
+pub const BAKED_NAVIGATION_VERSION: u32 = 1;
+
+/// Region label for blocked cells
+pub const NO_REGION: u32 = u32::MAX;
+
+/// Agent radii baked by default, covering the player and common enemies
+pub const DEFAULT_BAKE_RADII: [f32; 3] = [0.3, 0.5, 1.0];
+
+/// Walkability after inflating obstacles for one agent size
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InflatedLayer {
+    pub agent_radius: f32,
+    pub slop: f32,
+    pub walkable: Vec<bool>,
+}
+
+impl InflatedLayer {
+    /// Check whether this layer was built for the given radius and slop
+    pub fn matches(&self, agent_radius: f32, slop: f32) -> bool {
+        (self.agent_radius - agent_radius).abs() < 1e-4 && (self.slop - slop).abs() < 1e-4
+    }
+}
+
+/// Serializable snapshot of a fully built navigation grid
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BakedNavigation {
+    pub version: u32,
+    /// Hash of the terrain, objects, water, and config the grid was built from
+    pub source_hash: u64,
+    pub width: u32,
+    pub height: u32,
+    pub cell_size: f32,
+    pub walkable: Vec<bool>,
+    pub heights: Vec<f32>,
+    pub obstacle_priorities: Vec<u8>,
+    pub inflations: Vec<InflatedLayer>,
+    /// Connected component label per cell, `NO_REGION` for blocked cells
+    pub regions: Vec<u32>,
+}
+
+/// FNV-1a, chosen because it is stable across platforms and Rust versions
+struct StableHasher(u64);
+
+impl StableHasher {
+    fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn write_u32(&mut self, value: u32) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_f32(&mut self, value: f32) {
+        self.write(&value.to_bits().to_le_bytes());
+    }
+
+    fn write_str(&mut self, value: &str) {
+        self.write_u32(value.len() as u32);
+        self.write(value.as_bytes());
+    }
+}
+
+/// Hash everything that feeds into navigation grid generation
+pub fn navigation_source_hash(
+    terrain: &TerrainData,
+    objects: &[EnvironmentObject],
+    water_bodies: &[WaterBody],
+    config: &PathfindingConfig,
+) -> u64 {
+    let mut hasher = StableHasher::new();
+    hasher.write_u32(BAKED_NAVIGATION_VERSION);
+
+    hasher.write_u32(terrain.width);
+    hasher.write_u32(terrain.height);
+    hasher.write_f32(terrain.scale);
+    for height in &terrain.heights {
+        hasher.write_f32(*height);
+    }
+
+    hasher.write_f32(config.max_walkable_slope);
+    hasher.write_f32(config.slope_cost_factor);
+    hasher.write_f32(config.agent_clearance_slop);
+    hasher.write_f32(config.cell_size.unwrap_or(0.0));
+    hasher.write_f32(config.max_wading_depth);
+
+    // Hash the resolved obstacle rather than the raw object so edits to the
+    // object registry also invalidate the bake
+    hasher.write_u32(objects.len() as u32);
+    for object in objects {
+        let obstacle = EnvironmentObstacle::from(object);
+        hasher.write_str(&object.object_type);
+        for value in object.position.to_array() {
+            hasher.write_f32(value);
+        }
+        hasher.write_str(&format!("{:?}", obstacle.collision_shape()));
+        hasher.write(&[
+            obstacle.blocking_priority(),
+            obstacle.blocks_pathfinding() as u8,
+        ]);
+    }
+
+    // Deep water is blocked in the baked grid, so any change to it must rebake
+    hasher.write_u32(water_bodies.len() as u32);
+    for body in water_bodies {
+        hasher.write_str(&format!("{:?}", body.kind));
+        hasher.write_u32(body.cells.len() as u32);
+        for (&cell, &surface) in body.cells.iter().zip(&body.surface) {
+            hasher.write_u32(cell);
+            hasher.write_f32(surface);
+        }
+    }
+
+    hasher.0
+}
+
+/// Label 4-connected walkable regions; blocked cells get `NO_REGION`
+pub fn label_regions(walkable: &[bool], width: u32, height: u32) -> Vec<u32> {
+    let mut regions = vec![NO_REGION; walkable.len()];
+    let mut next_region = 0;
+    let mut queue = VecDeque::new();
+
+    for (start, &is_walkable) in walkable.iter().enumerate() {
+        if !is_walkable || regions[start] != NO_REGION {
+            continue;
+        }
+
+        regions[start] = next_region;
+        queue.push_back(start);
+        while let Some(index) = queue.pop_front() {
+            let node = GridNode::new(index as u32 % width, index as u32 / width);
+            for neighbor in node.neighbors(width, height) {
+                let neighbor_index = (neighbor.z * width + neighbor.x) as usize;
+                if walkable[neighbor_index] && regions[neighbor_index] == NO_REGION {
+                    regions[neighbor_index] = next_region;
+                    queue.push_back(neighbor_index);
+                }
+            }
+        }
+        next_region += 1;
+    }
+
+    regions
+}
+
+impl NavigationGrid {
+    /// Snapshot this grid, pre-inflating obstacles for each agent radius
+    pub fn bake(&self, source_hash: u64, agent_radii: &[f32]) -> BakedNavigation {
+        let slop = self.config.agent_clearance_slop;
+        let inflations = agent_radii
+            .iter()
+            .map(|&agent_radius| InflatedLayer {
+                agent_radius,
+                slop,
+                walkable: self.clone_and_inflate(agent_radius, slop).walkable,
+            })
+            .collect();
+
+        let regions = if self.regions.len() == self.walkable.len() {
+            self.regions.clone()
+        } else {
+            label_regions(&self.walkable, self.width, self.height)
+        };
+
+        BakedNavigation {
+            version: BAKED_NAVIGATION_VERSION,
+            source_hash,
+            width: self.width,
+            height: self.height,
+            cell_size: self.cell_size,
+            walkable: self.walkable.clone(),
+            heights: self.heights.clone(),
+            obstacle_priorities: self.obstacle_priorities.clone(),
+            inflations,
+            regions,
+        }
+    }
+
+    /// Restore a grid from baked data without recomputing slopes or obstacles
+    pub fn from_baked(
+        baked: BakedNavigation,
+        terrain: &TerrainData,
+        config: PathfindingConfig,
+    ) -> MinionResult<Self> {
+        let cells = (baked.width * baked.height) as usize;
+        let layers_valid = baked.inflations.iter().all(|l| l.walkable.len() == cells);
+        if baked.walkable.len() != cells
+            || baked.heights.len() != cells
+            || baked.obstacle_priorities.len() != cells
+            || baked.regions.len() != cells
+            || !layers_valid
+        {
+            return Err(MinionError::InvalidMapData {
+                reason: format!(
+                    "Baked navigation data does not match its {}x{} dimensions",
+                    baked.width, baked.height
+                ),
+            });
+        }
+
+        let mut grid = NavigationGrid {
+            walkable: baked.walkable,
+            heights: baked.heights,
+            obstacle_priorities: baked.obstacle_priorities,
+            width: baked.width,
+            height: baked.height,
+            cell_size: baked.cell_size,
+            terrain_width: terrain.width,
+            terrain_height: terrain.height,
+            terrain_scale: terrain.scale,
+            config,
+            inflated_layers: baked.inflations,
+            regions: baked.regions,
+            roads: Vec::new(),
+            min_step_cost: 0,
+        };
+        grid.refresh_min_movement_cost();
+        Ok(grid)
+    }
+
+    /// Use baked data when it matches the map, otherwise build the grid from
+    /// scratch and block deep water, which baked grids already include
+    pub fn from_baked_or_build(
+        baked: Option<BakedNavigation>,
+        terrain: &TerrainData,
+        objects: &[EnvironmentObject],
+        water_bodies: &[WaterBody],
+        config: PathfindingConfig,
+    ) -> MinionResult<Self> {
+        if let Some(baked) = baked {
+            let expected_hash = navigation_source_hash(terrain, objects, water_bodies, &config);
+            if baked.version != BAKED_NAVIGATION_VERSION || baked.source_hash != expected_hash {
+                info!("Baked navigation data is stale, rebuilding navigation grid");
+            } else {
+                match Self::from_baked(baked, terrain, config.clone()) {
+                    Ok(grid) => return Ok(grid),
+                    Err(err) => warn!("Ignoring baked navigation data: {err}"),
+                }
+            }
+        }
+
+        let mut grid = Self::from_terrain_and_objects(terrain, objects, config)?;
+        block_deep_water(&mut grid, terrain, water_bodies);
+        Ok(grid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map::WaterKind;
+
+    fn objects() -> Vec<EnvironmentObject> {
+        vec![
+            EnvironmentObject::simple("rock".to_string(), Vec3::new(2.0, 0.0, 2.0)),
+            EnvironmentObject::simple("tree".to_string(), Vec3::new(-3.0, 0.0, 1.0)),
+        ]
+    }
+
+    #[test]
+    fn test_source_hash_tracks_inputs() {
+        let terrain = TerrainData::create_flat(16, 16, 1.0, 0.0).unwrap();
+        let config = PathfindingConfig::default();
+        let base = navigation_source_hash(&terrain, &objects(), &[], &config);
+
+        assert_eq!(
+            base,
+            navigation_source_hash(&terrain, &objects(), &[], &config)
+        );
+
+        let mut moved = objects();
+        moved[0].position.x += 1.0;
+        assert_ne!(base, navigation_source_hash(&terrain, &moved, &[], &config));
+
+        let raised = TerrainData::create_flat(16, 16, 1.0, 1.0).unwrap();
+        assert_ne!(
+            base,
+            navigation_source_hash(&raised, &objects(), &[], &config)
+        );
+
+        let steeper = PathfindingConfig {
+            max_walkable_slope: 30.0,
+            ..Default::default()
+        };
+        assert_ne!(
+            base,
+            navigation_source_hash(&terrain, &objects(), &[], &steeper)
+        );
+
+        let mut lake = WaterBody::new(WaterKind::Lake);
+        lake.cells = vec![5, 6];
+        lake.surface = vec![1.0, 1.0];
+        let flooded = navigation_source_hash(&terrain, &objects(), &[lake.clone()], &config);
+        assert_ne!(base, flooded);
+        lake.surface[1] = 2.0;
+        assert_ne!(
+            flooded,
+            navigation_source_hash(&terrain, &objects(), &[lake], &config)
+        );
+    }
+
+    #[test]
+    fn test_label_regions_splits_on_walls() {
+        // 5x3 grid with a wall down the middle column
+        let mut walkable = vec![true; 15];
+        for z in 0..3 {
+            walkable[z * 5 + 2] = false;
+        }
+
+        let regions = label_regions(&walkable, 5, 3);
+        assert_eq!(regions[2], NO_REGION);
+        assert_eq!(regions[0], regions[11]);
+        assert_eq!(regions[4], regions[13]);
+        assert_ne!(regions[0], regions[4]);
+    }
+
+    #[test]
+    fn test_bake_round_trip() {
+        let terrain = TerrainData::create_flat(16, 16, 1.0, 0.0).unwrap();
+        let config = PathfindingConfig::default();
+        let built =
+            NavigationGrid::from_terrain_and_objects(&terrain, &objects(), config.clone()).unwrap();
+        let hash = navigation_source_hash(&terrain, &objects(), &[], &config);
+
+        let baked = built.bake(hash, &DEFAULT_BAKE_RADII);
+        assert_eq!(baked.inflations.len(), DEFAULT_BAKE_RADII.len());
+
+        let restored =
+            NavigationGrid::from_baked_or_build(Some(baked), &terrain, &objects(), &[], config)
+                .unwrap();
+        assert_eq!(restored.walkable, built.walkable);
+        assert_eq!(restored.obstacle_priorities, built.obstacle_priorities);
+        assert_eq!(restored.inflated_layers.len(), DEFAULT_BAKE_RADII.len());
+
+        // Cached layers must agree with inflating on the fly
+        let slop = restored.config.agent_clearance_slop;
+        assert_eq!(
+            restored.clone_and_inflate(0.5, slop).walkable,
+            built.clone_and_inflate(0.5, slop).walkable
+        );
+    }
+
+    #[test]
+    fn test_stale_bake_is_rebuilt() {
+        let terrain = TerrainData::create_flat(16, 16, 1.0, 0.0).unwrap();
+        let config = PathfindingConfig::default();
+        let built = NavigationGrid::from_terrain(&terrain, config.clone()).unwrap();
+        let baked = built.bake(navigation_source_hash(&terrain, &[], &[], &config), &[0.5]);
+
+        // Objects were added after baking, so the hash no longer matches
+        let rebuilt =
+            NavigationGrid::from_baked_or_build(Some(baked), &terrain, &objects(), &[], config)
+                .unwrap();
+        assert!(rebuilt.inflated_layers.is_empty());
+        assert!(rebuilt.walkable.iter().any(|w| !w));
+    }
+}
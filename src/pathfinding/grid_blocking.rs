@@ -1,8 +1,12 @@
 //! Optimized grid blocking operations with priority support
 
-use crate::pathfinding::{GridNode, NavigationGrid};
+use crate::map::{TerrainData, WaterBody, water_depth_map};
+use crate::pathfinding::{CollisionShape, GridNode, NavigationGrid, sample_bilinear};
 use bevy::prelude::*;
 
+/// Deep water overrides props but not hand-placed walls
+pub const WATER_PRIORITY: u8 = 180;
+
 /// Block circular area with priority-based override system
 pub fn block_circular_area_with_priority(
     nav_grid: &mut NavigationGrid,
@@ -15,9 +19,6 @@ pub fn block_circular_area_with_priority(
     };
     let cell_radius = (radius / nav_grid.cell_size).ceil() as i32;
 
-    // Pre-calculate constants for performance
-    let half_width = (nav_grid.terrain_width as f32 * nav_grid.terrain_scale) / 2.0;
-    let half_height = (nav_grid.terrain_height as f32 * nav_grid.terrain_scale) / 2.0;
     let radius_squared = radius * radius;
 
     for dz in -cell_radius..=cell_radius {
@@ -27,13 +28,12 @@ pub fn block_circular_area_with_priority(
 
             if x >= 0 && z >= 0 && x < nav_grid.width as i32 && z < nav_grid.height as i32 {
                 // Fast squared distance check
-                let world_x = (x as f32 * nav_grid.terrain_scale) - half_width;
-                let world_z = (z as f32 * nav_grid.terrain_scale) - half_height;
-                let dx_world = world_x - center.x;
-                let dz_world = world_z - center.z;
+                let cell = GridNode::new(x as u32, z as u32);
+                let cell_world = nav_grid.grid_to_world(cell);
+                let dx_world = cell_world.x - center.x;
+                let dz_world = cell_world.z - center.z;
 
                 if dx_world * dx_world + dz_world * dz_world <= radius_squared {
-                    let cell = GridNode::new(x as u32, z as u32);
                     nav_grid.set_cell_walkable_with_priority(cell, false, priority);
                 }
             }
@@ -65,6 +65,106 @@ pub fn block_rectangular_area_with_priority(
     }
 }
 
+/// Block every cell a convex polygon touches (vertices relative to center)
+pub fn block_polygon_area_with_priority(
+    nav_grid: &mut NavigationGrid,
+    center: Vec3,
+    vertices: &[Vec2],
+    priority: u8,
+) {
+    let shape = CollisionShape::ConvexPolygon {
+        vertices: vertices.to_vec(),
+    };
+    let (min_world, max_world) = shape.approximate_bounds(center);
+
+    // Clamp the polygon bounds to the grid so partially outside shapes still block
+    let origin = nav_grid.grid_to_world(GridNode::new(0, 0));
+    let to_cell = |world: f32, origin: f32, limit: u32| {
+        ((world - origin) / nav_grid.cell_size).clamp(0.0, limit.saturating_sub(1) as f32)
+    };
+    let min_x = to_cell(min_world.x, origin.x, nav_grid.width).floor() as u32;
+    let max_x = to_cell(max_world.x, origin.x, nav_grid.width).ceil() as u32;
+    let min_z = to_cell(min_world.z, origin.z, nav_grid.height).floor() as u32;
+    let max_z = to_cell(max_world.z, origin.z, nav_grid.height).ceil() as u32;
+
+    let half_cell = nav_grid.cell_size * 0.5;
+    for z in min_z..=max_z {
+        for x in min_x..=max_x {
+            let cell = GridNode::new(x, z);
+            let offset = nav_grid.grid_to_world(cell) - center;
+            if polygon_overlaps_cell(vertices, Vec2::new(offset.x, offset.z), half_cell) {
+                nav_grid.set_cell_walkable_with_priority(cell, false, priority);
+            }
+        }
+    }
+}
+
+/// Separating-axis test between a convex polygon and an axis-aligned cell square
+fn polygon_overlaps_cell(vertices: &[Vec2], cell_center: Vec2, half_cell: f32) -> bool {
+    // Edges that only touch don't count, so a shape flush with a cell border leaves it open
+    const MIN_OVERLAP: f32 = 1e-4;
+
+    let edge_normals = vertices
+        .iter()
+        .zip(vertices.iter().cycle().skip(1))
+        .map(|(a, b)| (*b - *a).perp());
+    [Vec2::X, Vec2::Y]
+        .into_iter()
+        .chain(edge_normals)
+        .filter(|axis| *axis != Vec2::ZERO)
+        .all(|axis| {
+            let (poly_min, poly_max) = vertices
+                .iter()
+                .map(|v| v.dot(axis))
+                .fold((f32::MAX, f32::MIN), |(lo, hi), d| (lo.min(d), hi.max(d)));
+            let cell_mid = cell_center.dot(axis);
+            let cell_reach = half_cell * (axis.x.abs() + axis.y.abs());
+            let overlap = poly_max.min(cell_mid + cell_reach) - poly_min.max(cell_mid - cell_reach);
+            overlap > MIN_OVERLAP * axis.length()
+        })
+}
+
+/// Block cells where water is deeper than the grid's wading depth
+pub fn block_deep_water(
+    nav_grid: &mut NavigationGrid,
+    terrain: &TerrainData,
+    bodies: &[WaterBody],
+) {
+    if bodies.is_empty() {
+        return;
+    }
+    let depths = water_depth_map(terrain, bodies);
+    let cell_to_sample = nav_grid.cell_size / terrain.scale;
+    let max_depth = nav_grid.config.max_wading_depth;
+
+    let mut blocked = 0;
+    for z in 0..nav_grid.height {
+        for x in 0..nav_grid.width {
+            let depth = sample_bilinear(
+                &depths,
+                terrain.width,
+                terrain.height,
+                x as f32 * cell_to_sample,
+                z as f32 * cell_to_sample,
+            )
+            .unwrap_or(0.0);
+            if depth > max_depth {
+                nav_grid.set_cell_walkable_with_priority(
+                    GridNode::new(x, z),
+                    false,
+                    WATER_PRIORITY,
+                );
+                blocked += 1;
+            }
+        }
+    }
+
+    if blocked > 0 {
+        nav_grid.refresh_regions();
+        info!("Blocked {blocked} navigation cells under deep water");
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -146,4 +246,91 @@ mod tests {
         assert_eq!(nav_grid.width, 4);
         assert_eq!(nav_grid.height, 4);
     }
+
+    #[test]
+    fn test_polygon_blocking_with_priority() {
+        let terrain = TerrainData::create_flat(16, 16, 1.0, 0.0).unwrap();
+        let mut nav_grid =
+            NavigationGrid::from_terrain(&terrain, PathfindingConfig::default()).unwrap();
+
+        // Thin wall along Z, 1 unit wide and 8 units long
+        let wall = [
+            Vec2::new(-0.5, -4.0),
+            Vec2::new(0.5, -4.0),
+            Vec2::new(0.5, 4.0),
+            Vec2::new(-0.5, 4.0),
+        ];
+        block_polygon_area_with_priority(&mut nav_grid, Vec3::ZERO, &wall, 255);
+
+        let inside = nav_grid.world_to_grid(Vec3::new(0.0, 0.0, 3.0)).unwrap();
+        assert!(!nav_grid.is_walkable(inside.x, inside.z));
+        assert_eq!(nav_grid.get_obstacle_priority(inside), 255);
+
+        let beside = nav_grid.world_to_grid(Vec3::new(3.0, 0.0, 0.0)).unwrap();
+        assert!(
+            nav_grid.is_walkable(beside.x, beside.z),
+            "Cells beside the wall must stay walkable"
+        );
+
+        // Partially and fully off-grid polygons should not panic
+        block_polygon_area_with_priority(&mut nav_grid, Vec3::new(8.0, 0.0, 0.0), &wall, 100);
+        block_polygon_area_with_priority(&mut nav_grid, Vec3::new(100.0, 0.0, 0.0), &wall, 100);
+    }
+
+    #[test]
+    fn test_thin_polygon_blocks_touched_cells() {
+        let terrain = TerrainData::create_flat(16, 16, 1.0, 0.0).unwrap();
+        let mut nav_grid =
+            NavigationGrid::from_terrain(&terrain, PathfindingConfig::default()).unwrap();
+
+        // Fence plank 0.15 thick that sits between two rows of cell centers
+        let plank = [
+            Vec2::new(-2.0, -0.075),
+            Vec2::new(2.0, -0.075),
+            Vec2::new(2.0, 0.075),
+            Vec2::new(-2.0, 0.075),
+        ];
+        let center = Vec3::new(0.0, 0.0, 0.4);
+        block_polygon_area_with_priority(&mut nav_grid, center, &plank, 200);
+
+        for x in [-2.0, 0.0, 2.0] {
+            let under = nav_grid.world_to_grid(Vec3::new(x, 0.0, 0.0)).unwrap();
+            assert!(
+                !nav_grid.is_walkable(under.x, under.z),
+                "Cells the plank crosses must be blocked"
+            );
+        }
+        let next_row = nav_grid.world_to_grid(Vec3::new(0.0, 0.0, 1.0)).unwrap();
+        assert!(nav_grid.is_walkable(next_row.x, next_row.z));
+        let past_end = nav_grid.world_to_grid(Vec3::new(3.0, 0.0, 0.0)).unwrap();
+        assert!(nav_grid.is_walkable(past_end.x, past_end.z));
+    }
+
+    #[test]
+    fn test_deep_water_blocking() {
+        use crate::map::WaterKind;
+
+        let terrain = TerrainData::create_flat(8, 8, 1.0, 0.0).unwrap();
+        let mut nav_grid =
+            NavigationGrid::from_terrain(&terrain, PathfindingConfig::default()).unwrap();
+
+        // Deep lake on the left columns, a shallow ford on the right
+        let mut lake = WaterBody::new(WaterKind::Lake);
+        let mut ford = WaterBody::new(WaterKind::River);
+        for z in 0..8 {
+            for x in 0..2 {
+                lake.push(z * 8 + x, 2.0);
+            }
+            ford.push(z * 8 + 6, 0.2);
+        }
+        block_deep_water(&mut nav_grid, &terrain, &[lake, ford]);
+
+        assert!(!nav_grid.is_walkable(0, 4));
+        assert_eq!(
+            nav_grid.get_obstacle_priority(GridNode::new(0, 4)),
+            WATER_PRIORITY
+        );
+        assert!(nav_grid.is_walkable(6, 4), "Shallow water stays walkable");
+        assert!(nav_grid.is_walkable(4, 4));
+    }
 }
@@ -0,0 +1,363 @@
+//! Jump Point Search over the 4-connected navigation grid.
+//!
+//! Vertical moves play the role diagonals have in classic JPS: every vertical
+//! step scans left and right, while horizontal runs only turn at forced
+//! neighbors. Jumps only pass through flat cells (every step costs the base
+//! amount); sloped cells end a jump and are expanded like plain A*. The
+//! heuristic is scaled by the grid's cheapest step, so downhill runs never
+//! make it overestimate and the search stays optimal on mixed terrain.
+
+use super::{GridNode, NavigationGrid};
+use pathfinding::prelude::astar;
+
+/// Step cost between cells of equal height, see `NavigationGrid::movement_cost`
+const FLAT_STEP_COST: u32 = 10;
+
+type Direction = (i32, i32);
+
+const DIRECTIONS: [Direction; 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+
+/// Search state: a jump point plus the direction it was reached from
+type JumpState = (GridNode, Option<Direction>);
+
+fn offset(grid: &NavigationGrid, node: GridNode, (dx, dz): Direction) -> Option<GridNode> {
+    let x = node.x.checked_add_signed(dx)?;
+    let z = node.z.checked_add_signed(dz)?;
+    (x < grid.width && z < grid.height).then_some(GridNode::new(x, z))
+}
+
+fn is_free(grid: &NavigationGrid, node: Option<GridNode>) -> bool {
+    node.is_some_and(|n| grid.is_walkable(n.x, n.z))
+}
+
+/// A cell is flat when moving to or from any walkable neighbor costs the base amount
+fn is_flat(grid: &NavigationGrid, node: GridNode) -> bool {
+    DIRECTIONS.iter().all(|&d| match offset(grid, node, d) {
+        Some(n) if grid.is_walkable(n.x, n.z) => {
+            grid.movement_cost(node, n) == FLAT_STEP_COST
+                && grid.movement_cost(n, node) == FLAT_STEP_COST
+        }
+        _ => true,
+    })
+}
+
+/// Free and flat, so a straight move past it could have been taken earlier
+fn is_open(grid: &NavigationGrid, node: Option<GridNode>) -> bool {
+    node.is_some_and(|n| grid.is_walkable(n.x, n.z) && is_flat(grid, n))
+}
+
+/// Horizontal runs must turn vertically where the cell behind that side was closed
+fn forced_turns(grid: &NavigationGrid, node: GridNode, (dx, _): Direction) -> Vec<Direction> {
+    let behind = offset(grid, node, (-dx, 0));
+    [(0, 1), (0, -1)]
+        .into_iter()
+        .filter(|&side| {
+            is_free(grid, offset(grid, node, side))
+                && !is_open(grid, behind.and_then(|b| offset(grid, b, side)))
+        })
+        .collect()
+}
+
+/// Walk from `from` in `direction` until reaching a jump point, returning it and the path cost
+fn jump(
+    grid: &NavigationGrid,
+    from: GridNode,
+    direction: Direction,
+    goal: GridNode,
+) -> Option<(GridNode, u32)> {
+    let mut current = from;
+    let mut cost = 0;
+
+    loop {
+        let next = offset(grid, current, direction)?;
+        if !grid.is_walkable(next.x, next.z) {
+            return None;
+        }
+        cost += grid.movement_cost(current, next);
+
+        if next == goal || !is_flat(grid, next) {
+            return Some((next, cost));
+        }
+
+        let is_jump_point = if direction.1 == 0 {
+            !forced_turns(grid, next, direction).is_empty()
+        } else {
+            jump(grid, next, (1, 0), goal).is_some() || jump(grid, next, (-1, 0), goal).is_some()
+        };
+        if is_jump_point {
+            return Some((next, cost));
+        }
+
+        current = next;
+    }
+}
+
+/// Directions worth searching from a jump point given how it was reached
+fn pruned_directions(
+    grid: &NavigationGrid,
+    node: GridNode,
+    arrived: Option<Direction>,
+) -> Vec<Direction> {
+    let Some(direction) = arrived.filter(|_| is_flat(grid, node)) else {
+        return DIRECTIONS.to_vec();
+    };
+
+    if direction.1 == 0 {
+        let mut directions = vec![direction];
+        directions.extend(forced_turns(grid, node, direction));
+        directions
+    } else {
+        vec![direction, (1, 0), (-1, 0)]
+    }
+}
+
+/// Find a cell path from `start` to `goal` using Jump Point Search
+pub(crate) fn search(
+    grid: &NavigationGrid,
+    start: GridNode,
+    goal: GridNode,
+    mut explored: Option<&mut Vec<GridNode>>,
+) -> Option<Vec<GridNode>> {
+    let step_cost = grid.min_movement_cost();
+    let (states, _cost) = astar(
+        &(start, None),
+        |&(node, arrived): &JumpState| {
+            if let Some(explored) = explored.as_deref_mut() {
+                explored.push(node);
+            }
+            pruned_directions(grid, node, arrived)
+                .into_iter()
+                .filter_map(|direction| {
+                    jump(grid, node, direction, goal)
+                        .map(|(next, cost)| ((next, Some(direction)), cost))
+                })
+                .collect::<Vec<_>>()
+        },
+        |(node, _)| node.manhattan_distance(&goal) * step_cost,
+        |(node, _)| *node == goal,
+    )?;
+
+    // Fill in the straight runs between jump points
+    let mut cells = vec![start];
+    for pair in states.windows(2) {
+        let ((from, _), (to, Some(direction))) = (pair[0], pair[1]) else {
+            continue;
+        };
+        let mut current = from;
+        while current != to {
+            current = offset(grid, current, direction)?;
+            cells.push(current);
+        }
+    }
+
+    Some(cells)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map::TerrainData;
+    use crate::pathfinding::{PathfindingConfig, astar_grid_path};
+    use bevy::prelude::Vec2;
+    use pathfinding::prelude::dijkstra;
+    use rand::{Rng, SeedableRng};
+    use rand_pcg::Pcg64;
+
+    fn path_cost(grid: &NavigationGrid, path: &[GridNode]) -> u32 {
+        path.windows(2)
+            .map(|step| grid.movement_cost(step[0], step[1]))
+            .sum()
+    }
+
+    /// Exact cheapest cost from Dijkstra, independent of any search heuristic
+    fn optimal_cost(grid: &NavigationGrid, start: GridNode, goal: GridNode) -> Option<u32> {
+        dijkstra(
+            &start,
+            |node| {
+                node.neighbors(grid.width, grid.height)
+                    .into_iter()
+                    .filter(|neighbor| grid.is_walkable(neighbor.x, neighbor.z))
+                    .map(|neighbor| (neighbor, grid.movement_cost(*node, neighbor)))
+                    .collect::<Vec<_>>()
+            },
+            |node| *node == goal,
+        )
+        .map(|(_, cost)| cost)
+    }
+
+    fn assert_valid_path(grid: &NavigationGrid, path: &[GridNode]) {
+        assert!(path.iter().all(|n| grid.is_walkable(n.x, n.z)));
+        for step in path.windows(2) {
+            assert_eq!(
+                step[0].manhattan_distance(&step[1]),
+                1,
+                "Steps must be adjacent"
+            );
+        }
+    }
+
+    /// Random obstacle field, optionally with raised plateaus to create slopes
+    fn random_grid(rng: &mut Pcg64, size: u32, with_hills: bool) -> NavigationGrid {
+        let heights = (0..size * size)
+            .map(|_| {
+                if with_hills && rng.gen_bool(0.2) {
+                    rng.gen_range(1..4) as f32 * 0.5
+                } else {
+                    0.0
+                }
+            })
+            .collect();
+        let terrain = TerrainData::new(size, size, heights, 1.0).unwrap();
+        let config = PathfindingConfig {
+            max_walkable_slope: 90.0,
+            ..Default::default()
+        };
+        let mut grid = NavigationGrid::from_terrain(&terrain, config).unwrap();
+
+        let density = rng.gen_range(0.0..0.35);
+        for z in 0..size {
+            for x in 0..size {
+                if rng.gen_bool(density) {
+                    grid.set_cell_walkable_with_priority(GridNode::new(x, z), false, 200);
+                }
+            }
+        }
+        grid
+    }
+
+    #[test]
+    fn test_jps_matches_astar_on_random_flat_grids() {
+        let mut rng = Pcg64::seed_from_u64(33);
+        for _ in 0..200 {
+            let size = rng.gen_range(4..24);
+            let grid = random_grid(&mut rng, size, false);
+            let start = GridNode::new(rng.gen_range(0..size), rng.gen_range(0..size));
+            let goal = GridNode::new(rng.gen_range(0..size), rng.gen_range(0..size));
+            if !grid.is_walkable(start.x, start.z) || !grid.is_walkable(goal.x, goal.z) {
+                continue;
+            }
+
+            let reference = astar_grid_path(&grid, start, goal, None);
+            let jumped = search(&grid, start, goal, None);
+            match (reference, jumped) {
+                (Some(reference), Some(jumped)) => {
+                    assert_valid_path(&grid, &jumped);
+                    assert_eq!(jumped.first(), Some(&start));
+                    assert_eq!(jumped.last(), Some(&goal));
+                    assert_eq!(path_cost(&grid, &jumped), path_cost(&grid, &reference));
+                }
+                (None, None) => {}
+                (reference, jumped) => panic!(
+                    "Reachability differs: astar={} jps={}",
+                    reference.is_some(),
+                    jumped.is_some()
+                ),
+            }
+        }
+    }
+
+    #[test]
+    fn test_jps_paths_are_valid_on_sloped_grids() {
+        let mut rng = Pcg64::seed_from_u64(7);
+        for _ in 0..200 {
+            let size = rng.gen_range(4..20);
+            let grid = random_grid(&mut rng, size, true);
+            let start = GridNode::new(rng.gen_range(0..size), rng.gen_range(0..size));
+            let goal = GridNode::new(rng.gen_range(0..size), rng.gen_range(0..size));
+            if !grid.is_walkable(start.x, start.z) || !grid.is_walkable(goal.x, goal.z) {
+                continue;
+            }
+
+            let reference = astar_grid_path(&grid, start, goal, None);
+            let jumped = search(&grid, start, goal, None);
+            assert_eq!(reference.is_some(), jumped.is_some());
+            if let Some(jumped) = jumped {
+                assert_valid_path(&grid, &jumped);
+                assert_eq!(jumped.last(), Some(&goal));
+                // Compare with the exact optimum rather than another heuristic search
+                assert_eq!(
+                    Some(path_cost(&grid, &jumped)),
+                    optimal_cost(&grid, start, goal)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_jps_expands_fewer_nodes_on_open_ground() {
+        let terrain = TerrainData::create_flat(64, 64, 1.0, 0.0).unwrap();
+        let mut grid =
+            NavigationGrid::from_terrain(&terrain, PathfindingConfig::default()).unwrap();
+        // A wall with a gap forces a detour
+        for z in 0..60 {
+            grid.set_cell_walkable_with_priority(GridNode::new(32, z), false, 200);
+        }
+
+        let start = GridNode::new(2, 2);
+        let goal = GridNode::new(60, 5);
+        let mut astar_explored = Vec::new();
+        let mut jps_explored = Vec::new();
+        let reference = astar_grid_path(&grid, start, goal, Some(&mut astar_explored)).unwrap();
+        let jumped = search(&grid, start, goal, Some(&mut jps_explored)).unwrap();
+
+        assert_eq!(path_cost(&grid, &jumped), path_cost(&grid, &reference));
+        assert!(
+            jps_explored.len() * 4 < astar_explored.len(),
+            "JPS expanded {} nodes, A* expanded {}",
+            jps_explored.len(),
+            astar_explored.len()
+        );
+    }
+
+    #[test]
+    fn test_jps_expands_fewer_nodes_on_hilly_ground() {
+        // Flat ground with a gently sloped hill in a corner, away from the route
+        let heights = (0..64 * 64)
+            .map(|i| {
+                let (x, z) = ((i % 64) as f32, (i / 64) as f32);
+                (6.0 - Vec2::new(x - 10.0, z - 54.0).length() * 0.5).max(0.0)
+            })
+            .collect();
+        let terrain = TerrainData::new(64, 64, heights, 1.0).unwrap();
+        let config = PathfindingConfig {
+            max_walkable_slope: 90.0,
+            ..Default::default()
+        };
+        let mut grid = NavigationGrid::from_terrain(&terrain, config).unwrap();
+        for z in 0..30 {
+            grid.set_cell_walkable_with_priority(GridNode::new(32, z), false, 200);
+        }
+        // The hill only lowers the cheapest step to its steepest drop
+        assert!(grid.min_movement_cost() >= 7);
+
+        let start = GridNode::new(2, 2);
+        let goal = GridNode::new(60, 5);
+        let mut dijkstra_expanded = 0;
+        dijkstra(
+            &start,
+            |node| {
+                dijkstra_expanded += 1;
+                node.neighbors(grid.width, grid.height)
+                    .into_iter()
+                    .filter(|neighbor| grid.is_walkable(neighbor.x, neighbor.z))
+                    .map(|neighbor| (neighbor, grid.movement_cost(*node, neighbor)))
+                    .collect::<Vec<_>>()
+            },
+            |node| *node == goal,
+        )
+        .unwrap();
+        let mut jps_explored = Vec::new();
+        let jumped = search(&grid, start, goal, Some(&mut jps_explored)).unwrap();
+
+        assert_eq!(
+            Some(path_cost(&grid, &jumped)),
+            optimal_cost(&grid, start, goal)
+        );
+        assert!(
+            jps_explored.len() * 4 < dijkstra_expanded,
+            "JPS expanded {} nodes, Dijkstra expanded {}",
+            jps_explored.len(),
+            dijkstra_expanded
+        );
+    }
+}
@@ -1,13 +1,18 @@
 use crate::components::PathfindingAgent;
 use crate::game_logic::errors::MinionResult;
-use crate::map::{EnvironmentObject, TerrainData};
+use crate::map::{EnvironmentObject, RoadSurface, TerrainData, road_surface_map};
 use crate::terrain::coordinates::*;
 use bevy::prelude::*;
 use pathfinding::prelude::astar;
 
+pub mod avoidance;
+pub mod baked;
 pub mod grid_blocking;
+pub mod jps;
 pub mod obstacles;
 
+pub use avoidance::{AvoidanceConfig, apply_local_avoidance};
+pub use baked::{BakedNavigation, InflatedLayer, NO_REGION, navigation_source_hash};
 pub use obstacles::*;
 
 /// Configuration for pathfinding grid generation
@@ -19,6 +24,24 @@ pub struct PathfindingConfig {
     pub slope_cost_factor: f32,
     /// Extra clearance (personal space) added on top of agent_radius when inflating obstacles
     pub agent_clearance_slop: f32,
+    /// World size of a navigation cell; None uses the terrain sample spacing
+    pub cell_size: Option<f32>,
+    /// Grid search algorithm used by `find_path`
+    pub search: PathSearch,
+    /// Deepest water in world units that agents can wade through
+    pub max_wading_depth: f32,
+    /// Movement cost multiplier for steps onto road cells
+    pub road_cost_factor: f32,
+}
+
+/// Grid search algorithms available to `find_path`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PathSearch {
+    /// Plain A* over every cell
+    #[default]
+    AStar,
+    /// Jump Point Search across flat cells, falling back to A* expansion on slopes
+    JumpPoint,
 }
 
 impl Default for PathfindingConfig {
@@ -27,6 +50,10 @@ impl Default for PathfindingConfig {
             max_walkable_slope: 45.0,  // 45 degrees max slope
             slope_cost_factor: 0.5,    // Linear slope cost factor
             agent_clearance_slop: 0.2, // Personal space/slop in world units
+            cell_size: None,           // Match terrain resolution
+            search: PathSearch::AStar, // Reference search; JPS is opt-in
+            max_wading_depth: 0.6,     // Roughly knee deep
+            road_cost_factor: 0.7,     // Prefer roads without long detours
         }
     }
 }
@@ -103,6 +130,14 @@ pub struct NavigationGrid {
     pub terrain_scale: f32,
     /// Pathfinding configuration used to generate this grid
     pub config: PathfindingConfig,
+    /// Pre-inflated walkability per agent radius, cleared when cells change
+    pub inflated_layers: Vec<InflatedLayer>,
+    /// Connected region label per cell, empty when out of date
+    pub regions: Vec<u32>,
+    /// Road surface per cell, empty when the map has no roads
+    pub roads: Vec<bool>,
+    /// Cheapest step between adjacent cells, cached for the search heuristics
+    pub min_step_cost: u32,
 }
 
 impl NavigationGrid {
@@ -117,22 +152,54 @@ impl NavigationGrid {
         objects: &[EnvironmentObject],
         config: PathfindingConfig,
     ) -> MinionResult<Self> {
-        let total_cells = (terrain.width * terrain.height) as usize;
+        let cell_size = config.cell_size.unwrap_or(terrain.scale);
+        if !cell_size.is_finite() || cell_size <= 0.0 {
+            return Err(crate::game_logic::errors::MinionError::InvalidConfig {
+                reason: format!("Navigation cell size must be positive, got {cell_size}"),
+            });
+        }
+
+        // Grid covers the same world extent as the terrain samples
+        let extent_x = terrain.width.saturating_sub(1) as f32 * terrain.scale;
+        let extent_z = terrain.height.saturating_sub(1) as f32 * terrain.scale;
+        let width = (extent_x / cell_size + 1e-3).floor() as u32 + 1;
+        let height = (extent_z / cell_size + 1e-3).floor() as u32 + 1;
+
+        let terrain_slopes = Self::calculate_terrain_slopes(terrain);
+        let total_cells = (width * height) as usize;
         let mut walkable = Vec::with_capacity(total_cells);
         let mut heights = Vec::with_capacity(total_cells);
         let obstacle_priorities = vec![0u8; total_cells]; // Initialize priorities
 
-        // Direct iteration - grid coordinates = terrain coordinates
-        for z in 0..terrain.height {
-            for x in 0..terrain.width {
-                let height = get_height_at_grid(terrain, x, z).ok_or_else(|| {
+        // Resample terrain heights and slopes at each navigation cell
+        let cell_to_sample = cell_size / terrain.scale;
+        for z in 0..height {
+            for x in 0..width {
+                let sample_x = x as f32 * cell_to_sample;
+                let sample_z = z as f32 * cell_to_sample;
+                let cell_height = sample_bilinear(
+                    &terrain.heights,
+                    terrain.width,
+                    terrain.height,
+                    sample_x,
+                    sample_z,
+                )
+                .ok_or_else(|| {
                     crate::game_logic::errors::MinionError::InvalidMapData {
                         reason: format!("Failed to get height at position ({x}, {z})"),
                     }
                 })?;
-
-                heights.push(height);
-                walkable.push(Self::calculate_walkability(terrain, x, z, &config));
+                let slope = sample_bilinear(
+                    &terrain_slopes,
+                    terrain.width,
+                    terrain.height,
+                    sample_x,
+                    sample_z,
+                )
+                .unwrap_or(f32::INFINITY);
+
+                heights.push(cell_height);
+                walkable.push(slope <= config.max_walkable_slope);
             }
         }
 
@@ -140,14 +207,19 @@ impl NavigationGrid {
             walkable,
             heights,
             obstacle_priorities, // Add priority tracking
-            width: terrain.width,
-            height: terrain.height,
-            cell_size: terrain.scale,
+            width,
+            height,
+            cell_size,
             terrain_width: terrain.width,
             terrain_height: terrain.height,
             terrain_scale: terrain.scale,
             config,
+            inflated_layers: Vec::new(),
+            regions: Vec::new(),
+            roads: Vec::new(),
+            min_step_cost: 0,
         };
+        nav_grid.refresh_min_movement_cost();
 
         // NEW: Use trait-based obstacle system
         let mut obstacle_manager = ObstacleManager::new();
@@ -160,6 +232,7 @@ impl NavigationGrid {
         );
 
         obstacle_manager.apply_to_navigation_grid(&mut nav_grid);
+        nav_grid.refresh_regions();
 
         // Count blocked cells after obstacle application
         let blocked_count = nav_grid.walkable.iter().filter(|&&w| !w).count();
@@ -174,34 +247,32 @@ impl NavigationGrid {
         Ok(nav_grid)
     }
 
-    /// Calculate if a terrain cell is walkable based on slope
-    fn calculate_walkability(
-        terrain: &TerrainData,
-        x: u32,
-        z: u32,
-        config: &PathfindingConfig,
-    ) -> bool {
-        let Some(current_height) = get_height_at_grid(terrain, x, z) else {
-            return false;
-        };
-
-        // Check slopes to all neighbors
-        for (nx, nz) in [
-            (x.saturating_sub(1), z),
-            (x + 1, z),
-            (x, z.saturating_sub(1)),
-            (x, z + 1),
-        ] {
-            if let Some(neighbor_height) = get_height_at_grid(terrain, nx, nz) {
-                let slope_angle = ((neighbor_height - current_height).abs() / terrain.scale)
-                    .atan()
-                    .to_degrees();
-                if slope_angle > config.max_walkable_slope {
-                    return false;
+    /// Calculate the steepest slope (degrees) from each terrain sample to its neighbors
+    fn calculate_terrain_slopes(terrain: &TerrainData) -> Vec<f32> {
+        let mut slopes = Vec::with_capacity(terrain.heights.len());
+        for z in 0..terrain.height {
+            for x in 0..terrain.width {
+                let current_height = get_height_at_grid(terrain, x, z).unwrap_or(0.0);
+                let mut max_slope: f32 = 0.0;
+
+                for (nx, nz) in [
+                    (x.saturating_sub(1), z),
+                    (x + 1, z),
+                    (x, z.saturating_sub(1)),
+                    (x, z + 1),
+                ] {
+                    if let Some(neighbor_height) = get_height_at_grid(terrain, nx, nz) {
+                        let slope_angle = ((neighbor_height - current_height).abs()
+                            / terrain.scale)
+                            .atan()
+                            .to_degrees();
+                        max_slope = max_slope.max(slope_angle);
+                    }
                 }
+                slopes.push(max_slope);
             }
         }
-        true
+        slopes
     }
 
     /// Check if a grid position is walkable
@@ -232,18 +303,90 @@ impl NavigationGrid {
 
         // Linear slope-based cost calculation
         let slope_factor = 1.0 + (height_diff * self.config.slope_cost_factor);
-        let movement_cost = base_cost * slope_factor.max(0.1); // Minimum cost
+        let mut movement_cost = base_cost * slope_factor.max(0.1); // Minimum cost
+        if self.is_road(to) {
+            movement_cost *= self.config.road_cost_factor;
+        }
+
+        // Never free, so a steep downhill road step can't zero the heuristics
+        (movement_cost as u32).max(1)
+    }
 
-        movement_cost as u32
+    /// Cheapest `movement_cost` between any two adjacent walkable cells, so
+    /// search heuristics scaled by it never overestimate on downhill runs
+    pub fn min_movement_cost(&self) -> u32 {
+        self.min_step_cost
+    }
+
+    /// Recompute the cheapest step after heights or road surfaces change.
+    ///
+    /// Blocking cells only removes steps, so the cached bound stays a valid
+    /// lower bound without a refresh.
+    pub fn refresh_min_movement_cost(&mut self) {
+        let grid = &*self;
+        let min_step_cost = (0..grid.height)
+            .flat_map(|z| (0..grid.width).map(move |x| GridNode::new(x, z)))
+            .filter(|node| grid.is_walkable(node.x, node.z))
+            .flat_map(|node| {
+                [
+                    GridNode::new(node.x + 1, node.z),
+                    GridNode::new(node.x, node.z + 1),
+                ]
+                .into_iter()
+                .filter(|next| grid.is_walkable(next.x, next.z))
+                .map(move |next| {
+                    grid.movement_cost(node, next)
+                        .min(grid.movement_cost(next, node))
+                })
+            })
+            .min()
+            .unwrap_or(10);
+        self.min_step_cost = min_step_cost;
+    }
+
+    /// Check if a grid cell lies on a road
+    pub fn is_road(&self, node: GridNode) -> bool {
+        if node.x >= self.width || node.z >= self.height {
+            return false;
+        }
+        let index = (node.z * self.width + node.x) as usize;
+        self.roads.get(index).copied().unwrap_or(false)
+    }
+
+    /// Mark cells whose nearest terrain sample is paved
+    pub fn set_road_surfaces(&mut self, terrain: &TerrainData, roads: &[RoadSurface]) {
+        if roads.is_empty() {
+            self.roads.clear();
+            self.refresh_min_movement_cost();
+            return;
+        }
+
+        let surfaces = road_surface_map(terrain, roads);
+        let cell_to_sample = self.cell_size / terrain.scale;
+        self.roads = (0..self.height)
+            .flat_map(|z| (0..self.width).map(move |x| (x, z)))
+            .map(|(x, z)| {
+                let sample_x = ((x as f32 * cell_to_sample).round() as u32).min(terrain.width - 1);
+                let sample_z = ((z as f32 * cell_to_sample).round() as u32).min(terrain.height - 1);
+                surfaces[(sample_z * terrain.width + sample_x) as usize].is_some()
+            })
+            .collect();
+        self.refresh_min_movement_cost();
+    }
+
+    /// Get the world XZ position of grid cell (0, 0)
+    fn grid_origin(&self) -> Vec2 {
+        Vec2::new(
+            -(self.terrain_width as f32 * self.terrain_scale) / 2.0,
+            -(self.terrain_height as f32 * self.terrain_scale) / 2.0,
+        )
     }
 
     /// Convert world position to grid coordinates, returning None if out of bounds
     pub fn world_to_grid(&self, world_pos: Vec3) -> Option<GridNode> {
-        let half_width = (self.terrain_width as f32 * self.terrain_scale) / 2.0;
-        let half_height = (self.terrain_height as f32 * self.terrain_scale) / 2.0;
-
-        let x = ((world_pos.x + half_width) / self.terrain_scale).round();
-        let z = ((world_pos.z + half_height) / self.terrain_scale).round();
+        let origin = self.grid_origin();
+        let x = ((world_pos.x - origin.x) / self.cell_size).round();
+        let z = ((world_pos.z - origin.y) / self.cell_size).round();
 
         if x >= 0.0 && z >= 0.0 && x < self.width as f32 && z < self.height as f32 {
             Some(GridNode::new(x as u32, z as u32))
@@ -252,6 +395,16 @@ impl NavigationGrid {
         }
     }
 
+    /// Convert grid coordinates to a world position on the navigation surface
+    pub fn grid_to_world(&self, node: GridNode) -> Vec3 {
+        let origin = self.grid_origin();
+        Vec3::new(
+            origin.x + node.x as f32 * self.cell_size,
+            self.get_height_at_grid(node).unwrap_or(0.0),
+            origin.y + node.z as f32 * self.cell_size,
+        )
+    }
+
     /// Check if a position is within the navigation grid bounds
     pub fn is_position_in_bounds(&self, world_pos: Vec3) -> bool {
         self.world_to_grid(world_pos).is_some()
@@ -289,10 +442,32 @@ impl NavigationGrid {
         }
 
         if let Some(cell) = self.walkable.get_mut(index) {
+            if *cell != walkable {
+                // Cached inflation and connectivity no longer describe this grid
+                self.inflated_layers.clear();
+                self.regions.clear();
+            }
             *cell = walkable;
         }
     }
 
+    /// Recompute connected region labels for the current walkability
+    pub fn refresh_regions(&mut self) {
+        self.regions = baked::label_regions(&self.walkable, self.width, self.height);
+    }
+
+    /// Region label of a cell, None if blocked or regions are out of date
+    pub fn region_at(&self, node: GridNode) -> Option<u32> {
+        if node.x >= self.width || node.z >= self.height {
+            return None;
+        }
+        let index = (node.z * self.width + node.x) as usize;
+        self.regions
+            .get(index)
+            .copied()
+            .filter(|&region| region != NO_REGION)
+    }
+
     /// Get obstacle priority at cell
     pub fn get_obstacle_priority(&self, node: GridNode) -> u8 {
         if node.x >= self.width || node.z >= self.height {
@@ -304,7 +479,32 @@ impl NavigationGrid {
 
     /// Clone the navigation grid and inflate obstacles by agent radius + slop
     pub fn clone_and_inflate(&self, agent_radius: f32, slop: f32) -> NavigationGrid {
-        let mut inflated_grid = self.clone();
+        // Copy the base grid without its caches; the inflated clone is short-lived
+        let mut inflated_grid = NavigationGrid {
+            walkable: self.walkable.clone(),
+            heights: self.heights.clone(),
+            obstacle_priorities: self.obstacle_priorities.clone(),
+            width: self.width,
+            height: self.height,
+            cell_size: self.cell_size,
+            terrain_width: self.terrain_width,
+            terrain_height: self.terrain_height,
+            terrain_scale: self.terrain_scale,
+            config: self.config.clone(),
+            inflated_layers: Vec::new(),
+            regions: Vec::new(),
+            roads: self.roads.clone(),
+            min_step_cost: self.min_step_cost,
+        };
+
+        if let Some(layer) = self
+            .inflated_layers
+            .iter()
+            .find(|layer| layer.matches(agent_radius, slop))
+        {
+            inflated_grid.walkable.clone_from(&layer.walkable);
+            return inflated_grid;
+        }
 
         // Calculate inflation radius in grid cells
         let inflation_radius = agent_radius + slop;
@@ -378,6 +578,27 @@ impl NavigationGrid {
     }
 }
 
+/// Bilinearly sample a row-major grid of values at fractional coordinates, clamped to its edges
+fn sample_bilinear(values: &[f32], width: u32, height: u32, x: f32, z: f32) -> Option<f32> {
+    if width == 0 || height == 0 {
+        return None;
+    }
+    let x = x.clamp(0.0, (width - 1) as f32);
+    let z = z.clamp(0.0, (height - 1) as f32);
+
+    let x0 = x.floor() as u32;
+    let z0 = z.floor() as u32;
+    let x1 = (x0 + 1).min(width - 1);
+    let z1 = (z0 + 1).min(height - 1);
+    let fx = x - x0 as f32;
+    let fz = z - z0 as f32;
+
+    let at = |gx: u32, gz: u32| values.get((gz * width + gx) as usize).copied();
+    let h0 = at(x0, z0)? * (1.0 - fx) + at(x1, z0)? * fx;
+    let h1 = at(x0, z1)? * (1.0 - fx) + at(x1, z1)? * fx;
+    Some(h0 * (1.0 - fz) + h1 * fz)
+}
+
 /// Filter waypoints to improve spacing while preserving path accuracy
 /// Uses a greedy approach: keep waypoints that are at least min_distance apart,
 /// but always keep the final waypoint to ensure we reach the destination
@@ -411,12 +632,96 @@ fn filter_waypoints_for_spacing(waypoints: Vec<Vec3>, min_distance: f32) -> Vec<
     filtered
 }
 
+/// Result of a single path query, including the nodes A* expanded
+#[derive(Debug, Clone, Default)]
+pub struct PathQuery {
+    pub start: Vec3,
+    pub goal: Vec3,
+    pub agent_radius: f32,
+    /// Filtered world waypoints, None if no path was found
+    pub path: Option<Vec<Vec3>>,
+    /// Grid nodes expanded by the search, in expansion order
+    pub explored: Vec<GridNode>,
+}
+
+/// Most recent traced query from `plan_paths`, recorded only while `capture` is set
+#[derive(Debug, Default, Resource)]
+pub struct LastPathQuery {
+    pub capture: bool,
+    pub query: Option<PathQuery>,
+}
+
 /// Find a path between two world positions using A* pathfinding
 pub fn find_path(
     navigation_grid: &NavigationGrid,
     start_world: Vec3,
     goal_world: Vec3,
     agent_radius: f32,
+) -> Option<Vec<Vec3>> {
+    search_path(navigation_grid, start_world, goal_world, agent_radius, None)
+}
+
+/// Find a path and record the explored set for debugging and benchmarking
+pub fn find_path_traced(
+    navigation_grid: &NavigationGrid,
+    start_world: Vec3,
+    goal_world: Vec3,
+    agent_radius: f32,
+) -> PathQuery {
+    let mut explored = Vec::new();
+    let path = search_path(
+        navigation_grid,
+        start_world,
+        goal_world,
+        agent_radius,
+        Some(&mut explored),
+    );
+
+    PathQuery {
+        start: start_world,
+        goal: goal_world,
+        agent_radius,
+        path,
+        explored,
+    }
+}
+
+/// Plain A* between two cells, recording expanded nodes when asked
+pub(crate) fn astar_grid_path(
+    grid: &NavigationGrid,
+    start: GridNode,
+    goal: GridNode,
+    mut explored: Option<&mut Vec<GridNode>>,
+) -> Option<Vec<GridNode>> {
+    // Scale by the cheapest step so roads and downhill runs never make it overestimate
+    let step_cost = grid.min_movement_cost() as f32;
+    let (path, _cost) = astar(
+        &start,
+        |node| {
+            let current_node = *node;
+            if let Some(explored) = explored.as_deref_mut() {
+                explored.push(current_node);
+            }
+            let neighbors: Vec<_> = node
+                .neighbors(grid.width, grid.height)
+                .into_iter()
+                .filter(|neighbor| grid.is_walkable(neighbor.x, neighbor.z))
+                .map(|neighbor| (neighbor, grid.movement_cost(current_node, neighbor)))
+                .collect();
+            neighbors
+        },
+        |node| (node.euclidean_distance(&goal) * step_cost) as u32,
+        |node| *node == goal,
+    )?;
+    Some(path)
+}
+
+fn search_path(
+    navigation_grid: &NavigationGrid,
+    start_world: Vec3,
+    goal_world: Vec3,
+    agent_radius: f32,
+    explored: Option<&mut Vec<GridNode>>,
 ) -> Option<Vec<Vec3>> {
     // Clone and inflate the navigation grid for this agent size so we don't mutate the base grid
     let inflated_grid = navigation_grid
@@ -445,6 +750,14 @@ pub fn find_path(
         inflated_grid.is_walkable(goal_node.x, goal_node.z)
     );
 
+    // Cells in different base regions can never connect, so skip the search
+    let start_region = navigation_grid.region_at(start_node);
+    let goal_region = navigation_grid.region_at(goal_node);
+    if start_region.is_some() && goal_region.is_some() && start_region != goal_region {
+        debug!("Pathfinding skipped: start and goal are in disconnected regions");
+        return None;
+    }
+
     // Check if start and goal are walkable on the inflated grid
     if !inflated_grid.is_walkable(start_node.x, start_node.z)
         || !inflated_grid.is_walkable(goal_node.x, goal_node.z)
@@ -457,41 +770,17 @@ pub fn find_path(
         return None;
     }
 
-    // Use A* to find the path on the inflated grid
-    let (path, _cost) = astar(
-        &start_node,
-        |node| {
-            let current_node = *node;
-            let neighbors: Vec<_> = node
-                .neighbors(inflated_grid.width, inflated_grid.height)
-                .into_iter()
-                .filter(|neighbor| inflated_grid.is_walkable(neighbor.x, neighbor.z))
-                .map(|neighbor| {
-                    (
-                        neighbor,
-                        inflated_grid.movement_cost(current_node, neighbor),
-                    )
-                })
-                .collect();
-            neighbors
-        },
-        |node| (node.euclidean_distance(&goal_node) * 10.0) as u32,
-        |node| *node == goal_node,
-    )?;
+    // Search the inflated grid with the configured algorithm
+    let path = match navigation_grid.config.search {
+        PathSearch::AStar => astar_grid_path(&inflated_grid, start_node, goal_node, explored),
+        PathSearch::JumpPoint => jps::search(&inflated_grid, start_node, goal_node, explored),
+    }?;
 
     // Convert grid path to world coordinates (use original grid for height data)
-    let half_width = (navigation_grid.terrain_width as f32 * navigation_grid.terrain_scale) / 2.0;
-    let half_height = (navigation_grid.terrain_height as f32 * navigation_grid.terrain_scale) / 2.0;
-
     let path_length = path.len(); // Store length before moving path
     let world_path: Vec<Vec3> = path
         .into_iter()
-        .map(|node| {
-            let world_x = (node.x as f32 * navigation_grid.terrain_scale) - half_width;
-            let world_z = (node.z as f32 * navigation_grid.terrain_scale) - half_height;
-            let height = navigation_grid.get_height_at_grid(node).unwrap_or(0.0);
-            Vec3::new(world_x, height, world_z)
-        })
+        .map(|node| navigation_grid.grid_to_world(node))
         .collect();
 
     // Filter waypoints to improve spacing while preserving path accuracy
@@ -584,18 +873,34 @@ pub fn plan_paths(
     mut agents_query: Query<(&mut PathfindingAgent, &Transform)>,
     navigation_grid: Res<NavigationGrid>,
     time: Res<Time>,
+    mut last_query: Option<ResMut<LastPathQuery>>,
 ) {
     let current_time = time.elapsed_secs();
 
     for (mut agent, transform) in agents_query.iter_mut() {
         if should_replan_path(&agent, current_time, transform.translation) {
             if let Some(destination) = agent.destination {
-                if let Some(new_path) = find_path(
-                    &navigation_grid,
-                    transform.translation,
-                    destination,
-                    agent.agent_radius,
-                ) {
+                let planned = match last_query.as_deref_mut() {
+                    Some(last) if last.capture => {
+                        let query = find_path_traced(
+                            &navigation_grid,
+                            transform.translation,
+                            destination,
+                            agent.agent_radius,
+                        );
+                        let path = query.path.clone();
+                        last.query = Some(query);
+                        path
+                    }
+                    _ => find_path(
+                        &navigation_grid,
+                        transform.translation,
+                        destination,
+                        agent.agent_radius,
+                    ),
+                };
+
+                if let Some(new_path) = planned {
                     let path_length = new_path.len();
                     agent.set_path(new_path);
                     agent.last_replan_time = current_time;
@@ -876,6 +1181,279 @@ mod tests {
         assert!(walkable_count > nav_grid.walkable.len() / 2);
     }
 
+    #[test]
+    fn test_navigation_grid_custom_cell_size() {
+        // 9 samples at 1.0 spacing span 8 world units
+        let terrain = TerrainData::create_flat(9, 9, 1.0, 2.0).unwrap();
+
+        let fine = PathfindingConfig {
+            cell_size: Some(0.5),
+            ..Default::default()
+        };
+        let nav_grid = NavigationGrid::from_terrain(&terrain, fine).unwrap();
+        assert_eq!(
+            nav_grid.width, 17,
+            "Half-size cells should double resolution"
+        );
+        assert_eq!(nav_grid.height, 17);
+        assert_eq!(nav_grid.heights.len(), 17 * 17);
+        assert!(nav_grid.heights.iter().all(|&h| (h - 2.0).abs() < 1e-5));
+
+        let coarse = PathfindingConfig {
+            cell_size: Some(2.0),
+            ..Default::default()
+        };
+        let nav_grid = NavigationGrid::from_terrain(&terrain, coarse).unwrap();
+        assert_eq!(
+            nav_grid.width, 5,
+            "Double-size cells should halve resolution"
+        );
+        assert_eq!(nav_grid.height, 5);
+
+        let invalid = PathfindingConfig {
+            cell_size: Some(0.0),
+            ..Default::default()
+        };
+        assert!(NavigationGrid::from_terrain(&terrain, invalid).is_err());
+    }
+
+    #[test]
+    fn test_navigation_grid_interpolates_heights() {
+        // Linear ramp along x: height = x sample index
+        let heights: Vec<f32> = (0..3).flat_map(|_| (0..3).map(|x| x as f32)).collect();
+        let terrain = TerrainData::new(3, 3, heights, 1.0).unwrap();
+
+        let config = PathfindingConfig {
+            cell_size: Some(0.5),
+            ..Default::default()
+        };
+        let nav_grid = NavigationGrid::from_terrain(&terrain, config).unwrap();
+
+        // Cell 1 sits halfway between samples 0 and 1
+        let height = nav_grid.get_height_at_grid(GridNode::new(1, 0)).unwrap();
+        assert!((height - 0.5).abs() < 1e-5, "Expected 0.5, got {height}");
+        let height = nav_grid.get_height_at_grid(GridNode::new(3, 2)).unwrap();
+        assert!((height - 1.5).abs() < 1e-5, "Expected 1.5, got {height}");
+    }
+
+    #[test]
+    fn test_grid_world_round_trip() {
+        let terrain = TerrainData::create_flat(16, 16, 0.5, 0.0).unwrap();
+
+        for cell_size in [0.25, 0.5, 1.0] {
+            let config = PathfindingConfig {
+                cell_size: Some(cell_size),
+                ..Default::default()
+            };
+            let nav_grid = NavigationGrid::from_terrain(&terrain, config).unwrap();
+
+            for node in [
+                GridNode::new(0, 0),
+                GridNode::new(3, 5),
+                GridNode::new(nav_grid.width - 1, nav_grid.height - 1),
+            ] {
+                let world = nav_grid.grid_to_world(node);
+                assert_eq!(
+                    nav_grid.world_to_grid(world),
+                    Some(node),
+                    "Round trip failed for {node:?} at cell size {cell_size}"
+                );
+            }
+
+            // Grid origin matches the terrain's first sample
+            let origin = nav_grid.grid_to_world(GridNode::new(0, 0));
+            assert_eq!(origin.x, -4.0);
+            assert_eq!(origin.z, -4.0);
+        }
+    }
+
+    #[test]
+    fn test_find_path_with_fine_cells() {
+        let terrain = TerrainData::create_flat(16, 16, 1.0, 0.0).unwrap();
+        let config = PathfindingConfig {
+            cell_size: Some(0.5),
+            ..Default::default()
+        };
+        let nav_grid = NavigationGrid::from_terrain(&terrain, config).unwrap();
+
+        let start = Vec3::new(-6.0, 0.0, -6.0);
+        let goal = Vec3::new(5.0, 0.0, 5.0);
+        let path = find_path(&nav_grid, start, goal, 0.5).expect("Path should exist");
+
+        assert!(path.first().unwrap().distance(start) < 0.5);
+        assert!(path.last().unwrap().distance(goal) < 0.5);
+        for waypoint in &path {
+            let node = nav_grid.world_to_grid(*waypoint).unwrap();
+            let snapped = nav_grid.grid_to_world(node);
+            assert!(
+                snapped.distance(*waypoint) < 1e-4,
+                "Waypoint should lie on a cell center"
+            );
+        }
+    }
+
+    #[test]
+    fn test_find_path_traced_matches_find_path() {
+        let terrain = TerrainData::create_flat(16, 16, 1.0, 0.0).unwrap();
+        let nav_grid =
+            NavigationGrid::from_terrain(&terrain, PathfindingConfig::default()).unwrap();
+
+        let start = Vec3::new(-5.0, 0.0, -5.0);
+        let goal = Vec3::new(5.0, 0.0, 3.0);
+        let query = find_path_traced(&nav_grid, start, goal, 0.5);
+
+        assert_eq!(query.path, find_path(&nav_grid, start, goal, 0.5));
+        assert!(query.path.is_some());
+        assert_eq!(
+            query.explored.first().copied(),
+            nav_grid.world_to_grid(start),
+            "Search should expand the start node first"
+        );
+        assert!(
+            query
+                .explored
+                .iter()
+                .all(|node| nav_grid.is_walkable(node.x, node.z))
+        );
+    }
+
+    #[test]
+    fn test_disconnected_regions_skip_search() {
+        let terrain = TerrainData::create_flat(16, 16, 1.0, 0.0).unwrap();
+        let mut nav_grid =
+            NavigationGrid::from_terrain(&terrain, PathfindingConfig::default()).unwrap();
+        for z in 0..nav_grid.height {
+            nav_grid.set_cell_walkable_with_priority(GridNode::new(8, z), false, 200);
+        }
+        assert!(
+            nav_grid.regions.is_empty(),
+            "Edits should invalidate regions"
+        );
+
+        nav_grid.refresh_regions();
+        let left = GridNode::new(2, 5);
+        let right = GridNode::new(13, 5);
+        assert_ne!(nav_grid.region_at(left), nav_grid.region_at(right));
+
+        let query = find_path_traced(
+            &nav_grid,
+            nav_grid.grid_to_world(left),
+            nav_grid.grid_to_world(right),
+            0.1,
+        );
+        assert!(query.path.is_none());
+        assert!(query.explored.is_empty(), "Region check should avoid A*");
+    }
+
+    #[test]
+    fn test_roads_lower_movement_cost() {
+        use crate::terrain::path_generator::PathType;
+
+        let terrain = TerrainData::create_flat(8, 8, 1.0, 0.0).unwrap();
+        let mut nav_grid =
+            NavigationGrid::from_terrain(&terrain, PathfindingConfig::default()).unwrap();
+        let road = RoadSurface {
+            path_type: PathType::Trail,
+            cells: (0..8).map(|x| 4 * 8 + x).collect(),
+        };
+        nav_grid.set_road_surfaces(&terrain, &[road]);
+
+        assert!(nav_grid.is_road(GridNode::new(3, 4)));
+        assert!(!nav_grid.is_road(GridNode::new(3, 3)));
+        let off_road = nav_grid.movement_cost(GridNode::new(2, 3), GridNode::new(3, 3));
+        let on_road = nav_grid.movement_cost(GridNode::new(2, 4), GridNode::new(3, 4));
+        assert!(on_road < off_road);
+
+        nav_grid.set_road_surfaces(&terrain, &[]);
+        assert!(!nav_grid.is_road(GridNode::new(3, 4)));
+    }
+
+    #[test]
+    fn test_steep_road_steps_are_never_free() {
+        use crate::terrain::path_generator::PathType;
+
+        let terrain = TerrainData::create_flat(8, 8, 1.0, 0.0).unwrap();
+        let mut nav_grid =
+            NavigationGrid::from_terrain(&terrain, PathfindingConfig::default()).unwrap();
+        // A cliff down onto the road clamps the slope factor to its minimum
+        nav_grid.heights[(4 * nav_grid.width + 3) as usize] = -50.0;
+        let road = RoadSurface {
+            path_type: PathType::MainRoad,
+            cells: (0..8).map(|x| 4 * 8 + x).collect(),
+        };
+        nav_grid.set_road_surfaces(&terrain, &[road]);
+
+        assert!(nav_grid.movement_cost(GridNode::new(3, 3), GridNode::new(3, 4)) >= 1);
+        assert!(nav_grid.min_movement_cost() >= 1);
+    }
+
+    #[test]
+    fn test_short_queries_do_not_scale_with_grid_area() {
+        use std::time::{Duration, Instant};
+
+        let time_queries = |size: u32| -> Duration {
+            let terrain = TerrainData::create_flat(size, size, 1.0, 0.0).unwrap();
+            let nav_grid =
+                NavigationGrid::from_terrain(&terrain, PathfindingConfig::default()).unwrap();
+            let started = Instant::now();
+            for _ in 0..200 {
+                astar_grid_path(&nav_grid, GridNode::new(2, 2), GridNode::new(6, 2), None).unwrap();
+            }
+            started.elapsed()
+        };
+
+        // The heuristic bound is cached, so a 1024x larger grid costs the same per query
+        let small = time_queries(16);
+        let large = time_queries(512);
+        assert!(
+            large < small * 20 + Duration::from_millis(20),
+            "200 queries took {large:?} on 512x512 and {small:?} on 16x16"
+        );
+    }
+
+    #[test]
+    fn test_astar_stays_optimal_with_roads() {
+        use crate::terrain::path_generator::PathType;
+        use pathfinding::prelude::dijkstra;
+
+        let terrain = TerrainData::create_flat(32, 32, 1.0, 0.0).unwrap();
+        let mut nav_grid =
+            NavigationGrid::from_terrain(&terrain, PathfindingConfig::default()).unwrap();
+        // A road three rows off the direct line, and one crossing it
+        let road = RoadSurface {
+            path_type: PathType::MainRoad,
+            cells: (0..32)
+                .map(|x| 3 * 32 + x)
+                .chain((0..32).map(|z| z * 32 + 20))
+                .collect(),
+        };
+        nav_grid.set_road_surfaces(&terrain, &[road]);
+
+        let cost = |path: &[GridNode]| -> u32 {
+            path.windows(2)
+                .map(|step| nav_grid.movement_cost(step[0], step[1]))
+                .sum()
+        };
+        for (start, goal) in [((0, 0), (31, 0)), ((0, 0), (31, 31)), ((5, 30), (28, 1))] {
+            let start = GridNode::new(start.0, start.1);
+            let goal = GridNode::new(goal.0, goal.1);
+            let (_, optimal) = dijkstra(
+                &start,
+                |node| {
+                    node.neighbors(nav_grid.width, nav_grid.height)
+                        .into_iter()
+                        .map(|neighbor| (neighbor, nav_grid.movement_cost(*node, neighbor)))
+                        .collect::<Vec<_>>()
+                },
+                |node| *node == goal,
+            )
+            .unwrap();
+
+            let path = astar_grid_path(&nav_grid, start, goal, None).unwrap();
+            assert_eq!(cost(&path), optimal);
+        }
+    }
+
     #[test]
     fn test_pathfinding_agent_new() {
         let agent = PathfindingAgent::new();
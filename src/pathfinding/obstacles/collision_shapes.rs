@@ -6,14 +6,115 @@ use bevy::prelude::*;
 /// Geometric shapes for collision detection
 #[derive(Debug, Clone)]
 pub enum CollisionShape {
-    Circle { radius: f32 },
-    Rectangle { half_extents: Vec3 },
-    Capsule { radius: f32, height: f32 },
-    Compound { shapes: Vec<(Vec3, CollisionShape)> },
+    Circle {
+        radius: f32,
+    },
+    Rectangle {
+        half_extents: Vec3,
+    },
+    /// Rectangle rotated about the Y axis by `yaw` radians
+    OrientedBox {
+        half_extents: Vec3,
+        yaw: f32,
+    },
+    /// Convex footprint in the XZ plane, vertices relative to the shape center in either winding
+    ConvexPolygon {
+        vertices: Vec<Vec2>,
+    },
+    Capsule {
+        radius: f32,
+        height: f32,
+    },
+    Compound {
+        shapes: Vec<(Vec3, CollisionShape)>,
+    },
     None,
 }
 
+/// Rotate an XZ offset about the Y axis, matching `Quat::from_rotation_y`
+pub fn rotate_xz(offset: Vec2, yaw: f32) -> Vec2 {
+    let (sin, cos) = yaw.sin_cos();
+    Vec2::new(
+        offset.x * cos + offset.y * sin,
+        -offset.x * sin + offset.y * cos,
+    )
+}
+
+/// Check if a point lies inside (or on the edge of) a convex polygon of either winding
+fn convex_polygon_contains(vertices: &[Vec2], point: Vec2) -> bool {
+    if vertices.len() < 3 {
+        return false;
+    }
+
+    let mut sign = 0.0_f32;
+    for (i, &a) in vertices.iter().enumerate() {
+        let b = vertices[(i + 1) % vertices.len()];
+        let cross = (b - a).perp_dot(point - a);
+        if cross.abs() <= f32::EPSILON {
+            continue;
+        }
+        if sign == 0.0 {
+            sign = cross.signum();
+        } else if cross.signum() != sign {
+            return false;
+        }
+    }
+    true
+}
+
 impl CollisionShape {
+    /// Rotate this shape about the Y axis by `yaw` radians
+    pub fn rotated(self, yaw: f32) -> CollisionShape {
+        if yaw.abs() <= f32::EPSILON {
+            return self;
+        }
+
+        match self {
+            CollisionShape::Rectangle { half_extents } => {
+                CollisionShape::OrientedBox { half_extents, yaw }
+            }
+            CollisionShape::OrientedBox {
+                half_extents,
+                yaw: existing,
+            } => CollisionShape::OrientedBox {
+                half_extents,
+                yaw: existing + yaw,
+            },
+            CollisionShape::ConvexPolygon { vertices } => CollisionShape::ConvexPolygon {
+                vertices: vertices.into_iter().map(|v| rotate_xz(v, yaw)).collect(),
+            },
+            CollisionShape::Compound { shapes } => CollisionShape::Compound {
+                shapes: shapes
+                    .into_iter()
+                    .map(|(offset, shape)| {
+                        let rotated = rotate_xz(Vec2::new(offset.x, offset.z), yaw);
+                        (
+                            Vec3::new(rotated.x, offset.y, rotated.y),
+                            shape.rotated(yaw),
+                        )
+                    })
+                    .collect(),
+            },
+            // Rotationally symmetric about Y
+            shape @ (CollisionShape::Circle { .. }
+            | CollisionShape::Capsule { .. }
+            | CollisionShape::None) => shape,
+        }
+    }
+
+    /// Get the XZ footprint corners of a box, relative to its center
+    fn box_corners(half_extents: Vec3, yaw: f32) -> Vec<Vec2> {
+        [
+            Vec2::new(-half_extents.x, -half_extents.z),
+            Vec2::new(half_extents.x, -half_extents.z),
+            Vec2::new(half_extents.x, half_extents.z),
+            Vec2::new(-half_extents.x, half_extents.z),
+        ]
+        .into_iter()
+        .map(|corner| rotate_xz(corner, yaw))
+        .collect()
+    }
+
     /// Check if a world position is inside this shape
     pub fn contains_point(&self, world_pos: Vec3, shape_center: Vec3) -> bool {
         match self {
@@ -26,6 +127,18 @@ impl CollisionShape {
                 let rel_pos = world_pos - shape_center;
                 rel_pos.x.abs() <= half_extents.x && rel_pos.z.abs() <= half_extents.z
             }
+            CollisionShape::OrientedBox { half_extents, yaw } => {
+                // Undo the rotation and test against the axis-aligned box
+                let local = rotate_xz(
+                    Vec2::new(world_pos.x - shape_center.x, world_pos.z - shape_center.z),
+                    -*yaw,
+                );
+                local.x.abs() <= half_extents.x && local.y.abs() <= half_extents.z
+            }
+            CollisionShape::ConvexPolygon { vertices } => convex_polygon_contains(
+                vertices,
+                Vec2::new(world_pos.x - shape_center.x, world_pos.z - shape_center.z),
+            ),
             CollisionShape::Capsule { radius, .. } => {
                 // Treat as circle for 2D pathfinding
                 let distance_2d =
@@ -55,6 +168,19 @@ impl CollisionShape {
                     priority,
                 );
             }
+            CollisionShape::OrientedBox { half_extents, yaw } => {
+                crate::pathfinding::grid_blocking::block_polygon_area_with_priority(
+                    nav_grid,
+                    center,
+                    &Self::box_corners(*half_extents, *yaw),
+                    priority,
+                );
+            }
+            CollisionShape::ConvexPolygon { vertices } => {
+                crate::pathfinding::grid_blocking::block_polygon_area_with_priority(
+                    nav_grid, center, vertices, priority,
+                );
+            }
             CollisionShape::Capsule { radius, .. } => {
                 crate::pathfinding::grid_blocking::block_circular_area_with_priority(
                     nav_grid, center, *radius, priority,
@@ -79,6 +205,20 @@ impl CollisionShape {
             CollisionShape::Rectangle { half_extents } => {
                 (center - *half_extents, center + *half_extents)
             }
+            CollisionShape::OrientedBox { half_extents, yaw } => {
+                let (min, max) = Self::polygon_bounds(&Self::box_corners(*half_extents, *yaw));
+                (
+                    center + Vec3::new(min.x, -half_extents.y, min.y),
+                    center + Vec3::new(max.x, half_extents.y, max.y),
+                )
+            }
+            CollisionShape::ConvexPolygon { vertices } => {
+                let (min, max) = Self::polygon_bounds(vertices);
+                (
+                    center + Vec3::new(min.x, 0.0, min.y),
+                    center + Vec3::new(max.x, 0.0, max.y),
+                )
+            }
             CollisionShape::Capsule { radius, height } => {
                 let extent = Vec3::new(*radius, *height * 0.5, *radius);
                 (center - extent, center + extent)
@@ -96,6 +236,17 @@ impl CollisionShape {
             CollisionShape::None => (center, center),
         }
     }
+
+    /// Get the XZ min/max of a set of polygon vertices
+    fn polygon_bounds(vertices: &[Vec2]) -> (Vec2, Vec2) {
+        if vertices.is_empty() {
+            return (Vec2::ZERO, Vec2::ZERO);
+        }
+        vertices.iter().fold(
+            (Vec2::splat(f32::INFINITY), Vec2::splat(f32::NEG_INFINITY)),
+            |(min, max), v| (min.min(*v), max.max(*v)),
+        )
+    }
 }
 
 #[cfg(test)]
@@ -190,4 +341,109 @@ mod tests {
         assert_eq!(min, center);
         assert_eq!(max, center);
     }
+
+    #[test]
+    fn test_oriented_box_contains_point() {
+        // Long thin box rotated 90 degrees now runs along Z instead of X
+        let shape = CollisionShape::OrientedBox {
+            half_extents: Vec3::new(3.0, 1.0, 0.5),
+            yaw: std::f32::consts::FRAC_PI_2,
+        };
+        let center = Vec3::ZERO;
+
+        assert!(shape.contains_point(Vec3::new(0.0, 0.0, 2.5), center));
+        assert!(shape.contains_point(Vec3::new(0.0, 0.0, -2.5), center));
+        assert!(!shape.contains_point(Vec3::new(2.5, 0.0, 0.0), center));
+    }
+
+    #[test]
+    fn test_oriented_box_bounds() {
+        let shape = CollisionShape::OrientedBox {
+            half_extents: Vec3::new(1.0, 2.0, 1.0),
+            yaw: std::f32::consts::FRAC_PI_4,
+        };
+        let (min, max) = shape.approximate_bounds(Vec3::ZERO);
+        let diagonal = 2.0_f32.sqrt();
+
+        assert!((max.x - diagonal).abs() < 1e-5, "Rotated box should widen");
+        assert!((min.z + diagonal).abs() < 1e-5);
+        assert_eq!(max.y, 2.0);
+    }
+
+    #[test]
+    fn test_convex_polygon_contains_point() {
+        let triangle = CollisionShape::ConvexPolygon {
+            vertices: vec![
+                Vec2::new(0.0, 2.0),
+                Vec2::new(-2.0, -1.0),
+                Vec2::new(2.0, -1.0),
+            ],
+        };
+        let center = Vec3::new(10.0, 0.0, 10.0);
+
+        assert!(triangle.contains_point(Vec3::new(10.0, 0.0, 10.0), center));
+        assert!(triangle.contains_point(Vec3::new(11.0, 0.0, 9.5), center));
+        assert!(!triangle.contains_point(Vec3::new(11.5, 0.0, 11.5), center));
+
+        // Winding order must not matter
+        let reversed = CollisionShape::ConvexPolygon {
+            vertices: vec![
+                Vec2::new(2.0, -1.0),
+                Vec2::new(-2.0, -1.0),
+                Vec2::new(0.0, 2.0),
+            ],
+        };
+        assert!(reversed.contains_point(Vec3::new(11.0, 0.0, 9.5), center));
+
+        let (min, max) = triangle.approximate_bounds(center);
+        assert_eq!(min, Vec3::new(8.0, 0.0, 9.0));
+        assert_eq!(max, Vec3::new(12.0, 0.0, 12.0));
+    }
+
+    #[test]
+    fn test_rotated_shapes() {
+        let yaw = std::f32::consts::FRAC_PI_2;
+
+        // Rectangles become oriented boxes, circles are unchanged
+        let rect = CollisionShape::Rectangle {
+            half_extents: Vec3::ONE,
+        };
+        assert!(matches!(
+            rect.rotated(yaw),
+            CollisionShape::OrientedBox { .. }
+        ));
+        let circle = CollisionShape::Circle { radius: 1.0 };
+        assert!(matches!(circle.rotated(yaw), CollisionShape::Circle { .. }));
+
+        // Zero yaw leaves rectangles axis-aligned
+        let rect = CollisionShape::Rectangle {
+            half_extents: Vec3::ONE,
+        };
+        assert!(matches!(
+            rect.rotated(0.0),
+            CollisionShape::Rectangle { .. }
+        ));
+
+        // Compound offsets rotate with the shape: +X maps to -Z for a quarter turn
+        let compound = CollisionShape::Compound {
+            shapes: vec![(
+                Vec3::new(2.0, 0.0, 0.0),
+                CollisionShape::Circle { radius: 0.5 },
+            )],
+        }
+        .rotated(yaw);
+        assert!(compound.contains_point(Vec3::new(0.0, 0.0, -2.0), Vec3::ZERO));
+        assert!(!compound.contains_point(Vec3::new(2.0, 0.0, 0.0), Vec3::ZERO));
+    }
+
+    #[test]
+    fn test_rotate_xz_matches_quat() {
+        let yaw = 0.7;
+        let offset = Vec2::new(1.5, -0.5);
+        let rotated = rotate_xz(offset, yaw);
+        let expected = Quat::from_rotation_y(yaw) * Vec3::new(offset.x, 0.0, offset.y);
+
+        assert!((rotated.x - expected.x).abs() < 1e-5);
+        assert!((rotated.y - expected.z).abs() < 1e-5);
+    }
 }
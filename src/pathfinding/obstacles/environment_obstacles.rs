@@ -1,6 +1,7 @@
 //! Static environment obstacles from map data
 
 use crate::map::EnvironmentObject;
+use crate::objects::{ObjectDefinition, ObjectRegistry};
 use crate::pathfinding::obstacles::{CollisionShape, Obstacle};
 use bevy::prelude::*;
 
@@ -16,56 +17,34 @@ pub struct EnvironmentObstacle {
 /// Strongly-typed environment object types
 #[derive(Debug, Clone)]
 pub enum EnvironmentObjectType {
-    Tree {
-        trunk_radius_factor: f32,
-    },
-    Rock {
-        collision_factor: f32,
-    },
-    Boulder {
-        collision_factor: f32,
-    },
-    Grass,
     Structure {
         collision_shape: CollisionShape,
     },
-    Custom {
+    /// Object described by an entry in the object definition registry, or
+    /// the registry's generic fallback for unknown types
+    Defined {
         name: String,
         collision_shape: CollisionShape,
+        blocking_priority: u8,
     },
 }
 
 impl From<&EnvironmentObject> for EnvironmentObstacle {
     fn from(obj: &EnvironmentObject) -> Self {
-        let object_type = match obj.object_type.as_str() {
-            "tree" => EnvironmentObjectType::Tree {
-                trunk_radius_factor: 0.3,
-            },
-            "rock" => EnvironmentObjectType::Rock {
-                collision_factor: 0.5,
-            },
-            "boulder" => EnvironmentObjectType::Boulder {
-                collision_factor: 0.5,
-            },
-            "grass" => EnvironmentObjectType::Grass,
-            name => {
-                // Default fallback for unknown types
-                let collision_shape = if obj.scale.x > 0.0 && obj.scale.z > 0.0 {
-                    CollisionShape::Rectangle {
-                        half_extents: obj.scale * 0.5,
-                    }
-                } else {
-                    CollisionShape::None
-                };
-                EnvironmentObjectType::Custom {
-                    name: name.to_string(),
-                    collision_shape,
-                }
-            }
-        };
+        let definition = ObjectRegistry::global().get_or_fallback(&obj.object_type);
+        Self::from_definition(obj, &definition)
+    }
+}
 
+impl EnvironmentObstacle {
+    /// Create an obstacle using an explicit object definition
+    pub fn from_definition(obj: &EnvironmentObject, definition: &ObjectDefinition) -> Self {
         Self {
-            object_type,
+            object_type: EnvironmentObjectType::Defined {
+                name: definition.name.clone(),
+                collision_shape: definition.nav_shape.to_collision_shape(obj.scale),
+                blocking_priority: definition.blocking_priority,
+            },
             position: obj.position,
             rotation: obj.rotation,
             scale: obj.scale,
@@ -76,34 +55,20 @@ impl From<&EnvironmentObject> for EnvironmentObstacle {
 impl Obstacle for EnvironmentObstacle {
     fn collision_shape(&self) -> CollisionShape {
         let shape = match &self.object_type {
-            EnvironmentObjectType::Tree {
-                trunk_radius_factor,
-            } => CollisionShape::Circle {
-                // Increase tree collision radius for pathfinding to account for canopy
-                radius: self.scale.x * trunk_radius_factor * 2.0, // 2x larger for pathfinding
-            },
-            EnvironmentObjectType::Rock { collision_factor }
-            | EnvironmentObjectType::Boulder { collision_factor } => CollisionShape::Circle {
-                // Increase rock/boulder collision radius for pathfinding safety margin
-                radius: self.scale.x * collision_factor * 1.5, // 1.5x larger for pathfinding
-            },
-            EnvironmentObjectType::Grass => CollisionShape::None,
-            EnvironmentObjectType::Structure { collision_shape } => collision_shape.clone(),
-            EnvironmentObjectType::Custom {
+            EnvironmentObjectType::Structure { collision_shape }
+            | EnvironmentObjectType::Defined {
                 collision_shape, ..
             } => collision_shape.clone(),
-        };
+        }
+        .rotated(self.rotation.y); // Only yaw matters for the 2D navigation grid
 
         // Debug logging for pathfinding obstacles
         if let CollisionShape::Circle { radius } = &shape {
             debug!(
                 "Pathfinding obstacle: {} at ({:.1}, {:.1}, {:.1}) radius={:.1}",
                 match &self.object_type {
-                    EnvironmentObjectType::Tree { .. } => "tree",
-                    EnvironmentObjectType::Rock { .. } => "rock",
-                    EnvironmentObjectType::Boulder { .. } => "boulder",
-                    EnvironmentObjectType::Custom { name, .. } => name,
-                    _ => "other",
+                    EnvironmentObjectType::Defined { name, .. } => name,
+                    EnvironmentObjectType::Structure { .. } => "structure",
                 },
                 self.position.x,
                 self.position.y,
@@ -117,11 +82,10 @@ impl Obstacle for EnvironmentObstacle {
 
     fn blocks_pathfinding(&self) -> bool {
         match &self.object_type {
-            EnvironmentObjectType::Grass => false,
-            EnvironmentObjectType::Custom {
+            EnvironmentObjectType::Structure { collision_shape }
+            | EnvironmentObjectType::Defined {
                 collision_shape, ..
             } => !matches!(collision_shape, CollisionShape::None),
-            _ => true,
         }
     }
 
@@ -131,12 +95,10 @@ impl Obstacle for EnvironmentObstacle {
 
     fn blocking_priority(&self) -> u8 {
         match &self.object_type {
-            EnvironmentObjectType::Tree { .. } => 150, // Trees are important obstacles
-            EnvironmentObjectType::Boulder { .. } => 200, // Boulders are very solid
-            EnvironmentObjectType::Rock { .. } => 120, // Rocks are medium priority
             EnvironmentObjectType::Structure { .. } => 255, // Structures are absolute
-            EnvironmentObjectType::Custom { .. } => 100, // Default priority
-            EnvironmentObjectType::Grass => 0,         // Grass doesn't block
+            EnvironmentObjectType::Defined {
+                blocking_priority, ..
+            } => *blocking_priority,
         }
     }
 }
@@ -156,8 +118,8 @@ mod tests {
 
         let obstacle = EnvironmentObstacle::from(&env_obj);
         assert!(matches!(
-            obstacle.object_type,
-            EnvironmentObjectType::Tree { .. }
+            &obstacle.object_type,
+            EnvironmentObjectType::Defined { name, .. } if name == "tree"
         ));
         assert!(obstacle.blocks_pathfinding());
         assert_eq!(obstacle.blocking_priority(), 150);
@@ -182,8 +144,8 @@ mod tests {
 
         let obstacle = EnvironmentObstacle::from(&env_obj);
         assert!(matches!(
-            obstacle.object_type,
-            EnvironmentObjectType::Rock { .. }
+            &obstacle.object_type,
+            EnvironmentObjectType::Defined { name, .. } if name == "rock"
         ));
         assert!(obstacle.blocks_pathfinding());
         assert_eq!(obstacle.blocking_priority(), 120);
@@ -207,8 +169,8 @@ mod tests {
 
         let obstacle = EnvironmentObstacle::from(&env_obj);
         assert!(matches!(
-            obstacle.object_type,
-            EnvironmentObjectType::Boulder { .. }
+            &obstacle.object_type,
+            EnvironmentObjectType::Defined { name, .. } if name == "boulder"
         ));
         assert!(obstacle.blocks_pathfinding());
         assert_eq!(obstacle.blocking_priority(), 200);
@@ -247,8 +209,8 @@ mod tests {
 
         let obstacle = EnvironmentObstacle::from(&env_obj);
         assert!(matches!(
-            obstacle.object_type,
-            EnvironmentObjectType::Custom { .. }
+            &obstacle.object_type,
+            EnvironmentObjectType::Defined { name, .. } if name == "custom_building"
         ));
         assert!(obstacle.blocks_pathfinding());
         assert_eq!(obstacle.blocking_priority(), 100);
@@ -272,8 +234,8 @@ mod tests {
 
         let obstacle = EnvironmentObstacle::from(&env_obj);
         assert!(matches!(
-            obstacle.object_type,
-            EnvironmentObjectType::Custom { .. }
+            &obstacle.object_type,
+            EnvironmentObjectType::Defined { name, .. } if name == "invalid_object"
         ));
         assert!(matches!(obstacle.collision_shape(), CollisionShape::None));
         assert!(!obstacle.blocks_pathfinding()); // Should not block with None collision shape
@@ -340,4 +302,51 @@ mod tests {
         ));
         assert!(!grass.contains_point(Vec3::new(0.0, 0.0, 0.0)));
     }
+
+    #[test]
+    fn test_rotated_custom_obstacle() {
+        // 6x1 wall turned a quarter turn should run along Z
+        let wall = EnvironmentObstacle::from(&EnvironmentObject::new(
+            "wall".to_string(),
+            Vec3::ZERO,
+            Vec3::new(0.0, std::f32::consts::FRAC_PI_2, 0.0),
+            Vec3::new(6.0, 2.0, 1.0),
+        ));
+
+        assert!(matches!(
+            wall.collision_shape(),
+            CollisionShape::OrientedBox { .. }
+        ));
+        assert!(wall.contains_point(Vec3::new(0.0, 0.0, 2.5)));
+        assert!(!wall.contains_point(Vec3::new(2.5, 0.0, 0.0)));
+
+        let (min, max) = wall.collision_shape().approximate_bounds(Vec3::ZERO);
+        assert!((max.z - 3.0).abs() < 1e-5 && (min.x + 0.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_obstacle_from_definition() {
+        let registry = ObjectRegistry::from_toml_str(
+            r#"
+            [[object]]
+            name = "hut"
+            blocking_priority = 255
+            model = { placeholder = { type = "cuboid", size = [2.0, 2.0, 2.0] }, color = [0.5, 0.4, 0.3] }
+            collider = { type = "cuboid", half_extents = [1.0, 1.0, 1.0] }
+            nav_shape = { type = "polygon", vertices = [[-1.0, -1.0], [1.0, -1.0], [0.0, 1.0]] }
+            "#,
+        )
+        .unwrap();
+        let hut = EnvironmentObject::simple("hut".to_string(), Vec3::ZERO);
+        let obstacle = EnvironmentObstacle::from_definition(&hut, registry.get("hut").unwrap());
+
+        assert_eq!(obstacle.blocking_priority(), 255);
+        assert!(obstacle.blocks_pathfinding());
+        assert!(matches!(
+            obstacle.collision_shape(),
+            CollisionShape::ConvexPolygon { .. }
+        ));
+        assert!(obstacle.contains_point(Vec3::new(0.0, 0.0, 0.5)));
+        assert!(!obstacle.contains_point(Vec3::new(0.9, 0.0, 0.9)));
+    }
 }
@@ -183,27 +183,23 @@ fn bullet_enemy_collision(
                     game_config.score += game_config.settings.score_per_enemy;
                     commands.entity(enemy_entity).despawn();
 
-                    // Inline respawn position calculation
-                    let respawn_pos = if let Some(map) = map.as_deref() {
-                        if !map.enemy_zones.is_empty() {
-                            let zone_index =
-                                (respawn_counter.count as usize) % map.enemy_zones.len();
-                            crate::game_logic::spawning::generate_zone_position(
-                                &map.enemy_zones[zone_index],
-                                respawn_counter.count,
-                            )
-                        } else {
-                            Vec3::new(5.0, 2.0, 0.0) // Safe fallback if no zones
-                        }
-                    } else {
-                        Vec3::new(5.0, 2.0, 0.0) // Safe fallback if no map
-                    };
+                    // Respawn in the next zone, or at a safe fallback without zones
+                    let counter = respawn_counter.count;
+                    let zone = map
+                        .as_deref()
+                        .filter(|map| !map.enemy_zones.is_empty())
+                        .map(|map| &map.enemy_zones[counter as usize % map.enemy_zones.len()]);
+                    let respawn_pos = zone.map_or(Vec3::new(5.0, 2.0, 0.0), |zone| {
+                        crate::game_logic::spawning::generate_zone_position(zone, counter)
+                    });
                     respawn_counter.count += 1;
 
                     crate::game_logic::spawning::spawn_enemy_entity(
                         &mut commands,
                         &asset_server,
                         respawn_pos,
+                        zone,
+                        counter,
                         &game_config,
                     );
                 }
@@ -241,27 +237,23 @@ fn area_effect_damage(
                         game_config.score += game_config.settings.score_per_enemy;
                         commands.entity(enemy_entity).despawn();
 
-                        // Inline respawn position calculation
-                        let respawn_pos = if let Some(map) = map.as_deref() {
-                            if !map.enemy_zones.is_empty() {
-                                let zone_index =
-                                    (respawn_counter.count as usize) % map.enemy_zones.len();
-                                crate::game_logic::spawning::generate_zone_position(
-                                    &map.enemy_zones[zone_index],
-                                    respawn_counter.count,
-                                )
-                            } else {
-                                Vec3::new(5.0, 2.0, 0.0) // Safe fallback if no zones
-                            }
-                        } else {
-                            Vec3::new(5.0, 2.0, 0.0) // Safe fallback if no map
-                        };
+                        // Respawn in the next zone, or at a safe fallback without zones
+                        let counter = respawn_counter.count;
+                        let zone = map
+                            .as_deref()
+                            .filter(|map| !map.enemy_zones.is_empty())
+                            .map(|map| &map.enemy_zones[counter as usize % map.enemy_zones.len()]);
+                        let respawn_pos = zone.map_or(Vec3::new(5.0, 2.0, 0.0), |zone| {
+                            crate::game_logic::spawning::generate_zone_position(zone, counter)
+                        });
                         respawn_counter.count += 1;
 
                         crate::game_logic::spawning::spawn_enemy_entity(
                             &mut commands,
                             &asset_server,
                             respawn_pos,
+                            zone,
+                            counter,
                             &game_config,
                         );
                     }
@@ -290,7 +282,7 @@ fn cleanup_combat_entities(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::map::{SpawnZone, TerrainData};
+    use crate::map::{SpawnZone, TerrainData, ZoneStats};
 
     #[test]
     fn test_inlined_bullet_movement() {
@@ -360,6 +352,8 @@ mod tests {
             radius: 5.0,
             max_enemies: 10,
             enemy_types: vec!["dark-knight".to_string()],
+            tier: 0,
+            stats: ZoneStats::default(),
         };
 
         let terrain = TerrainData::create_flat(10, 10, 1.0, 0.0).unwrap();
@@ -369,6 +363,9 @@ mod tests {
             player_spawn: Vec3::ZERO,
             enemy_zones: vec![zone],
             environment_objects: vec![],
+            water_bodies: vec![],
+            road_surfaces: vec![],
+            biome_map: None,
         };
 
         let respawn_counter = 0;
@@ -405,6 +402,9 @@ mod tests {
             player_spawn: Vec3::ZERO,
             enemy_zones: vec![],
             environment_objects: vec![],
+            water_bodies: vec![],
+            road_surfaces: vec![],
+            biome_map: None,
         };
 
         let respawn_counter = 0;
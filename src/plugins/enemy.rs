@@ -1,4 +1,10 @@
-use crate::{components::*, game_logic::enemy::*, map::MapDefinition, resources::*};
+use crate::{
+    components::*,
+    game_logic::enemy::*,
+    map::{MapDefinition, MapPatrols},
+    pathfinding::NavigationGrid,
+    resources::*,
+};
 use bevy::prelude::*;
 use bevy_rapier3d::prelude::*;
 
@@ -10,7 +16,14 @@ impl Plugin for EnemyPlugin {
             .add_systems(OnEnter(GameState::Playing), spawn_enemies)
             .add_systems(
                 Update,
-                (enemy_ai, update_entity_lod).run_if(in_state(GameState::Playing)),
+                (
+                    assign_patrols,
+                    enemy_ai
+                        .after(assign_patrols)
+                        .before(crate::pathfinding::apply_local_avoidance),
+                    update_entity_lod,
+                )
+                    .run_if(in_state(GameState::Playing)),
             )
             .add_systems(OnExit(GameState::Playing), cleanup_enemies);
     }
@@ -35,6 +48,8 @@ fn spawn_enemies(
                         &mut commands,
                         &asset_server,
                         spawn_pos,
+                        Some(spawn_zone),
+                        i,
                         &game_config,
                     );
                 }
@@ -44,6 +59,81 @@ fn spawn_enemies(
     }
 }
 
+/// Enemies that have not been given a patrol yet
+type UnpatrolledEnemies<'w, 's> =
+    Query<'w, 's, (Entity, &'static Transform), (With<Enemy>, Without<Patrol>)>;
+
+/// Give every enemy without a patrol the route or wander area of its spawn zone
+fn assign_patrols(
+    mut commands: Commands,
+    enemy_query: UnpatrolledEnemies,
+    map: Option<Res<MapDefinition>>,
+    patrols: Option<Res<MapPatrols>>,
+) {
+    let Some(map) = map else {
+        return;
+    };
+    let default_patrols = MapPatrols::default();
+    let patrols = patrols.as_deref().unwrap_or(&default_patrols);
+
+    for (entity, transform) in enemy_query.iter() {
+        let Some(zone_index) = nearest_zone(&map, transform.translation) else {
+            continue;
+        };
+
+        let patrol = match patrols.route_for_zone(zone_index) {
+            Some(route) => Patrol::route(
+                route.positions(),
+                route.neighbors(),
+                route.pause,
+                patrols.speed_factor,
+            ),
+            None => {
+                let zone = &map.enemy_zones[zone_index];
+                Patrol::wander(
+                    Vec3::new(zone.center.x, 0.0, zone.center.z),
+                    zone.radius,
+                    patrols.wander_pause,
+                    patrols.speed_factor,
+                )
+            }
+        };
+        commands.entity(entity).insert(patrol);
+    }
+}
+
+/// Spawn zone containing `position`, or the closest one by center distance
+fn nearest_zone(map: &MapDefinition, position: Vec3) -> Option<usize> {
+    let position_2d = Vec2::new(position.x, position.z);
+    let distance_to = |center: Vec3| position_2d.distance(Vec2::new(center.x, center.z));
+
+    map.enemy_zones
+        .iter()
+        .enumerate()
+        .find(|(_, zone)| distance_to(zone.center) <= zone.radius)
+        .or_else(|| {
+            map.enemy_zones
+                .iter()
+                .enumerate()
+                .min_by(|(_, a), (_, b)| distance_to(a.center).total_cmp(&distance_to(b.center)))
+        })
+        .map(|(index, _)| index)
+}
+
+/// Face along `movement_direction`
+/// NOTE: GLB models are facing backwards, so we flip the direction
+fn face_movement(transform: &mut Transform, movement_direction: Vec3) {
+    if movement_direction.length() > 0.1 {
+        let character_pos = transform.translation;
+        let flat_target = Vec3::new(
+            character_pos.x - movement_direction.x, // Flip for GLB orientation
+            character_pos.y,                        // Keep same Y level
+            character_pos.z - movement_direction.z, // Flip for GLB orientation
+        );
+        transform.look_at(flat_target, Vec3::Y);
+    }
+}
+
 fn enemy_ai(
     mut enemy_query: Query<
         (
@@ -51,11 +141,13 @@ fn enemy_ai(
             &Enemy,
             &mut KinematicCharacterController,
             &mut PathfindingAgent,
+            Option<&mut Patrol>,
         ),
         (With<Enemy>, Without<Player>),
     >,
     player_query: Query<&Transform, (With<Player>, Without<Enemy>)>,
     game_config: Res<GameConfig>,
+    nav_grid: Option<Res<NavigationGrid>>,
     time: Res<Time>,
 ) {
     let player_transform = player_query
@@ -67,23 +159,26 @@ fn enemy_ai(
         player_transform.translation.z,
     );
 
-    // First pass: collect all enemy positions for separation calculation
-    let enemy_positions: Vec<(Vec3, bool)> = enemy_query
-        .iter()
-        .map(|(transform, enemy, _controller, _agent)| {
-            let pos = Vec3::new(transform.translation.x, 0.0, transform.translation.z);
-            (pos, enemy.is_dying)
+    // Local avoidance keeps enemies apart, so each one just steers toward its target
+    let mut rng = rand::thread_rng();
+    let is_walkable = |position: Vec3| {
+        nav_grid.as_ref().is_none_or(|grid| {
+            grid.world_to_grid(position)
+                .is_some_and(|node| grid.is_walkable(node.x, node.z))
         })
-        .collect();
+    };
 
-    // Second pass: update each enemy with hybrid pathfinding + flocking behavior
-    for (i, (mut enemy_transform, enemy, mut controller, mut pathfinding_agent)) in
-        enemy_query.iter_mut().enumerate()
+    for (mut enemy_transform, enemy, mut controller, mut pathfinding_agent, patrol) in
+        &mut enemy_query
     {
         if enemy.is_dying {
-            continue; // Skip dying enemies
+            // Dying enemies stay put but still act as obstacles for local avoidance
+            pathfinding_agent.set_preferred_velocity(Vec3::ZERO, 0.0);
+            continue;
         }
 
+        let max_speed = enemy.speed.0 * game_config.settings.enemy_speed_multiplier.get();
+
         // Use 2D distance for movement (ignore Y differences)
         let enemy_pos_2d = Vec3::new(
             enemy_transform.translation.x,
@@ -92,18 +187,6 @@ fn enemy_ai(
         );
         let distance = enemy_pos_2d.distance(player_pos_2d);
 
-        // Calculate flocking forces for this enemy
-        let other_positions_for_enemy: Vec<(Vec3, bool)> = enemy_positions
-            .iter()
-            .enumerate()
-            .filter(|(j, _)| *j != i) // Skip self
-            .map(|(_, (pos, is_dying))| (*pos, *is_dying))
-            .collect();
-
-        let separation_radius = 2.0;
-        let flocking_forces =
-            calculate_flocking_forces(enemy_pos_2d, &other_positions_for_enemy, separation_radius);
-
         // Inline AI decision logic - check if enemy should chase
         let is_chasing = distance <= enemy.chase_distance.0
             && distance > game_config.settings.enemy_stopping_distance.get();
@@ -125,36 +208,61 @@ fn enemy_ai(
 
             // Calculate direction to movement target
             let target_pos_2d = Vec3::new(movement_target.x, 0.0, movement_target.z);
-            let direction_to_target = (target_pos_2d - enemy_pos_2d).normalize();
-
-            // Blend movement direction with separation forces
-            let separation_weight = if distance < 5.0 { 0.7 } else { 0.3 };
-            let movement_direction = (direction_to_target
-                + flocking_forces.total_force * separation_weight)
-                .normalize_or_zero();
+            let movement_direction = (target_pos_2d - enemy_pos_2d).normalize_or_zero();
 
-            // Use kinematic character controller for movement
-            let max_speed = enemy.speed.0 * game_config.settings.enemy_speed_multiplier.get();
+            // Use kinematic character controller for movement; local avoidance refines it
             let move_distance = max_speed * time.delta_secs();
             let movement = movement_direction * move_distance;
             controller.translation = Some(movement);
+            pathfinding_agent.set_preferred_velocity(movement_direction * max_speed, max_speed);
+
+            face_movement(&mut enemy_transform, movement_direction);
+        } else if let Some(mut patrol) = patrol.filter(|_| distance > enemy.chase_distance.0) {
+            // Idle - walk the patrol, resuming where it left off after a chase
+            let patrol_speed = max_speed * patrol.speed_factor;
+            let target_2d = Vec3::new(patrol.target.x, 0.0, patrol.target.z);
+            let reach = pathfinding_agent.waypoint_reach_distance.max(1.0);
+            let arrived = enemy_pos_2d.distance(target_2d) <= reach;
+
+            if patrol.is_paused() {
+                patrol.pause_remaining -= time.delta_secs();
+            } else {
+                patrol.leg_elapsed += time.delta_secs();
+                if arrived || patrol.leg_elapsed > Patrol::MAX_LEG_SECONDS {
+                    patrol.arrive(&mut rng, is_walkable);
+                }
+            }
+
+            if patrol.is_paused() {
+                pathfinding_agent.destination = None;
+                pathfinding_agent.clear_path();
+                controller.translation = Some(Vec3::ZERO);
+                pathfinding_agent.set_preferred_velocity(Vec3::ZERO, patrol_speed);
+                continue;
+            }
 
-            // Rotate toward movement direction
-            // NOTE: GLB models are facing backwards, so we flip the direction
-            if movement_direction.length() > 0.1 {
-                let character_pos = enemy_transform.translation;
-                let flat_target = Vec3::new(
-                    character_pos.x - movement_direction.x, // Flip for GLB orientation
-                    character_pos.y,                        // Keep same Y level
-                    character_pos.z - movement_direction.z, // Flip for GLB orientation
-                );
-                enemy_transform.look_at(flat_target, Vec3::Y);
+            let target_2d = Vec3::new(patrol.target.x, 0.0, patrol.target.z);
+            if pathfinding_agent.destination != Some(target_2d) {
+                pathfinding_agent.destination = Some(target_2d);
+                pathfinding_agent.clear_path();
             }
+
+            let movement_target = pathfinding_agent.current_waypoint().unwrap_or(target_2d);
+            let movement_direction = (Vec3::new(movement_target.x, 0.0, movement_target.z)
+                - enemy_pos_2d)
+                .normalize_or_zero();
+
+            controller.translation = Some(movement_direction * patrol_speed * time.delta_secs());
+            pathfinding_agent
+                .set_preferred_velocity(movement_direction * patrol_speed, patrol_speed);
+
+            face_movement(&mut enemy_transform, movement_direction);
         } else {
             // Not chasing - clear pathfinding destination and stop movement
             pathfinding_agent.destination = None;
             pathfinding_agent.clear_path();
             controller.translation = Some(Vec3::ZERO);
+            pathfinding_agent.set_preferred_velocity(Vec3::ZERO, max_speed);
         }
     }
 }
@@ -279,19 +387,6 @@ mod tests {
         assert_eq!(movement_target_short, player_pos);
     }
 
-    #[test]
-    fn test_separation_weight_calculation() {
-        // Close distance should have high separation weight
-        let distance_close = 3.0;
-        let separation_weight_close = if distance_close < 5.0 { 0.7 } else { 0.3 };
-        assert_eq!(separation_weight_close, 0.7);
-
-        // Far distance should have low separation weight
-        let distance_far = 8.0;
-        let separation_weight_far = if distance_far < 5.0 { 0.7 } else { 0.3 };
-        assert_eq!(separation_weight_far, 0.3);
-    }
-
     #[test]
     fn test_inlined_lod_calculation() {
         let lod_distance_high = 10.0;
@@ -357,7 +452,7 @@ mod tests {
     fn test_integration_with_existing_math_functions() {
         // Test that we still use the mathematical functions correctly
         use crate::game_logic::enemy::*;
-        use crate::map::SpawnZone;
+        use crate::map::{SpawnZone, ZoneStats};
 
         // Test spawn position calculation (kept as reusable function)
         let spawn_zone = SpawnZone {
@@ -365,6 +460,8 @@ mod tests {
             radius: 5.0,
             max_enemies: 4,
             enemy_types: vec!["dark-knight".to_string()],
+            tier: 0,
+            stats: ZoneStats::default(),
         };
 
         let pos1 = calculate_enemy_spawn_position(&spawn_zone, 0);
@@ -383,4 +480,32 @@ mod tests {
         assert!(forces.separation.x < 0.0);
         assert_eq!(forces.total_force, forces.separation);
     }
+
+    #[test]
+    fn test_nearest_zone_prefers_containing_zone() {
+        use crate::map::{SpawnZone, TerrainData};
+
+        let zone = |x: f32, radius: f32| {
+            SpawnZone::new(
+                Vec3::new(x, 0.0, 0.0),
+                radius,
+                1,
+                vec!["goblin".to_string()],
+            )
+            .unwrap()
+        };
+        let map = MapDefinition::new(
+            "zones".to_string(),
+            TerrainData::create_flat(64, 64, 1.0, 0.0).unwrap(),
+            Vec3::ZERO,
+            vec![zone(0.0, 20.0), zone(15.0, 2.0)],
+            vec![],
+        )
+        .unwrap();
+
+        // Inside the large zone even though the small zone's center is closer
+        assert_eq!(nearest_zone(&map, Vec3::new(11.0, 0.0, 0.0)), Some(0));
+        // Outside every zone falls back to the closest center
+        assert_eq!(nearest_zone(&map, Vec3::new(30.0, 5.0, 0.0)), Some(1));
+    }
 }
@@ -1,4 +1,5 @@
 use crate::map::{EnvironmentObject, MapDefinition};
+use crate::objects::{ObjectDefinition, ObjectRegistry};
 use crate::resources::GameState;
 use bevy::prelude::*;
 use bevy_rapier3d::prelude::*;
@@ -24,6 +25,7 @@ fn spawn_environment_objects(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
+    asset_server: Res<AssetServer>,
     map: Res<MapDefinition>,
 ) {
     println!(
@@ -31,8 +33,17 @@ fn spawn_environment_objects(
         map.environment_objects.len()
     );
 
+    let registry = ObjectRegistry::global();
     for obj in &map.environment_objects {
-        spawn_single_environment_object(&mut commands, &mut meshes, &mut materials, obj);
+        let definition = registry.get_or_fallback(&obj.object_type);
+        spawn_single_environment_object(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            &asset_server,
+            obj,
+            &definition,
+        );
     }
 }
 
@@ -40,7 +51,9 @@ fn spawn_single_environment_object(
     commands: &mut Commands,
     meshes: &mut Assets<Mesh>,
     materials: &mut Assets<StandardMaterial>,
+    asset_server: &AssetServer,
     obj: &EnvironmentObject,
+    definition: &ObjectDefinition,
 ) {
     // Create transform from object data
     let rotation = Quat::from_euler(
@@ -56,7 +69,7 @@ fn spawn_single_environment_object(
     };
 
     // Choose collider based on object type
-    let collider = get_object_collider(&obj.object_type, &obj.scale);
+    let collider = get_object_collider(definition, &obj.scale);
 
     // Debug logging for physics obstacles
     debug!(
@@ -70,89 +83,48 @@ fn spawn_single_environment_object(
         obj.scale.z
     );
 
-    // Create primitive mesh and material based on object type
-    let (mesh, material) =
-        create_placeholder_mesh_and_material(&obj.object_type, meshes, materials);
-
-    commands.spawn((
-        Mesh3d(mesh),
-        MeshMaterial3d(material),
+    let mut entity = commands.spawn((
         transform,
-        collider,
         RigidBody::Fixed,
         EnvironmentObjectMarker {
             object_type: obj.object_type.clone(),
         },
         Name::new(format!("EnvObject_{obj_type}", obj_type = obj.object_type)),
     ));
+
+    if let Some(collider) = collider {
+        entity.insert(collider);
+    }
+
+    // Use the model asset when defined, otherwise a primitive placeholder
+    match &definition.model.asset {
+        Some(asset) => {
+            entity.insert(SceneRoot(asset_server.load(asset.clone())));
+        }
+        None => {
+            let (mesh, material) =
+                create_placeholder_mesh_and_material(definition, meshes, materials);
+            entity.insert((Mesh3d(mesh), MeshMaterial3d(material)));
+        }
+    }
 }
 
 fn create_placeholder_mesh_and_material(
-    object_type: &str,
+    definition: &ObjectDefinition,
     meshes: &mut Assets<Mesh>,
     materials: &mut Assets<StandardMaterial>,
 ) -> (Handle<Mesh>, Handle<StandardMaterial>) {
-    let (mesh, color) = match object_type {
-        "tree" => {
-            // Tree: tall cylinder (trunk-like)
-            (
-                Mesh::from(Cylinder::new(0.2, 2.0)),
-                Color::srgb(0.4, 0.2, 0.0),
-            )
-        }
-        "rock" => {
-            // Rock: slightly flattened sphere
-            (Sphere::new(0.8).mesh().uv(8, 6), Color::srgb(0.5, 0.5, 0.5))
-        }
-        "boulder" => {
-            // Boulder: larger, more irregular (use a cube for now)
-            (
-                Mesh::from(Cuboid::new(1.2, 1.0, 1.1)),
-                Color::srgb(0.4, 0.4, 0.45),
-            )
-        }
-        "grass" => {
-            // Grass: small flat cylinder
-            (
-                Mesh::from(Cylinder::new(0.3, 0.1)),
-                Color::srgb(0.2, 0.8, 0.2),
-            )
-        }
-        _ => {
-            // Default: simple cube
-            (
-                Mesh::from(Cuboid::new(1.0, 1.0, 1.0)),
-                Color::srgb(0.6, 0.3, 0.6),
-            )
-        }
-    };
+    let [r, g, b] = definition.model.color;
 
-    let mesh_handle = meshes.add(mesh);
+    let mesh_handle = meshes.add(definition.model.placeholder.to_mesh());
     let material_handle = materials.add(StandardMaterial {
-        base_color: color,
+        base_color: Color::srgb(r, g, b),
         ..default()
     });
 
     (mesh_handle, material_handle)
 }
 
-fn get_object_collider(object_type: &str, scale: &Vec3) -> Collider {
-    match object_type {
-        "tree" => {
-            // Tree: tall cylinder
-            Collider::cylinder(scale.y * 1.5, scale.x * 0.3)
-        }
-        "rock" | "boulder" => {
-            // Rock/boulder: roughly spherical
-            Collider::ball(scale.x * 0.5)
-        }
-        "grass" => {
-            // Grass: very small collider or no collider
-            Collider::ball(scale.x * 0.1)
-        }
-        _ => {
-            // Default: box collider
-            Collider::cuboid(scale.x * 0.5, scale.y * 0.5, scale.z * 0.5)
-        }
-    }
+fn get_object_collider(definition: &ObjectDefinition, scale: &Vec3) -> Option<Collider> {
+    definition.collider.to_collider(*scale)
 }
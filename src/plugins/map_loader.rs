@@ -1,9 +1,12 @@
 use crate::game_logic::errors::{MinionError, MinionResult};
-use crate::map::{MapDefinition, SpawnZone, TerrainData};
-use crate::pathfinding::{NavigationGrid, PathfindingConfig};
+use crate::map::{MapDefinition, MapPatrols, SpawnZone, TerrainData};
+use crate::pathfinding::{BakedNavigation, NavigationGrid, PathfindingConfig};
 use crate::resources::{GameConfig, GameState};
 use crate::terrain::coordinates::get_height_at_world_interpolated;
-use crate::terrain_generation::{get_terrain_preset, is_suitable_for_spawning};
+use crate::terrain_generation::{
+    DifficultyConfig, MapSeed, SeedStream, generate_difficulty_zones, get_terrain_preset,
+    is_suitable_for_spawning,
+};
 use bevy::prelude::*;
 
 pub struct MapLoaderPlugin;
@@ -17,108 +20,76 @@ impl Plugin for MapLoaderPlugin {
 pub fn load_map(mut commands: Commands, game_config: Res<GameConfig>) {
     let map_result = load_map_from_config(&game_config);
 
-    match map_result {
-        Ok(map) => {
+    let (map, baked_navigation) = match map_result {
+        Ok((map, baked_navigation)) => {
             info!("Successfully loaded map: {}", map.name);
-
-            // Initialize NavigationGrid from the terrain data and environment objects
-            let pathfinding_config = PathfindingConfig::default();
-            match NavigationGrid::from_terrain_and_objects(
-                &map.terrain,
-                &map.environment_objects,
-                pathfinding_config,
-            ) {
-                Ok(nav_grid) => {
-                    info!(
-                        "Successfully created navigation grid ({width}x{height}) with {obj_count} environment objects",
-                        width = nav_grid.width,
-                        height = nav_grid.height,
-                        obj_count = map.environment_objects.len()
-                    );
-                    commands.insert_resource(nav_grid);
-                }
-                Err(err) => {
-                    warn!("Failed to create navigation grid: {err}");
-                    warn!("Pathfinding will not be available - falling back to direct movement");
-                }
-            }
-
-            commands.insert_resource(map);
+            (map, baked_navigation)
         }
         Err(err) => {
             warn!("Failed to load map: {err}");
 
             // Attempt progressive degradation based on error type
-            let fallback_result = create_fallback_map_progressive(&game_config, &err);
-
-            match fallback_result {
+            match create_fallback_map_progressive(&game_config, &err) {
                 Ok(fallback_map) => {
                     info!("Successfully created fallback map: {}", fallback_map.name);
-
-                    // Initialize NavigationGrid for fallback map too
-                    let pathfinding_config = PathfindingConfig::default();
-                    match NavigationGrid::from_terrain_and_objects(
-                        &fallback_map.terrain,
-                        &fallback_map.environment_objects,
-                        pathfinding_config,
-                    ) {
-                        Ok(nav_grid) => {
-                            info!(
-                                "Successfully created navigation grid for fallback map ({width}x{height}) with {obj_count} environment objects",
-                                width = nav_grid.width,
-                                height = nav_grid.height,
-                                obj_count = fallback_map.environment_objects.len()
-                            );
-                            commands.insert_resource(nav_grid);
-                        }
-                        Err(err) => {
-                            warn!("Failed to create navigation grid for fallback map: {err}");
-                        }
-                    }
-
-                    commands.insert_resource(fallback_map);
+                    (fallback_map, None)
                 }
                 Err(fallback_err) => {
                     error!("Failed to create fallback map: {fallback_err}");
                     warn!("Using minimal hardcoded fallback...");
 
                     // Last resort: hardcoded fallback
-                    let minimal_map = create_minimal_fallback_map();
-
-                    // Initialize NavigationGrid for minimal fallback too
-                    let pathfinding_config = PathfindingConfig::default();
-                    match NavigationGrid::from_terrain_and_objects(
-                        &minimal_map.terrain,
-                        &minimal_map.environment_objects,
-                        pathfinding_config,
-                    ) {
-                        Ok(nav_grid) => {
-                            info!(
-                                "Successfully created navigation grid for minimal fallback ({width}x{height}) with {obj_count} environment objects",
-                                width = nav_grid.width,
-                                height = nav_grid.height,
-                                obj_count = minimal_map.environment_objects.len()
-                            );
-                            commands.insert_resource(nav_grid);
-                        }
-                        Err(err) => {
-                            warn!("Failed to create navigation grid for minimal fallback: {err}");
-                        }
-                    }
-
-                    commands.insert_resource(minimal_map);
+                    (create_minimal_fallback_map(), None)
                 }
             }
         }
+    };
+
+    // Initialize NavigationGrid from baked data when it is still valid for this map
+    match NavigationGrid::from_baked_or_build(
+        baked_navigation,
+        &map.terrain,
+        &map.environment_objects,
+        &map.water_bodies,
+        PathfindingConfig::default(),
+    ) {
+        Ok(mut nav_grid) => {
+            // Road costs are never baked
+            nav_grid.set_road_surfaces(&map.terrain, &map.road_surfaces);
+            info!(
+                "Successfully created navigation grid ({width}x{height}) with {obj_count} environment objects",
+                width = nav_grid.width,
+                height = nav_grid.height,
+                obj_count = map.environment_objects.len()
+            );
+            commands.insert_resource(nav_grid);
+        }
+        Err(err) => {
+            warn!("Failed to create navigation grid: {err}");
+            warn!("Pathfinding will not be available - falling back to direct movement");
+        }
     }
+
+    // Patrol routes are optional; zones without one wander
+    let patrols =
+        MapPatrols::load_for_map(&game_config.settings.map_file_path).unwrap_or_else(|err| {
+            warn!("Failed to load patrol routes: {err}");
+            MapPatrols::default()
+        });
+    info!("Loaded {} patrol routes", patrols.routes.len());
+    commands.insert_resource(patrols);
+
+    commands.insert_resource(map);
 }
 
-fn load_map_from_config(game_config: &GameConfig) -> MinionResult<MapDefinition> {
+fn load_map_from_config(
+    game_config: &GameConfig,
+) -> MinionResult<(MapDefinition, Option<BakedNavigation>)> {
     let map_file = &game_config.settings.map_file_path;
     info!("Attempting to load map from: {map_file}");
 
     // Enhanced error handling with more specific error types
-    let result = MapDefinition::load_from_file(map_file);
+    let result = MapDefinition::load_with_navigation(map_file);
 
     match &result {
         Ok(_) => debug!("Map loaded successfully from {map_file}"),
@@ -204,18 +175,22 @@ fn create_generated_terrain_map(_game_config: &GameConfig) -> MinionResult<MapDe
     // Generate procedural terrain based on a preset
     // TODO: Could use game_config to customize terrain generation parameters
     let terrain_generator =
-        get_terrain_preset("hills", Some(42)).ok_or_else(|| MinionError::InvalidMapData {
+        get_terrain_preset("hills", 42).ok_or_else(|| MinionError::InvalidMapData {
             reason: "Failed to get terrain preset".to_string(),
         })?;
 
     let terrain = terrain_generator.generate(32, 32, 0.5)?;
 
-    // Generate spawn zones using terrain analysis
-    let spawn_zones = generate_terrain_based_spawn_zones(&terrain)?;
-
     // Find suitable player spawn position
     let player_spawn = find_suitable_player_spawn(&terrain)?;
 
+    // Generate spawn zones using terrain analysis
+    let spawn_zones = generate_terrain_based_spawn_zones(
+        &terrain,
+        player_spawn,
+        MapSeed(terrain_generator.seed),
+    )?;
+
     MapDefinition::new(
         "procedural_fallback".to_string(),
         terrain,
@@ -235,8 +210,9 @@ fn create_mixed_fallback_map(_game_config: &GameConfig) -> MinionResult<MapDefin
 /// Create a map with regenerated spawn zones on basic terrain
 fn create_respawn_fallback_map(_game_config: &GameConfig) -> MinionResult<MapDefinition> {
     let terrain = TerrainData::create_flat(24, 24, 1.5, 0.0)?;
-    let spawn_zones = generate_terrain_based_spawn_zones(&terrain)?;
     let player_spawn = Vec3::new(0.0, 1.0, 0.0);
+    let spawn_zones =
+        generate_terrain_based_spawn_zones(&terrain, player_spawn, MapSeed::default())?;
 
     MapDefinition::new(
         "respawn_fallback".to_string(),
@@ -247,56 +223,27 @@ fn create_respawn_fallback_map(_game_config: &GameConfig) -> MinionResult<MapDef
     )
 }
 
-/// Generate spawn zones based on terrain analysis
-fn generate_terrain_based_spawn_zones(terrain: &TerrainData) -> MinionResult<Vec<SpawnZone>> {
-    let mut spawn_zones = Vec::new();
-    let max_slope = 0.3; // Maximum slope for spawning
-    let min_distance = 8.0; // Minimum distance between spawn zones
-    let max_attempts = 100; // Prevent infinite loops
-
-    // Calculate terrain bounds in world coordinates
-    let terrain_width = terrain.width as f32 * terrain.scale;
-    let terrain_height = terrain.height as f32 * terrain.scale;
-
-    let mut attempts = 0;
-    let target_zones = 5; // Try to create 5 spawn zones
-
-    while spawn_zones.len() < target_zones && attempts < max_attempts {
-        attempts += 1;
-
-        // Generate random position within terrain bounds (with margin)
-        let margin = 4.0;
-        let x = (rand::random::<f32>() - 0.5) * (terrain_width - margin * 2.0);
-        let z = (rand::random::<f32>() - 0.5) * (terrain_height - margin * 2.0);
-
-        // Check if position is suitable
-        if !is_suitable_for_spawning(terrain, x, z, max_slope) {
-            continue;
-        }
-
-        // Check distance from existing spawn zones
-        let too_close = spawn_zones.iter().any(|zone: &SpawnZone| {
-            let distance = (Vec3::new(x, 0.0, z) - zone.center).length();
-            distance < min_distance
-        });
-
-        if too_close {
-            continue;
-        }
-
-        // Get terrain height for this position
-        let height = get_height_at_world_interpolated(terrain, x, z).unwrap_or(0.0);
-
-        // Create spawn zone
-        let spawn_zone = SpawnZone::new(
-            Vec3::new(x, height, z),
-            3.0, // radius
-            2,   // max enemies
-            vec!["dark-knight".to_string()],
-        )?;
-
-        spawn_zones.push(spawn_zone);
-        debug!("Generated spawn zone at ({x:.1}, {height:.1}, {z:.1})");
+/// Generate spawn zones graded by walking distance from the player spawn
+fn generate_terrain_based_spawn_zones(
+    terrain: &TerrainData,
+    player_spawn: Vec3,
+    seed: MapSeed,
+) -> MinionResult<Vec<SpawnZone>> {
+    // Fallback terrains are small, so shrink the safe radius to fit
+    let extent = terrain.width.min(terrain.height) as f32 * terrain.scale;
+    let defaults = DifficultyConfig::default();
+    let safe_radius = defaults.safe_radius.min(extent / 6.0);
+    let config = defaults.with_safe_radius(safe_radius);
+
+    let mut spawn_zones = generate_difficulty_zones(
+        terrain,
+        player_spawn,
+        &config,
+        seed.child(SeedStream::Spawns),
+        |_, _| true,
+    )?;
+    for zone in &spawn_zones {
+        debug!("Generated tier {} spawn zone at {}", zone.tier, zone.center);
     }
 
     // If we couldn't generate enough zones, add some basic fallback zones
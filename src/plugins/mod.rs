@@ -3,6 +3,7 @@ pub mod egui_ui;
 pub mod enemy;
 pub mod environment;
 pub mod map_loader;
+pub mod pathfinding_debug;
 pub mod player;
 pub mod scene;
 pub mod tooltips;
@@ -15,6 +16,7 @@ pub use egui_ui::EguiUiPlugin;
 pub use enemy::EnemyPlugin;
 pub use environment::EnvironmentPlugin;
 pub use map_loader::MapLoaderPlugin;
+pub use pathfinding_debug::PathfindingDebugPlugin;
 pub use player::PlayerPlugin;
 pub use scene::ScenePlugin;
 pub use tooltips::TooltipPlugin;
@@ -0,0 +1,283 @@
+//! Toggleable gizmo overlay for inspecting the navigation grid and agent paths.
+//!
+//! Press F3 to toggle the overlay, and `[` / `]` to shrink or grow the
+//! inflation radius preview.
+
+use crate::components::{PathfindingAgent, Player};
+use crate::pathfinding::{GridNode, LastPathQuery, NavigationGrid};
+use crate::resources::GameState;
+use bevy::prelude::*;
+use std::f32::consts::FRAC_PI_2;
+
+pub struct PathfindingDebugPlugin;
+
+impl Plugin for PathfindingDebugPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PathfindingDebugOverlay>()
+            .init_resource::<LastPathQuery>()
+            .add_systems(
+                Update,
+                (
+                    handle_debug_overlay_input,
+                    draw_navigation_grid.after(handle_debug_overlay_input),
+                    draw_agent_paths.after(handle_debug_overlay_input),
+                    draw_last_query.after(handle_debug_overlay_input),
+                )
+                    .run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+/// Settings for the pathfinding debug overlay
+#[derive(Debug, Clone, Resource)]
+pub struct PathfindingDebugOverlay {
+    pub enabled: bool,
+    /// Agent radius used to preview obstacle inflation
+    pub inflation_radius: f32,
+    /// Only cells within this distance of the player are drawn
+    pub draw_distance: f32,
+    pub show_blocked: bool,
+    pub show_inflated: bool,
+    pub show_paths: bool,
+    pub show_explored: bool,
+}
+
+impl Default for PathfindingDebugOverlay {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            inflation_radius: 0.5, // Typical enemy radius
+            draw_distance: 30.0,   // Keeps gizmo counts manageable on large maps
+            show_blocked: true,
+            show_inflated: true,
+            show_paths: true,
+            show_explored: true,
+        }
+    }
+}
+
+const INFLATION_STEP: f32 = 0.1;
+const MAX_INFLATION_RADIUS: f32 = 5.0;
+const CELL_HEIGHT_OFFSET: f32 = 0.05;
+
+/// Map an obstacle priority to a color from yellow (low) to red (high)
+pub fn priority_color(priority: u8) -> Color {
+    let t = priority as f32 / u8::MAX as f32;
+    Color::srgb(1.0, 1.0 - t, 0.0)
+}
+
+/// Grid cells within `distance` of `center`, clamped to the grid bounds
+fn cells_near(nav_grid: &NavigationGrid, center: Option<Vec3>, distance: f32) -> Vec<GridNode> {
+    let (min_x, max_x, min_z, max_z) = match center.and_then(|c| nav_grid.world_to_grid(c)) {
+        Some(node) => {
+            let reach = (distance / nav_grid.cell_size).ceil() as u32;
+            (
+                node.x.saturating_sub(reach),
+                (node.x + reach).min(nav_grid.width - 1),
+                node.z.saturating_sub(reach),
+                (node.z + reach).min(nav_grid.height - 1),
+            )
+        }
+        None => (0, nav_grid.width - 1, 0, nav_grid.height - 1),
+    };
+
+    (min_z..=max_z)
+        .flat_map(|z| (min_x..=max_x).map(move |x| GridNode::new(x, z)))
+        .collect()
+}
+
+fn cell_isometry(nav_grid: &NavigationGrid, node: GridNode) -> Isometry3d {
+    let position = nav_grid.grid_to_world(node) + Vec3::Y * CELL_HEIGHT_OFFSET;
+    Isometry3d::new(position, Quat::from_rotation_x(FRAC_PI_2))
+}
+
+fn handle_debug_overlay_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut overlay: ResMut<PathfindingDebugOverlay>,
+    mut last_query: ResMut<LastPathQuery>,
+) {
+    if keyboard.just_pressed(KeyCode::F3) {
+        overlay.enabled = !overlay.enabled;
+        info!(
+            "Pathfinding debug overlay {}",
+            if overlay.enabled {
+                "enabled"
+            } else {
+                "disabled"
+            }
+        );
+    }
+
+    if keyboard.just_pressed(KeyCode::BracketLeft) {
+        overlay.inflation_radius = (overlay.inflation_radius - INFLATION_STEP).max(0.0);
+    }
+    if keyboard.just_pressed(KeyCode::BracketRight) {
+        overlay.inflation_radius =
+            (overlay.inflation_radius + INFLATION_STEP).min(MAX_INFLATION_RADIUS);
+    }
+
+    // Only pay for explored-set tracing while someone is looking at it
+    last_query.capture = overlay.enabled && overlay.show_explored;
+    if !last_query.capture {
+        last_query.query = None;
+    }
+}
+
+fn draw_navigation_grid(
+    mut gizmos: Gizmos,
+    overlay: Res<PathfindingDebugOverlay>,
+    nav_grid: Option<Res<NavigationGrid>>,
+    player_query: Query<&Transform, With<Player>>,
+    mut inflated_cache: Local<Option<(f32, NavigationGrid)>>,
+) {
+    let Some(nav_grid) = nav_grid else {
+        return;
+    };
+    if !overlay.enabled {
+        return;
+    }
+
+    // Inflation is expensive, so only rebuild when the grid or radius changes
+    let cache_stale = match inflated_cache.as_ref() {
+        Some((radius, _)) => nav_grid.is_changed() || *radius != overlay.inflation_radius,
+        None => true,
+    };
+    if overlay.show_inflated && cache_stale {
+        let inflated = nav_grid.clone_and_inflate(
+            overlay.inflation_radius,
+            nav_grid.config.agent_clearance_slop,
+        );
+        *inflated_cache = Some((overlay.inflation_radius, inflated));
+    }
+
+    let center = player_query.iter().next().map(|t| t.translation);
+    let cell_extent = Vec2::splat(nav_grid.cell_size * 0.9);
+
+    for node in cells_near(&nav_grid, center, overlay.draw_distance) {
+        if !nav_grid.is_walkable(node.x, node.z) {
+            if overlay.show_blocked {
+                let color = priority_color(nav_grid.get_obstacle_priority(node));
+                gizmos.rect(cell_isometry(&nav_grid, node), cell_extent, color);
+            }
+        } else if overlay.show_inflated {
+            let inflated_blocked = inflated_cache
+                .as_ref()
+                .is_some_and(|(_, inflated)| !inflated.is_walkable(node.x, node.z));
+            if inflated_blocked {
+                gizmos.rect(
+                    cell_isometry(&nav_grid, node),
+                    cell_extent * 0.6,
+                    Color::srgb(1.0, 0.5, 0.0),
+                );
+            }
+        }
+    }
+}
+
+fn draw_agent_paths(
+    mut gizmos: Gizmos,
+    overlay: Res<PathfindingDebugOverlay>,
+    agents: Query<(&Transform, &PathfindingAgent)>,
+) {
+    if !overlay.enabled || !overlay.show_paths {
+        return;
+    }
+
+    let path_color = Color::srgb(0.2, 0.6, 1.0);
+    let target_color = Color::srgb(0.2, 1.0, 0.2);
+
+    for (transform, agent) in agents.iter() {
+        let remaining = agent
+            .nav_path
+            .waypoints()
+            .get(agent.nav_path.current_index()..)
+            .unwrap_or_default();
+
+        if !remaining.is_empty() {
+            let lift = Vec3::Y * CELL_HEIGHT_OFFSET;
+            gizmos.linestrip(
+                std::iter::once(transform.translation)
+                    .chain(remaining.iter().copied())
+                    .map(|p| p + lift),
+                path_color,
+            );
+            for waypoint in remaining {
+                gizmos.sphere(
+                    Isometry3d::from_translation(*waypoint + lift),
+                    0.15,
+                    path_color,
+                );
+            }
+        }
+
+        if let Some(destination) = agent.destination {
+            gizmos.circle(
+                Isometry3d::new(
+                    destination + Vec3::Y * CELL_HEIGHT_OFFSET,
+                    Quat::from_rotation_x(FRAC_PI_2),
+                ),
+                agent.waypoint_reach_distance.max(0.25),
+                target_color,
+            );
+        }
+    }
+}
+
+fn draw_last_query(
+    mut gizmos: Gizmos,
+    overlay: Res<PathfindingDebugOverlay>,
+    last_query: Res<LastPathQuery>,
+    nav_grid: Option<Res<NavigationGrid>>,
+) {
+    let (Some(nav_grid), Some(query)) = (nav_grid, last_query.query.as_ref()) else {
+        return;
+    };
+    if !overlay.enabled || !overlay.show_explored {
+        return;
+    }
+
+    let explored_color = Color::srgba(0.0, 0.9, 0.9, 0.5);
+    let cell_extent = Vec2::splat(nav_grid.cell_size * 0.4);
+    for node in &query.explored {
+        gizmos.rect(cell_isometry(&nav_grid, *node), cell_extent, explored_color);
+    }
+
+    let endpoint_color = if query.path.is_some() {
+        Color::srgb(0.2, 1.0, 0.2)
+    } else {
+        Color::srgb(1.0, 0.0, 0.0)
+    };
+    gizmos.line(query.start, query.goal, endpoint_color);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map::TerrainData;
+    use crate::pathfinding::PathfindingConfig;
+
+    #[test]
+    fn test_priority_color_ramp() {
+        let low = priority_color(0).to_srgba();
+        let high = priority_color(u8::MAX).to_srgba();
+        assert_eq!((low.red, low.green), (1.0, 1.0));
+        assert_eq!((high.red, high.green), (1.0, 0.0));
+    }
+
+    #[test]
+    fn test_cells_near_clamps_to_grid() {
+        let terrain = TerrainData::create_flat(16, 16, 1.0, 0.0).unwrap();
+        let nav_grid =
+            NavigationGrid::from_terrain(&terrain, PathfindingConfig::default()).unwrap();
+
+        // No focus point draws the whole grid
+        let all = cells_near(&nav_grid, None, 2.0);
+        assert_eq!(all.len(), (nav_grid.width * nav_grid.height) as usize);
+
+        // A corner focus is clipped by the grid edges
+        let corner = nav_grid.grid_to_world(GridNode::new(0, 0));
+        let near = cells_near(&nav_grid, Some(corner), 2.0);
+        assert_eq!(near.len(), 9);
+        assert!(near.iter().all(|n| n.x <= 2 && n.z <= 2));
+    }
+}
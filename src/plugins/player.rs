@@ -6,7 +6,9 @@ use crate::game_logic::{
     should_clear_movement_target, validate_component_initialization, validate_mouse_input,
 };
 use crate::map::MapDefinition;
-use crate::pathfinding::{plan_paths, update_pathfinding_agents};
+use crate::pathfinding::{
+    AvoidanceConfig, apply_local_avoidance, plan_paths, update_pathfinding_agents,
+};
 use crate::resources::{GameConfig, GameState};
 use bevy::prelude::Camera3d;
 use bevy::prelude::*;
@@ -17,23 +19,25 @@ pub struct PlayerPlugin;
 
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            OnEnter(GameState::Playing),
-            spawn_player.after(crate::plugins::map_loader::load_map),
-        )
-        .add_systems(
-            Update,
-            (
-                handle_player_input,
-                plan_paths.after(handle_player_input),
-                update_pathfinding_agents.after(plan_paths),
-                move_player.after(update_pathfinding_agents),
-                update_player_from_controller_output.after(move_player),
-                debug_player_state.after(update_player_from_controller_output),
+        app.init_resource::<AvoidanceConfig>()
+            .add_systems(
+                OnEnter(GameState::Playing),
+                spawn_player.after(crate::plugins::map_loader::load_map),
             )
-                .run_if(in_state(GameState::Playing)),
-        )
-        .add_systems(OnExit(GameState::Playing), cleanup_player);
+            .add_systems(
+                Update,
+                (
+                    handle_player_input,
+                    plan_paths.after(handle_player_input),
+                    update_pathfinding_agents.after(plan_paths),
+                    move_player.after(update_pathfinding_agents),
+                    apply_local_avoidance.after(move_player),
+                    update_player_from_controller_output.after(apply_local_avoidance),
+                    debug_player_state.after(update_player_from_controller_output),
+                )
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(OnExit(GameState::Playing), cleanup_player);
     }
 }
 
@@ -216,12 +220,14 @@ fn move_player(
         &mut Transform,
         &mut Player,
         &mut KinematicCharacterController,
-        &PathfindingAgent,
+        &mut PathfindingAgent,
     )>,
     game_config: Res<GameConfig>,
     time: Res<Time>,
 ) {
-    for (mut transform, mut player, mut controller, pathfinding_agent) in player_query.iter_mut() {
+    for (mut transform, mut player, mut controller, mut pathfinding_agent) in
+        player_query.iter_mut()
+    {
         let config = MovementConfig {
             speed: player.speed.0,
             stopping_distance: game_config.settings.player_stopping_distance.get(),
@@ -303,6 +309,10 @@ fn move_player(
                 movement_config,
             );
             controller.translation = Some(gravity_result.movement_vector);
+            pathfinding_agent.set_preferred_velocity(
+                final_movement_vector / time.delta_secs().max(f32::EPSILON),
+                player.speed.0,
+            );
 
             debug!(
                 "Moving: distance={:.2} speed_factor={:.2}",
@@ -339,6 +349,7 @@ fn move_player(
             let gravity_result =
                 apply_gravity_to_movement(Vec3::ZERO, false, time.delta_secs(), movement_config);
             controller.translation = Some(gravity_result.movement_vector);
+            pathfinding_agent.set_preferred_velocity(Vec3::ZERO, player.speed.0);
         }
     }
 }
@@ -1,7 +1,12 @@
 use crate::components::*;
 use crate::map::MapDefinition;
-use crate::resources::GameState;
-use crate::terrain::generate_terrain_mesh_and_collider;
+use crate::resources::{GameConfig, GameState};
+use crate::terrain::biomes::create_default_biomes;
+use crate::terrain::chunks::{
+    TerrainChunkConfig, chunk_layout, generate_chunk_collider, generate_chunk_mesh,
+};
+use crate::terrain::generate_water_mesh;
+use crate::terrain::surface::{SurfaceVertex, TerrainPalette, surface_vertices, terrain_material};
 use bevy::prelude::*;
 use bevy_rapier3d::prelude::*;
 
@@ -9,83 +14,146 @@ pub struct ScenePlugin;
 
 impl Plugin for ScenePlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            OnEnter(GameState::Playing),
-            setup_scene.after(crate::plugins::map_loader::load_map),
-        )
-        .add_systems(Update, follow_camera.run_if(in_state(GameState::Playing)));
+        app.init_resource::<TerrainChunkConfig>()
+            .add_systems(
+                OnEnter(GameState::Playing),
+                (spawn_terrain, setup_scene).after(crate::plugins::map_loader::load_map),
+            )
+            .add_systems(
+                Update,
+                (
+                    follow_camera,
+                    update_terrain_chunk_lod.after(follow_camera),
+                    update_terrain_chunk_colliders,
+                )
+                    .run_if(in_state(GameState::Playing)),
+            );
     }
 }
 
-fn setup_scene(
+/// Per-vertex surface of the loaded map, kept so chunk LODs can be meshed on demand
+#[derive(Resource)]
+struct TerrainSurface(Vec<SurfaceVertex>);
+
+/// Spawn the chunked terrain and its water
+fn spawn_terrain(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
-    camera_query: Query<&Camera3d>,
     ground_query: Query<&Ground>,
-    light_query: Query<&SceneLight>,
     map: Res<MapDefinition>,
+    chunk_config: Res<TerrainChunkConfig>,
+    game_config: Res<GameConfig>,
 ) {
     // Only spawn ground if it doesn't exist
     if ground_query.is_empty() {
-        // Generate real 3D terrain from heightmap data
-        match generate_terrain_mesh_and_collider(&map.terrain) {
-            Ok((mesh, collider)) => {
-                let terrain_width = map.terrain.width as f32 * map.terrain.scale;
-                let terrain_height = map.terrain.height as f32 * map.terrain.scale;
-                let center_x_offset = terrain_width / 2.0;
-                let center_z_offset = terrain_height / 2.0;
-                info!(
-                    "Generated terrain: {}x{} (scale: {}), world bounds: ({:.1}, {:.1}) to ({:.1}, {:.1})",
-                    map.terrain.width,
-                    map.terrain.height,
-                    map.terrain.scale,
-                    -center_x_offset,
-                    -center_z_offset,
-                    center_x_offset - map.terrain.scale,
-                    center_z_offset - map.terrain.scale
+        let chunks = chunk_layout(&map.terrain, chunk_config.chunk_size);
+        if !chunks.is_empty() {
+            let terrain_width = map.terrain.width as f32 * map.terrain.scale;
+            let terrain_height = map.terrain.height as f32 * map.terrain.scale;
+            let center_x_offset = terrain_width / 2.0;
+            let center_z_offset = terrain_height / 2.0;
+            info!(
+                "Generated terrain: {}x{} (scale: {}) in {} chunks, world bounds: ({:.1}, {:.1}) to ({:.1}, {:.1})",
+                map.terrain.width,
+                map.terrain.height,
+                map.terrain.scale,
+                chunks.len(),
+                -center_x_offset,
+                -center_z_offset,
+                center_x_offset - map.terrain.scale,
+                center_z_offset - map.terrain.scale
+            );
+
+            let surface = surface_vertices(
+                &map.terrain,
+                map.biome_map.as_ref(),
+                &map.road_surfaces,
+                &create_default_biomes(),
+                &TerrainPalette::default(),
+            );
+            let material = materials.add(terrain_material());
+
+            // Start at the configured cap; colliders follow once entities exist
+            let starting_level = LodLevel::try_from(game_config.settings.max_lod_level.as_str())
+                .unwrap_or(LodLevel::High); // Default to High on invalid config
+
+            // Only the starting level is meshed here; the LOD system builds the others when needed
+            for bounds in chunks {
+                let mut chunk = TerrainChunk {
+                    bounds,
+                    current_level: starting_level,
+                    high_handle: None,
+                    med_handle: None,
+                    low_handle: None,
+                    collider: None,
+                };
+                let starting_mesh = chunk_lod_mesh(
+                    &mut chunk,
+                    starting_level,
+                    &mut meshes,
+                    &map,
+                    &surface,
+                    &chunk_config,
                 );
 
                 commands.spawn((
-                    Mesh3d(meshes.add(mesh)),
-                    MeshMaterial3d(materials.add(StandardMaterial {
-                        base_color: Color::srgb(0.3, 0.5, 0.3),
-                        ..default()
-                    })),
+                    Mesh3d(starting_mesh),
+                    MeshMaterial3d(material.clone()),
                     Transform::from_xyz(0.0, 0.0, 0.0),
-                    RigidBody::Fixed,
-                    collider,
+                    chunk,
                     Ground,
                 ));
             }
-            Err(e) => {
-                warn!("Failed to generate terrain mesh: {e}");
+            commands.insert_resource(TerrainSurface(surface));
+        } else {
+            warn!("Terrain is too small to mesh, using a flat plane");
 
-                // Fallback to flat terrain if heightmap generation fails
-                let terrain_width = map.terrain.width as f32 * map.terrain.scale;
-                let terrain_height = map.terrain.height as f32 * map.terrain.scale;
+            // Fallback to flat terrain when there are no cells to chunk
+            let terrain_width = map.terrain.width as f32 * map.terrain.scale;
+            let terrain_height = map.terrain.height as f32 * map.terrain.scale;
 
-                commands.spawn((
-                    Mesh3d(
-                        meshes.add(
-                            Plane3d::default()
-                                .mesh()
-                                .size(terrain_width, terrain_height),
-                        ),
+            commands.spawn((
+                Mesh3d(
+                    meshes.add(
+                        Plane3d::default()
+                            .mesh()
+                            .size(terrain_width, terrain_height),
                     ),
-                    MeshMaterial3d(materials.add(StandardMaterial {
-                        base_color: Color::srgb(0.3, 0.5, 0.3),
-                        ..default()
-                    })),
-                    Transform::from_xyz(0.0, 0.0, 0.0),
-                    RigidBody::Fixed,
-                    Collider::cuboid(terrain_width / 2.0, 0.1, terrain_height / 2.0),
-                    Ground,
-                ));
-            }
+                ),
+                MeshMaterial3d(materials.add(StandardMaterial {
+                    base_color: Color::srgb(0.3, 0.5, 0.3),
+                    ..default()
+                })),
+                Transform::from_xyz(0.0, 0.0, 0.0),
+                RigidBody::Fixed,
+                Collider::cuboid(terrain_width / 2.0, 0.1, terrain_height / 2.0),
+                Ground,
+            ));
+        }
+
+        // Water is purely visual; navigation already keeps agents out of deep water
+        if let Some(mesh) = generate_water_mesh(&map.terrain, &map.water_bodies) {
+            commands.spawn((
+                Mesh3d(meshes.add(mesh)),
+                MeshMaterial3d(materials.add(StandardMaterial {
+                    base_color: Color::srgba(0.15, 0.35, 0.6, 0.7),
+                    alpha_mode: AlphaMode::Blend,
+                    perceptual_roughness: 0.1,
+                    ..default()
+                })),
+                Transform::from_xyz(0.0, 0.0, 0.0),
+                Water,
+            ));
         }
     }
+}
 
+fn setup_scene(
+    mut commands: Commands,
+    camera_query: Query<&Camera3d>,
+    light_query: Query<&SceneLight>,
+) {
     // Only spawn light if it doesn't exist
     if light_query.is_empty() {
         commands.spawn((
@@ -139,3 +207,107 @@ fn follow_camera(
         camera_transform.look_at(player_transform.translation, Vec3::Y);
     }
 }
+
+/// Swap chunk meshes by camera distance, like `update_entity_lod` does for characters
+fn update_terrain_chunk_lod(
+    mut chunk_query: Query<(&mut TerrainChunk, &mut Mesh3d)>,
+    camera_query: Query<&Transform, With<CameraFollow>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    map: Res<MapDefinition>,
+    surface: Option<Res<TerrainSurface>>,
+    chunk_config: Res<TerrainChunkConfig>,
+    game_config: Res<GameConfig>,
+) {
+    let (Ok(camera_transform), Some(surface)) = (camera_query.single(), surface) else {
+        return;
+    };
+    let max_lod =
+        LodLevel::try_from(game_config.settings.max_lod_level.as_str()).unwrap_or(LodLevel::High); // Default to High on invalid config
+
+    for (mut chunk, mut mesh) in chunk_query.iter_mut() {
+        let distance = chunk
+            .bounds
+            .world_distance(&map.terrain, camera_transform.translation);
+        let required_lod = LodLevel::apply_max_cap(chunk_config.level_at(distance), max_lod);
+
+        if chunk.current_level != required_lod {
+            mesh.0 = chunk_lod_mesh(
+                &mut chunk,
+                required_lod,
+                &mut meshes,
+                &map,
+                &surface.0,
+                &chunk_config,
+            );
+            chunk.current_level = required_lod;
+        }
+    }
+}
+
+/// Mesh for one LOD level of a chunk, built and cached the first time it is asked for
+fn chunk_lod_mesh(
+    chunk: &mut TerrainChunk,
+    level: LodLevel,
+    meshes: &mut Assets<Mesh>,
+    map: &MapDefinition,
+    surface: &[SurfaceVertex],
+    chunk_config: &TerrainChunkConfig,
+) -> Handle<Mesh> {
+    let bounds = chunk.bounds;
+    let handle = match level {
+        LodLevel::High => &mut chunk.high_handle,
+        LodLevel::Medium => &mut chunk.med_handle,
+        LodLevel::Low => &mut chunk.low_handle,
+    };
+    handle
+        .get_or_insert_with(|| {
+            meshes.add(generate_chunk_mesh(
+                &map.terrain,
+                bounds,
+                chunk_config.step_for(level),
+                Some(surface),
+            ))
+        })
+        .clone()
+}
+
+/// Terrain chunks and whether each currently has a collider
+type ChunkColliders<'w, 's> = Query<'w, 's, (Entity, &'static mut TerrainChunk, Has<Collider>)>;
+
+/// Anything that walks on terrain and needs solid ground nearby
+type Actors<'w, 's> = Query<'w, 's, &'static Transform, Or<(With<Player>, With<Enemy>)>>;
+
+/// Give chunks near the player or an enemy a collider and drop it once they leave
+fn update_terrain_chunk_colliders(
+    mut commands: Commands,
+    mut chunk_query: ChunkColliders,
+    actor_query: Actors,
+    map: Res<MapDefinition>,
+    chunk_config: Res<TerrainChunkConfig>,
+) {
+    for (entity, mut chunk, has_collider) in chunk_query.iter_mut() {
+        let near = actor_query.iter().any(|transform| {
+            chunk
+                .bounds
+                .world_distance(&map.terrain, transform.translation)
+                <= chunk_config.collider_radius
+        });
+
+        if near && !has_collider {
+            if chunk.collider.is_none() {
+                match generate_chunk_collider(&map.terrain, chunk.bounds) {
+                    Ok(collider) => chunk.collider = Some(collider),
+                    Err(e) => {
+                        warn!("Failed to generate terrain chunk collider: {e}");
+                        continue;
+                    }
+                }
+            }
+            if let Some(collider) = chunk.collider.clone() {
+                commands.entity(entity).insert((RigidBody::Fixed, collider));
+            }
+        } else if !near && has_collider {
+            commands.entity(entity).remove::<(RigidBody, Collider)>();
+        }
+    }
+}
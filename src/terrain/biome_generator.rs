@@ -1,7 +1,9 @@
 use super::biomes::{BiomeBlend, BiomeConfig, BiomeData, BiomeMap, BiomeType};
+use super::climate::{ClimateConfig, ClimateMap, climate_blend};
 use super::constants::*;
 use crate::game_logic::errors::{MinionError, MinionResult};
-use crate::map::TerrainData;
+use crate::map::{TerrainData, WaterBody};
+use crate::terrain_generation::{calculate_slope, par_rows};
 use bevy::prelude::*;
 use rand::{Rng, SeedableRng};
 use rand_pcg::Pcg64;
@@ -15,6 +17,17 @@ pub struct BiomeGenerationConfig {
     pub region_count: u32,
     pub transition_radius: f32, // World units for smooth blending
     pub biome_preferences: Vec<BiomeType>, // Preferred biomes to place
+    pub assignment: BiomeAssignment,
+}
+
+/// How biomes are assigned to the terrain
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum BiomeAssignment {
+    /// Blend biomes placed at jittered Voronoi sites
+    #[default]
+    Voronoi,
+    /// Match temperature and humidity maps against each biome's climate
+    Climate(ClimateConfig),
 }
 
 /// Voronoi region with biome assignment
@@ -31,12 +44,14 @@ pub struct BiomeGenerator {
     biome_configs: HashMap<BiomeType, BiomeConfig>,
     rng: Pcg64,
     voronoi_cache: Option<(Voronoi, Vec<BiomeRegion>)>, // Cache the expensive Voronoi computation
+    water_bodies: Vec<WaterBody>,                       // Moisture sources for climate mode
 }
 
-impl Default for BiomeGenerationConfig {
-    fn default() -> Self {
+impl BiomeGenerationConfig {
+    /// Default regions and blending; the seed should come from the map seed
+    pub fn new(seed: u32) -> Self {
         Self {
-            seed: 12345,
+            seed,
             region_count: DEFAULT_BIOME_REGIONS,
             transition_radius: DEFAULT_TRANSITION_RADIUS,
             biome_preferences: vec![
@@ -45,6 +60,7 @@ impl Default for BiomeGenerationConfig {
                 BiomeType::Mountains,
                 BiomeType::Desert,
             ],
+            assignment: BiomeAssignment::Voronoi,
         }
     }
 }
@@ -61,11 +77,22 @@ impl BiomeGenerator {
             biome_configs,
             rng,
             voronoi_cache: None,
+            water_bodies: Vec::new(),
         }
     }
 
+    /// Water bodies that raise humidity in climate mode
+    pub fn with_water_bodies(mut self, water_bodies: Vec<WaterBody>) -> Self {
+        self.water_bodies = water_bodies;
+        self
+    }
+
     /// Generate a biome map for the given terrain
     pub fn generate(&mut self, terrain: &TerrainData) -> MinionResult<BiomeMap> {
+        if let BiomeAssignment::Climate(ref climate) = self.config.assignment {
+            return Ok(self.generate_from_climate(terrain, climate));
+        }
+
         info!(
             "Generating biome map with {} regions",
             self.config.region_count
@@ -109,26 +136,24 @@ impl BiomeGenerator {
             (cached_voronoi, cached_regions)
         };
 
-        // Generate biome blend for each terrain grid cell
-        let mut blends = Vec::with_capacity((terrain.width * terrain.height) as usize);
-
-        for z in 0..terrain.height {
-            for x in 0..terrain.width {
-                let world_x = (x as f32 * terrain.scale) - half_width;
-                let world_z = (z as f32 * terrain.scale) - half_height;
-
-                let blend = self.calculate_biome_blend_at_position(
-                    Point {
-                        x: world_x as f64,
-                        y: world_z as f64,
-                    },
-                    regions,
-                    voronoi,
-                )?;
-
-                blends.push(blend);
-            }
-        }
+        // Generate biome blend for each terrain grid cell, rows in parallel
+        let generator = &*self;
+        let blends = par_rows(terrain.height, |z| {
+            let world_z = (z as f32 * terrain.scale) - half_height;
+            (0..terrain.width)
+                .map(|x| {
+                    let world_x = (x as f32 * terrain.scale) - half_width;
+                    generator.calculate_biome_blend_at_position(
+                        Point {
+                            x: world_x as f64,
+                            y: world_z as f64,
+                        },
+                        regions,
+                        voronoi,
+                    )
+                })
+                .collect()
+        });
 
         Ok(BiomeMap {
             width: terrain.width,
@@ -138,6 +163,45 @@ impl BiomeGenerator {
         })
     }
 
+    /// Assign biomes by looking up each sample's climate
+    fn generate_from_climate(&self, terrain: &TerrainData, climate: &ClimateConfig) -> BiomeMap {
+        info!("Generating climate biome map");
+
+        let climate_map =
+            ClimateMap::generate(terrain, &self.water_bodies, climate, self.config.seed);
+        let blends = par_rows(terrain.height, |z| {
+            (0..terrain.width)
+                .map(|x| {
+                    let index = (z * terrain.width + x) as usize;
+                    // Same normalization as the Voronoi site assignment
+                    let elevation = (terrain.heights[index] / 50.0).clamp(-1.0, 1.0);
+                    let slope = if x > 0 && z > 0 && x < terrain.width - 1 && z < terrain.height - 1
+                    {
+                        calculate_slope(terrain, x, z)
+                    } else {
+                        0.0
+                    };
+
+                    climate_blend(
+                        climate_map.temperature[index],
+                        climate_map.humidity[index],
+                        elevation,
+                        slope,
+                        &self.biome_configs,
+                        climate,
+                    )
+                })
+                .collect()
+        });
+
+        BiomeMap {
+            width: terrain.width,
+            height: terrain.height,
+            blends,
+            scale: terrain.scale,
+        }
+    }
+
     /// Generate complete biome data including discrete map and smooth blends
     pub fn generate_biome_data(&mut self, terrain: &TerrainData) -> MinionResult<BiomeData> {
         // Generate the smooth blend map
@@ -280,7 +344,7 @@ impl BiomeGenerator {
             return 0.0;
         }
 
-        crate::terrain_generation::calculate_slope(terrain, grid_x as u32, grid_z as u32)
+        calculate_slope(terrain, grid_x as u32, grid_z as u32)
     }
 
     /// Select the best biome type for a location based on characteristics
@@ -308,7 +372,10 @@ impl BiomeGenerator {
         let mut best_biome = BiomeType::Plains;
         let mut best_score = 0.0;
 
-        for (biome_type, config) in &self.biome_configs {
+        // HashMap order is random, visit biomes in a fixed order so ties are stable
+        let mut biome_configs: Vec<_> = self.biome_configs.iter().collect();
+        biome_configs.sort_by_key(|(biome_type, _)| **biome_type);
+        for (biome_type, config) in biome_configs {
             let score = config.is_suitable(elevation, slope);
             if score > best_score {
                 best_score = score;
@@ -325,7 +392,7 @@ impl BiomeGenerator {
         point: Point,
         regions: &[BiomeRegion],
         _voronoi: &voronoice::Voronoi,
-    ) -> MinionResult<BiomeBlend> {
+    ) -> BiomeBlend {
         // Calculate distances to all region centers
         let mut influences = Vec::new();
 
@@ -353,11 +420,11 @@ impl BiomeGenerator {
 
         // If no influences (shouldn't happen), default to Plains
         if influences.is_empty() {
-            return Ok(BiomeBlend::single(BiomeType::Plains));
+            return BiomeBlend::single(BiomeType::Plains);
         }
 
         // Create blend from influences
-        Ok(BiomeBlend::from_weights(influences))
+        BiomeBlend::from_weights(influences)
     }
 }
 
@@ -368,7 +435,8 @@ mod tests {
 
     #[test]
     fn test_biome_generation_config() {
-        let config = BiomeGenerationConfig::default();
+        let config = BiomeGenerationConfig::new(7);
+        assert_eq!(config.seed, 7);
         assert_eq!(config.region_count, DEFAULT_BIOME_REGIONS);
         assert!(config.transition_radius > 0.0);
         assert!(!config.biome_preferences.is_empty());
@@ -378,7 +446,7 @@ mod tests {
     fn test_voronoi_sites_generation() {
         let config = BiomeGenerationConfig {
             region_count: 4,
-            ..Default::default()
+            ..BiomeGenerationConfig::new(12345)
         };
         let biome_configs = create_default_biomes();
         let mut generator = BiomeGenerator::new(config, biome_configs);
@@ -398,7 +466,7 @@ mod tests {
         use crate::map::TerrainData;
 
         let terrain = TerrainData::create_flat(10, 10, 1.0, 5.0).unwrap();
-        let config = BiomeGenerationConfig::default();
+        let config = BiomeGenerationConfig::new(12345);
         let biome_configs = create_default_biomes();
         let generator = BiomeGenerator::new(config, biome_configs);
 
@@ -406,4 +474,23 @@ mod tests {
         let elevation = generator.sample_terrain_elevation(&point, &terrain);
         assert_eq!(elevation, 5.0);
     }
+
+    #[test]
+    fn test_climate_assignment() {
+        // Low plain rising into a high ridge
+        let heights = (0..32 * 32).map(|i| (i % 32) as f32 * 2.5).collect();
+        let terrain = TerrainData::new(32, 32, heights, 1.0).unwrap();
+        let config = BiomeGenerationConfig {
+            assignment: BiomeAssignment::Climate(ClimateConfig::default()),
+            ..BiomeGenerationConfig::new(12345)
+        };
+        let mut generator = BiomeGenerator::new(config, create_default_biomes());
+        let data = generator.generate_biome_data(&terrain).unwrap();
+
+        assert_eq!(data.blend_map.blends.len(), 32 * 32);
+        // The cold summit is never desert
+        for row in &data.biome_map {
+            assert_ne!(row[31], BiomeType::Desert);
+        }
+    }
 }
@@ -1,8 +1,9 @@
-use super::biome_generator::{BiomeGenerationConfig, BiomeGenerator};
+use super::biome_generator::{BiomeAssignment, BiomeGenerationConfig, BiomeGenerator};
 use super::biomes::{BiomeData, BiomeMap, BiomeType, create_default_biomes};
+use super::climate::ClimateConfig;
 use super::path_generator::{PathGenerationConfig, PathGenerator, PathNetwork};
 use crate::game_logic::errors::MinionResult;
-use crate::map::TerrainData;
+use crate::map::{TerrainData, WaterBody};
 
 /// Integration utilities for adding biome generation to existing terrain
 pub struct BiomeIntegration;
@@ -24,6 +25,7 @@ impl BiomeIntegration {
                 BiomeType::Mountains,
                 BiomeType::Desert,
             ],
+            assignment: BiomeAssignment::Voronoi,
         };
 
         let biome_configs = create_default_biomes();
@@ -53,6 +55,7 @@ impl BiomeIntegration {
                 BiomeType::Mountains,
                 BiomeType::Desert,
             ],
+            assignment: BiomeAssignment::Voronoi,
         };
 
         let biome_configs = create_default_biomes();
@@ -60,6 +63,24 @@ impl BiomeIntegration {
         generator.generate_biome_data(terrain)
     }
 
+    /// Generate biome data from a climate model, moistened by `water_bodies`
+    pub fn generate_climate_biome_data_for_terrain(
+        terrain: &TerrainData,
+        water_bodies: &[WaterBody],
+        seed: u32,
+        climate: ClimateConfig,
+    ) -> MinionResult<BiomeData> {
+        let config = BiomeGenerationConfig {
+            assignment: BiomeAssignment::Climate(climate),
+            ..BiomeGenerationConfig::new(seed)
+        };
+
+        let biome_configs = create_default_biomes();
+        let mut generator =
+            BiomeGenerator::new(config, biome_configs).with_water_bodies(water_bodies.to_vec());
+        generator.generate_biome_data(terrain)
+    }
+
     /// Generate a path network for terrain with optional biome integration
     pub fn generate_path_network(
         terrain: &TerrainData,
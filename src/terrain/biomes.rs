@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Different biome types that can exist in the world
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum BiomeType {
     Plains,
     Forest,
@@ -46,6 +46,21 @@ pub struct BiomeConfig {
     pub slope_tolerance: f32,                        // Maximum slope this biome tolerates
     pub temperature: f32,                            // -1.0 (cold) to 1.0 (hot)
     pub humidity: f32,                               // -1.0 (dry) to 1.0 (wet)
+    #[serde(default)]
+    pub objects: Vec<ObjectRule>, // Environment objects scattered in this biome
+}
+
+/// Placement rule for one environment object type within a biome
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ObjectRule {
+    pub object_type: String,
+    pub density: f32,                     // Objects per 100 square world units
+    pub spacing: f32,                     // Minimum distance between objects at scale 1.0
+    pub max_slope: f32,                   // Steepest ground the object stands on
+    pub height_range: Option<(f32, f32)>, // World height limits, any height when None
+    pub scale_range: (f32, f32),
+    pub clustering: f32,   // 0.0 spreads evenly, 1.0 packs objects into groups
+    pub cluster_size: f32, // World units across one group
 }
 
 /// Weighted biome influence at a specific location
@@ -90,6 +105,7 @@ impl BiomeConfig {
             slope_tolerance,
             temperature,
             humidity,
+            objects: Vec::new(),
         }
     }
 
@@ -99,6 +115,12 @@ impl BiomeConfig {
         self
     }
 
+    /// Add an environment object placement rule
+    pub fn with_object(mut self, rule: ObjectRule) -> Self {
+        self.objects.push(rule);
+        self
+    }
+
     /// Check if this biome is suitable for given elevation and slope
     pub fn is_suitable(&self, elevation: f32, slope: f32) -> f32 {
         let elevation_score = if elevation >= self.elevation_preference.0
@@ -124,6 +146,41 @@ impl BiomeConfig {
     }
 }
 
+impl ObjectRule {
+    /// Create a rule with even spacing and no height limits
+    pub fn new(object_type: impl Into<String>, density: f32, spacing: f32) -> Self {
+        Self {
+            object_type: object_type.into(),
+            density,
+            spacing,
+            max_slope: 0.4,
+            height_range: None,
+            scale_range: (0.8, 1.2),
+            clustering: 0.0,
+            cluster_size: 20.0,
+        }
+    }
+
+    /// Set the random scale range
+    pub fn with_scale(mut self, min: f32, max: f32) -> Self {
+        self.scale_range = (min, max);
+        self
+    }
+
+    /// Set the steepest slope the object accepts
+    pub fn with_max_slope(mut self, max_slope: f32) -> Self {
+        self.max_slope = max_slope;
+        self
+    }
+
+    /// Gather objects into groups of roughly `size` world units
+    pub fn with_clustering(mut self, strength: f32, size: f32) -> Self {
+        self.clustering = strength;
+        self.cluster_size = size;
+        self
+    }
+}
+
 impl BiomeBlend {
     /// Create a new biome blend with a single biome
     pub fn single(biome_type: BiomeType) -> Self {
@@ -137,7 +194,11 @@ impl BiomeBlend {
         // Remove zero weights
         weights.retain(|(_, weight)| *weight > 0.0);
 
-        // Normalize weights to sum to 1.0
+        // Keep only top 4 weights for efficiency
+        weights.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+        weights.truncate(4);
+
+        // Normalize the kept weights to sum to 1.0
         let total: f32 = weights.iter().map(|(_, w)| *w).sum();
         if total > 0.0 {
             for (_, weight) in &mut weights {
@@ -145,10 +206,6 @@ impl BiomeBlend {
             }
         }
 
-        // Keep only top 4 weights for efficiency
-        weights.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
-        weights.truncate(4);
-
         Self { weights }
     }
 
@@ -223,7 +280,13 @@ pub fn create_default_biomes() -> HashMap<BiomeType, BiomeConfig> {
             0.2,        // Moderate humidity
         )
         .with_secondary_surface(SurfaceType::Dirt, 0.2)
-        .with_secondary_surface(SurfaceType::Rock(RockSize::SmallRocks), 0.1),
+        .with_secondary_surface(SurfaceType::Rock(RockSize::SmallRocks), 0.1)
+        .with_object(
+            ObjectRule::new("tree", 0.6, 2.5)
+                .with_scale(0.8, 1.4)
+                .with_clustering(0.4, 20.0),
+        )
+        .with_object(ObjectRule::new("rock", 0.4, 1.2).with_scale(0.3, 1.5)),
     );
 
     biomes.insert(
@@ -238,7 +301,14 @@ pub fn create_default_biomes() -> HashMap<BiomeType, BiomeConfig> {
             0.5,        // High humidity
         )
         .with_secondary_surface(SurfaceType::Grass, 0.3)
-        .with_secondary_surface(SurfaceType::Rock(RockSize::MediumRocks), 0.15),
+        .with_secondary_surface(SurfaceType::Rock(RockSize::MediumRocks), 0.15)
+        .with_object(
+            ObjectRule::new("tree", 4.0, 2.0)
+                .with_scale(1.2, 2.0)
+                .with_max_slope(0.5)
+                .with_clustering(0.8, 30.0),
+        )
+        .with_object(ObjectRule::new("rock", 0.5, 1.2).with_scale(0.4, 1.8)),
     );
 
     biomes.insert(
@@ -253,7 +323,14 @@ pub fn create_default_biomes() -> HashMap<BiomeType, BiomeConfig> {
             -0.2,       // Low humidity
         )
         .with_secondary_surface(SurfaceType::Rock(RockSize::Boulders), 0.3)
-        .with_secondary_surface(SurfaceType::Ice, 0.2),
+        .with_secondary_surface(SurfaceType::Ice, 0.2)
+        .with_object(
+            ObjectRule::new("rock", 2.0, 1.5)
+                .with_scale(0.8, 3.3)
+                .with_max_slope(0.8)
+                .with_clustering(0.6, 12.0),
+        )
+        .with_object(ObjectRule::new("tree", 0.2, 2.0).with_scale(0.6, 1.0)),
     );
 
     biomes.insert(
@@ -268,7 +345,12 @@ pub fn create_default_biomes() -> HashMap<BiomeType, BiomeConfig> {
             -0.8,       // Very dry
         )
         .with_secondary_surface(SurfaceType::Rock(RockSize::MediumRocks), 0.2)
-        .with_secondary_surface(SurfaceType::Dirt, 0.1),
+        .with_secondary_surface(SurfaceType::Dirt, 0.1)
+        .with_object(
+            ObjectRule::new("rock", 0.6, 1.5)
+                .with_scale(0.6, 2.4)
+                .with_clustering(0.5, 15.0),
+        ),
     );
 
     biomes.insert(
@@ -298,7 +380,9 @@ pub fn create_default_biomes() -> HashMap<BiomeType, BiomeConfig> {
             -0.3,       // Low humidity
         )
         .with_secondary_surface(SurfaceType::Rock(RockSize::Boulders), 0.3)
-        .with_secondary_surface(SurfaceType::Dirt, 0.1),
+        .with_secondary_surface(SurfaceType::Dirt, 0.1)
+        .with_object(ObjectRule::new("rock", 0.6, 1.5).with_scale(0.5, 2.0))
+        .with_object(ObjectRule::new("tree", 0.1, 2.0).with_scale(0.4, 0.8)),
     );
 
     biomes.insert(
@@ -337,6 +421,19 @@ mod tests {
 
         assert_eq!(blend.get_weight(BiomeType::Forest), 0.75);
         assert_eq!(blend.get_weight(BiomeType::Plains), 0.25);
+
+        // Dropping the weakest biomes still leaves weights summing to 1.0
+        let weights = vec![
+            (BiomeType::Forest, 4.0),
+            (BiomeType::Plains, 3.0),
+            (BiomeType::Desert, 2.0),
+            (BiomeType::Swamp, 2.0),
+            (BiomeType::Tundra, 1.0),
+        ];
+        let blend = BiomeBlend::from_weights(weights);
+        assert_eq!(blend.weights.len(), 4);
+        let total: f32 = blend.weights.iter().map(|(_, w)| w).sum();
+        assert!((total - 1.0).abs() < 1e-6);
     }
 
     #[test]
@@ -378,5 +475,15 @@ mod tests {
         assert_eq!(biomes.len(), 7);
         assert!(biomes.contains_key(&BiomeType::Plains));
         assert!(biomes.contains_key(&BiomeType::Mountains));
+
+        // Forests grow trees, deserts do not
+        let has_trees = |biome| {
+            biomes[&biome]
+                .objects
+                .iter()
+                .any(|rule| rule.object_type == "tree")
+        };
+        assert!(has_trees(BiomeType::Forest));
+        assert!(!has_trees(BiomeType::Desert));
     }
 }
@@ -0,0 +1,456 @@
+//! Terrain split into chunks with decimated level-of-detail meshes.
+//!
+//! Each chunk mesh samples its interior every `step` cells but always keeps
+//! the border at full resolution, stitched to the coarse interior. Shared
+//! borders therefore match whatever level the neighbouring chunk shows, so
+//! LOD changes never open cracks.
+
+use super::surface::SurfaceVertex;
+use crate::components::LodLevel;
+use crate::game_logic::errors::{MinionError, MinionResult};
+use crate::map::TerrainData;
+use bevy::prelude::*;
+use bevy::render::mesh::{Indices, PrimitiveTopology};
+use bevy_rapier3d::prelude::*;
+use std::collections::HashMap;
+
+/// Chunk size and LOD tuning
+#[derive(Debug, Clone, Resource)]
+pub struct TerrainChunkConfig {
+    /// Cells per chunk side
+    pub chunk_size: u32,
+    /// Sample step of the high, medium, and low meshes
+    pub lod_steps: [u32; 3],
+    /// Camera distance to switch to medium LOD
+    pub lod_distance_high: f32,
+    /// Camera distance to switch to low LOD
+    pub lod_distance_low: f32,
+    /// Chunks within this distance of a player or enemy get a collider
+    pub collider_radius: f32,
+}
+
+impl Default for TerrainChunkConfig {
+    fn default() -> Self {
+        Self {
+            chunk_size: 64,
+            lod_steps: [1, 4, 16],
+            lod_distance_high: 40.0, // Matches the follow camera's view of the player
+            lod_distance_low: 90.0,
+            collider_radius: 20.0,
+        }
+    }
+}
+
+impl TerrainChunkConfig {
+    /// Sample step used for a LOD level
+    pub fn step_for(&self, level: LodLevel) -> u32 {
+        match level {
+            LodLevel::High => self.lod_steps[0],
+            LodLevel::Medium => self.lod_steps[1],
+            LodLevel::Low => self.lod_steps[2],
+        }
+        .max(1)
+    }
+
+    /// LOD level wanted at `distance` from the camera
+    pub fn level_at(&self, distance: f32) -> LodLevel {
+        if distance <= self.lod_distance_high {
+            LodLevel::High
+        } else if distance <= self.lod_distance_low {
+            LodLevel::Medium
+        } else {
+            LodLevel::Low
+        }
+    }
+}
+
+/// Heightmap vertex range covered by one chunk; neighbours share edge vertices
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChunkBounds {
+    pub min_x: u32,
+    pub min_z: u32,
+    pub max_x: u32, // Inclusive
+    pub max_z: u32, // Inclusive
+}
+
+impl ChunkBounds {
+    /// World position of the chunk center at height zero
+    pub fn world_center(&self, terrain: &TerrainData) -> Vec3 {
+        let x = (self.min_x + self.max_x) as f32 / 2.0;
+        let z = (self.min_z + self.max_z) as f32 / 2.0;
+        Vec3::new(
+            x * terrain.scale - terrain.width as f32 * terrain.scale / 2.0,
+            0.0,
+            z * terrain.scale - terrain.height as f32 * terrain.scale / 2.0,
+        )
+    }
+
+    /// Horizontal distance from `point` to the closest part of the chunk
+    pub fn world_distance(&self, terrain: &TerrainData, point: Vec3) -> f32 {
+        let half_width = terrain.width as f32 * terrain.scale / 2.0;
+        let half_height = terrain.height as f32 * terrain.scale / 2.0;
+        let min = Vec2::new(
+            self.min_x as f32 * terrain.scale - half_width,
+            self.min_z as f32 * terrain.scale - half_height,
+        );
+        let max = Vec2::new(
+            self.max_x as f32 * terrain.scale - half_width,
+            self.max_z as f32 * terrain.scale - half_height,
+        );
+        let point = Vec2::new(point.x, point.z);
+        point.distance(point.clamp(min, max))
+    }
+}
+
+/// Split the heightmap into chunks of `chunk_size` cells
+pub fn chunk_layout(terrain: &TerrainData, chunk_size: u32) -> Vec<ChunkBounds> {
+    let chunk_size = chunk_size.max(1);
+    let last_x = terrain.width.saturating_sub(1);
+    let last_z = terrain.height.saturating_sub(1);
+
+    let mut chunks = Vec::new();
+    let mut min_z = 0;
+    while min_z < last_z {
+        let mut min_x = 0;
+        while min_x < last_x {
+            chunks.push(ChunkBounds {
+                min_x,
+                min_z,
+                max_x: (min_x + chunk_size).min(last_x),
+                max_z: (min_z + chunk_size).min(last_z),
+            });
+            min_x += chunk_size;
+        }
+        min_z += chunk_size;
+    }
+    chunks
+}
+
+/// Coarse sample offsets across `size` cells, always ending on the edge
+fn coarse_offsets(size: u32, step: u32) -> Vec<u32> {
+    let mut offsets: Vec<u32> = (0..size).step_by(step as usize).collect();
+    offsets.push(size);
+    offsets
+}
+
+/// Builds a chunk mesh, sharing vertices between triangles
+struct ChunkMeshBuilder<'a> {
+    terrain: &'a TerrainData,
+    surface: Option<&'a [SurfaceVertex]>,
+    lookup: HashMap<(u32, u32), u32>,
+    samples: Vec<(u32, u32)>,
+    indices: Vec<u32>,
+}
+
+impl ChunkMeshBuilder<'_> {
+    fn vertex(&mut self, x: u32, z: u32) -> u32 {
+        *self.lookup.entry((x, z)).or_insert_with(|| {
+            self.samples.push((x, z));
+            (self.samples.len() - 1) as u32
+        })
+    }
+
+    /// Add a triangle, flipping it if needed so it faces up
+    fn triangle(&mut self, a: (u32, u32), b: (u32, u32), c: (u32, u32)) {
+        let (ux, uz) = (b.0 as i64 - a.0 as i64, b.1 as i64 - a.1 as i64);
+        let (vx, vz) = (c.0 as i64 - a.0 as i64, c.1 as i64 - a.1 as i64);
+        let up = uz * vx - ux * vz;
+        if up == 0 {
+            return; // Degenerate
+        }
+        let (b, c) = if up > 0 { (b, c) } else { (c, b) };
+
+        let indices = [
+            self.vertex(a.0, a.1),
+            self.vertex(b.0, b.1),
+            self.vertex(c.0, c.1),
+        ];
+        self.indices.extend_from_slice(&indices);
+    }
+
+    /// Grid of quads over the given sample columns and rows
+    fn grid(&mut self, xs: &[u32], zs: &[u32]) {
+        for z in zs.windows(2) {
+            for x in xs.windows(2) {
+                self.triangle((x[0], z[0]), (x[0], z[1]), (x[1], z[0]));
+                self.triangle((x[1], z[0]), (x[0], z[1]), (x[1], z[1]));
+            }
+        }
+    }
+
+    /// Zip a full resolution border onto a parallel coarse inner row, both
+    /// ordered by the `along` coordinate
+    fn stitch(&mut self, outer: &[(u32, u32)], inner: &[(u32, u32)], along: fn((u32, u32)) -> u32) {
+        let (mut i, mut j) = (0, 0);
+        while i + 1 < outer.len() || j + 1 < inner.len() {
+            let advance_outer = j + 1 >= inner.len()
+                || (i + 1 < outer.len() && along(outer[i + 1]) <= along(inner[j + 1]));
+            if advance_outer {
+                self.triangle(outer[i], outer[i + 1], inner[j]);
+                i += 1;
+            } else {
+                self.triangle(outer[i], inner[j + 1], inner[j]);
+                j += 1;
+            }
+        }
+    }
+
+    fn build(self) -> Mesh {
+        let terrain = self.terrain;
+        let width = terrain.width as usize;
+        let center_x_offset = terrain.width as f32 * terrain.scale / 2.0;
+        let center_z_offset = terrain.height as f32 * terrain.scale / 2.0;
+        let height_at = |x: i64, z: i64| {
+            let x = x.clamp(0, terrain.width as i64 - 1) as usize;
+            let z = z.clamp(0, terrain.height as i64 - 1) as usize;
+            terrain.heights[z * width + x]
+        };
+
+        let mut positions = Vec::with_capacity(self.samples.len());
+        let mut normals = Vec::with_capacity(self.samples.len());
+        let mut uvs = Vec::with_capacity(self.samples.len());
+        for &(x, z) in &self.samples {
+            let (xi, zi) = (x as i64, z as i64);
+            positions.push([
+                x as f32 * terrain.scale - center_x_offset,
+                height_at(xi, zi),
+                z as f32 * terrain.scale - center_z_offset,
+            ]);
+
+            // Normals come from the full heightmap so every LOD shades alike
+            let dx = (height_at(xi + 1, zi) - height_at(xi - 1, zi)) / (2.0 * terrain.scale);
+            let dz = (height_at(xi, zi + 1) - height_at(xi, zi - 1)) / (2.0 * terrain.scale);
+            normals.push(Vec3::new(-dx, 1.0, -dz).normalize().to_array());
+
+            uvs.push([
+                x as f32 / (terrain.width - 1).max(1) as f32,
+                z as f32 / (terrain.height - 1).max(1) as f32,
+            ]);
+        }
+
+        let mut mesh = Mesh::new(
+            PrimitiveTopology::TriangleList,
+            bevy::render::render_asset::RenderAssetUsages::RENDER_WORLD
+                | bevy::render::render_asset::RenderAssetUsages::MAIN_WORLD,
+        );
+        if let Some(surface) = self.surface {
+            let color = |&(x, z): &(u32, u32)| surface[(z * terrain.width + x) as usize].color;
+            mesh.insert_attribute(
+                Mesh::ATTRIBUTE_COLOR,
+                self.samples.iter().map(color).collect::<Vec<_>>(),
+            );
+        }
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+        mesh.insert_indices(Indices::U32(self.indices));
+        mesh
+    }
+}
+
+/// Mesh for one chunk sampled every `step` cells, with a full resolution border.
+///
+/// `surface` is the output of `surface_vertices` for the whole terrain; when
+/// given, its colors are copied onto the chunk.
+pub fn generate_chunk_mesh(
+    terrain: &TerrainData,
+    bounds: ChunkBounds,
+    step: u32,
+    surface: Option<&[SurfaceVertex]>,
+) -> Mesh {
+    let mut builder = ChunkMeshBuilder {
+        terrain,
+        surface,
+        lookup: HashMap::new(),
+        samples: Vec::new(),
+        indices: Vec::new(),
+    };
+
+    let size_x = bounds.max_x - bounds.min_x;
+    let size_z = bounds.max_z - bounds.min_z;
+    let xs: Vec<u32> = coarse_offsets(size_x, step.max(1))
+        .into_iter()
+        .map(|x| bounds.min_x + x)
+        .collect();
+    let zs: Vec<u32> = coarse_offsets(size_z, step.max(1))
+        .into_iter()
+        .map(|z| bounds.min_z + z)
+        .collect();
+
+    // Too small to decimate: the full grid is already minimal
+    if step <= 1 || xs.len() < 4 || zs.len() < 4 {
+        let full_xs: Vec<u32> = (bounds.min_x..=bounds.max_x).collect();
+        let full_zs: Vec<u32> = (bounds.min_z..=bounds.max_z).collect();
+        builder.grid(&full_xs, &full_zs);
+        return builder.build();
+    }
+
+    // Coarse interior inside the first and last sample rows
+    let inner_xs = &xs[1..xs.len() - 1];
+    let inner_zs = &zs[1..zs.len() - 1];
+    builder.grid(inner_xs, inner_zs);
+
+    let (x0, x1) = (inner_xs[0], inner_xs[inner_xs.len() - 1]);
+    let (z0, z1) = (inner_zs[0], inner_zs[inner_zs.len() - 1]);
+    let by_x: fn((u32, u32)) -> u32 = |p| p.0;
+    let by_z: fn((u32, u32)) -> u32 = |p| p.1;
+
+    // Each side runs corner to corner on the border and inner corner to inner
+    // corner on the coarse ring; sample positions drive the zipper
+    let bottom: Vec<_> = (bounds.min_x..=bounds.max_x)
+        .map(|x| (x, bounds.min_z))
+        .collect();
+    let top: Vec<_> = (bounds.min_x..=bounds.max_x)
+        .map(|x| (x, bounds.max_z))
+        .collect();
+    let left: Vec<_> = (bounds.min_z..=bounds.max_z)
+        .map(|z| (bounds.min_x, z))
+        .collect();
+    let right: Vec<_> = (bounds.min_z..=bounds.max_z)
+        .map(|z| (bounds.max_x, z))
+        .collect();
+    let inner_bottom: Vec<_> = inner_xs.iter().map(|&x| (x, z0)).collect();
+    let inner_top: Vec<_> = inner_xs.iter().map(|&x| (x, z1)).collect();
+    let inner_left: Vec<_> = inner_zs.iter().map(|&z| (x0, z)).collect();
+    let inner_right: Vec<_> = inner_zs.iter().map(|&z| (x1, z)).collect();
+
+    builder.stitch(&bottom, &inner_bottom, by_x);
+    builder.stitch(&top, &inner_top, by_x);
+    builder.stitch(&left, &inner_left, by_z);
+    builder.stitch(&right, &inner_right, by_z);
+
+    builder.build()
+}
+
+/// Full resolution trimesh collider for one chunk
+pub fn generate_chunk_collider(
+    terrain: &TerrainData,
+    bounds: ChunkBounds,
+) -> MinionResult<Collider> {
+    let width = terrain.width as usize;
+    let center_x_offset = terrain.width as f32 * terrain.scale / 2.0;
+    let center_z_offset = terrain.height as f32 * terrain.scale / 2.0;
+    let columns = bounds.max_x - bounds.min_x + 1;
+
+    let mut vertices = Vec::new();
+    for z in bounds.min_z..=bounds.max_z {
+        for x in bounds.min_x..=bounds.max_x {
+            vertices.push(Vec3::new(
+                x as f32 * terrain.scale - center_x_offset,
+                terrain.heights[z as usize * width + x as usize],
+                z as f32 * terrain.scale - center_z_offset,
+            ));
+        }
+    }
+
+    let mut indices = Vec::new();
+    for z in 0..bounds.max_z - bounds.min_z {
+        for x in 0..columns - 1 {
+            let i0 = z * columns + x;
+            let i1 = i0 + 1;
+            let i2 = i0 + columns;
+            let i3 = i2 + 1;
+            indices.push([i0, i2, i1]);
+            indices.push([i1, i2, i3]);
+        }
+    }
+
+    Collider::trimesh(vertices, indices).map_err(|e| MinionError::InvalidMapData {
+        reason: format!("Failed to create terrain chunk collider: {e}"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::render::mesh::VertexAttributeValues;
+
+    fn positions(mesh: &Mesh) -> Vec<[f32; 3]> {
+        match mesh.attribute(Mesh::ATTRIBUTE_POSITION) {
+            Some(VertexAttributeValues::Float32x3(values)) => values.clone(),
+            _ => panic!("Chunk mesh should have positions"),
+        }
+    }
+
+    fn triangle_area(mesh: &Mesh) -> f32 {
+        let positions = positions(mesh);
+        let Some(Indices::U32(indices)) = mesh.indices() else {
+            panic!("Chunk mesh should have u32 indices");
+        };
+        indices
+            .chunks(3)
+            .map(|t| {
+                let a = Vec3::from(positions[t[0] as usize]).with_y(0.0);
+                let b = Vec3::from(positions[t[1] as usize]).with_y(0.0);
+                let c = Vec3::from(positions[t[2] as usize]).with_y(0.0);
+                let normal = (b - a).cross(c - a);
+                // Every triangle faces up
+                assert!(normal.y > 0.0);
+                normal.length() / 2.0
+            })
+            .sum()
+    }
+
+    #[test]
+    fn test_chunk_layout_covers_terrain() {
+        let terrain = TerrainData::create_flat(130, 65, 1.0, 0.0).unwrap();
+        let chunks = chunk_layout(&terrain, 64);
+
+        // 129 cells wide needs three chunks, 64 cells deep needs one
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[0].max_x, 64);
+        assert_eq!(chunks[1].min_x, 64);
+        assert_eq!(chunks[2].max_x, 129);
+        assert!(chunks.iter().all(|c| c.max_z == 64));
+    }
+
+    #[test]
+    fn test_lod_meshes_decimate_and_cover_chunk() {
+        let heights = (0..33 * 33).map(|i| (i as f32 * 0.37).sin()).collect();
+        let terrain = TerrainData::new(33, 33, heights, 1.0).unwrap();
+        let bounds = chunk_layout(&terrain, 32)[0];
+
+        let high = generate_chunk_mesh(&terrain, bounds, 1, None);
+        let low = generate_chunk_mesh(&terrain, bounds, 8, None);
+
+        assert_eq!(high.count_vertices(), 33 * 33);
+        assert!(low.count_vertices() < high.count_vertices() / 4);
+        // No holes or overlaps: both tile the whole 32x32 chunk
+        assert!((triangle_area(&high) - 32.0 * 32.0).abs() < 1e-3);
+        assert!((triangle_area(&low) - 32.0 * 32.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_chunk_borders_match_across_lods() {
+        let heights = (0..33 * 17).map(|i| (i as f32 * 0.21).cos()).collect();
+        let terrain = TerrainData::new(33, 17, heights, 0.5).unwrap();
+        let chunks = chunk_layout(&terrain, 16);
+        assert_eq!(chunks.len(), 2);
+
+        // Left chunk at full detail, right chunk coarse
+        let left = positions(&generate_chunk_mesh(&terrain, chunks[0], 1, None));
+        let right = positions(&generate_chunk_mesh(&terrain, chunks[1], 4, None));
+
+        let seam_x = chunks[0].max_x as f32 * 0.5 - 33.0 * 0.5 / 2.0;
+        let on_seam = |positions: &[[f32; 3]]| {
+            let mut seam: Vec<[u32; 2]> = positions
+                .iter()
+                .filter(|p| (p[0] - seam_x).abs() < 1e-5)
+                .map(|p| [p[1].to_bits(), p[2].to_bits()])
+                .collect();
+            seam.sort();
+            seam
+        };
+        assert_eq!(on_seam(&left).len(), 17);
+        assert_eq!(on_seam(&left), on_seam(&right));
+    }
+
+    #[test]
+    fn test_lod_selection() {
+        let config = TerrainChunkConfig::default();
+        assert_eq!(config.level_at(10.0), LodLevel::High);
+        assert_eq!(config.level_at(60.0), LodLevel::Medium);
+        assert_eq!(config.level_at(500.0), LodLevel::Low);
+        assert_eq!(config.step_for(LodLevel::Low), 16);
+    }
+}
@@ -0,0 +1,225 @@
+//! Temperature and humidity maps for climate-driven biome assignment.
+//!
+//! Both fields start as seeded noise. Temperature then falls with altitude
+//! and humidity rises near water. Biomes are picked Whittaker-style by
+//! comparing each sample's climate with the `BiomeConfig` climate values.
+
+use super::biomes::{BiomeBlend, BiomeConfig, BiomeType};
+use super::constants::FALLBACK_BIOME_SUITABILITY;
+use super::coordinates::distance_to_samples;
+use crate::map::{TerrainData, WaterBody, water_depth_map};
+use crate::terrain_generation::par_rows;
+use noise::{NoiseFn, Perlin};
+use std::collections::HashMap;
+
+/// Tuning for the climate layer
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClimateConfig {
+    /// World units spanned by one climate noise feature
+    pub feature_size: f32,
+    /// Noise octaves layered into each field
+    pub octaves: u32,
+    /// Temperature lost per world unit of height above zero
+    pub lapse_rate: f32,
+    /// Humidity added on the shoreline, fading out over `water_reach`
+    pub water_humidity: f32,
+    /// World distance from water over which the humidity bonus fades
+    pub water_reach: f32,
+    /// Width of the climate-space falloff used when blending biomes
+    pub blend_width: f32,
+}
+
+impl Default for ClimateConfig {
+    fn default() -> Self {
+        Self {
+            feature_size: 80.0,
+            octaves: 3,
+            lapse_rate: 0.02, // 50 units of height cools by a full step
+            water_humidity: 0.6,
+            water_reach: 20.0,
+            blend_width: 0.25,
+        }
+    }
+}
+
+/// Per-sample temperature and humidity, both in -1.0..=1.0
+#[derive(Debug, Clone)]
+pub struct ClimateMap {
+    pub width: u32,
+    pub height: u32,
+    pub temperature: Vec<f32>, // Flattened 2D array (row-major)
+    pub humidity: Vec<f32>,
+}
+
+impl ClimateMap {
+    /// Build the climate for `terrain`, moistening the land around `water_bodies`
+    pub fn generate(
+        terrain: &TerrainData,
+        water_bodies: &[WaterBody],
+        config: &ClimateConfig,
+        seed: u32,
+    ) -> Self {
+        let temperature_noise = Perlin::new(seed);
+        let humidity_noise = Perlin::new(seed.wrapping_add(1));
+        let water_distance = water_distance_map(terrain, water_bodies);
+        let frequency = 1.0 / config.feature_size.max(f32::EPSILON) as f64;
+
+        let samples = par_rows(terrain.height, |z| {
+            (0..terrain.width)
+                .map(|x| {
+                    let index = (z * terrain.width + x) as usize;
+                    let point = [
+                        x as f64 * terrain.scale as f64 * frequency,
+                        z as f64 * terrain.scale as f64 * frequency,
+                    ];
+
+                    let altitude = terrain.heights[index].max(0.0);
+                    let t = octave_noise(&temperature_noise, point, config.octaves)
+                        - config.lapse_rate * altitude;
+
+                    let shore = 1.0 - water_distance[index] / config.water_reach.max(f32::EPSILON);
+                    let h = octave_noise(&humidity_noise, point, config.octaves)
+                        + config.water_humidity * shore.clamp(0.0, 1.0);
+
+                    (t.clamp(-1.0, 1.0), h.clamp(-1.0, 1.0))
+                })
+                .collect()
+        });
+        let (temperature, humidity) = samples.into_iter().unzip();
+
+        Self {
+            width: terrain.width,
+            height: terrain.height,
+            temperature,
+            humidity,
+        }
+    }
+
+    /// Temperature and humidity at grid position
+    pub fn get(&self, x: u32, z: u32) -> Option<(f32, f32)> {
+        if x >= self.width || z >= self.height {
+            return None;
+        }
+        let index = (z * self.width + x) as usize;
+        Some((self.temperature[index], self.humidity[index]))
+    }
+}
+
+/// Fractal noise in roughly -1.0..=1.0
+fn octave_noise(noise: &Perlin, point: [f64; 2], octaves: u32) -> f32 {
+    let mut value = 0.0;
+    let mut amplitude = 1.0;
+    let mut frequency = 1.0;
+    let mut total = 0.0;
+    for _ in 0..octaves.max(1) {
+        value += noise.get([point[0] * frequency, point[1] * frequency]) * amplitude;
+        total += amplitude;
+        amplitude *= 0.5;
+        frequency *= 2.0;
+    }
+    // Perlin rarely leaves +-0.7, so stretch it to use the full range
+    (value / total * 1.4) as f32
+}
+
+/// World distance from each sample to the nearest water, `f32::MAX` without water
+fn water_distance_map(terrain: &TerrainData, water_bodies: &[WaterBody]) -> Vec<f32> {
+    let wet: Vec<bool> = water_depth_map(terrain, water_bodies)
+        .iter()
+        .map(|&depth| depth > 0.0)
+        .collect();
+    distance_to_samples(terrain, &wet)
+}
+
+/// Whittaker-style lookup: weight biomes by closeness in climate space,
+/// scaled by how well the terrain suits them
+pub fn climate_blend(
+    temperature: f32,
+    humidity: f32,
+    elevation: f32,
+    slope: f32,
+    biome_configs: &HashMap<BiomeType, BiomeConfig>,
+    config: &ClimateConfig,
+) -> BiomeBlend {
+    let width = config.blend_width.max(f32::EPSILON);
+    let mut weights: Vec<(BiomeType, f32)> = biome_configs
+        .values()
+        .map(|biome| {
+            let distance_squared =
+                (temperature - biome.temperature).powi(2) + (humidity - biome.humidity).powi(2);
+            let closeness = (-distance_squared / (width * width)).exp();
+            let suitability = biome
+                .is_suitable(elevation, slope)
+                .max(FALLBACK_BIOME_SUITABILITY);
+            (biome.biome_type, closeness * suitability)
+        })
+        .collect();
+
+    // HashMap order is random, keep ties deterministic
+    weights.sort_by_key(|(biome_type, _)| *biome_type as u8);
+
+    let blend = BiomeBlend::from_weights(weights);
+    if blend.weights.is_empty() {
+        BiomeBlend::single(BiomeType::Plains)
+    } else {
+        blend
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map::WaterKind;
+    use crate::terrain::biomes::create_default_biomes;
+
+    #[test]
+    fn test_lapse_rate_cools_high_ground() {
+        // Left half at sea level, right half a mountain
+        let heights = (0..16 * 16)
+            .map(|i| if i % 16 < 8 { 0.0 } else { 60.0 })
+            .collect();
+        let terrain = TerrainData::new(16, 16, heights, 1.0).unwrap();
+        let climate = ClimateMap::generate(&terrain, &[], &ClimateConfig::default(), 7);
+
+        let mean = |range: std::ops::Range<u32>| {
+            let mut sum = 0.0;
+            for z in 0..16 {
+                for x in range.clone() {
+                    sum += climate.get(x, z).unwrap().0;
+                }
+            }
+            sum / (16 * range.len()) as f32
+        };
+        assert!(mean(8..16) < mean(0..8) - 0.5);
+    }
+
+    #[test]
+    fn test_water_raises_humidity() {
+        let terrain = TerrainData::create_flat(32, 1, 1.0, 0.0).unwrap();
+        let mut lake = WaterBody::new(WaterKind::Lake);
+        lake.push(0, 1.0);
+        let config = ClimateConfig {
+            water_reach: 10.0,
+            ..Default::default()
+        };
+
+        let dry = ClimateMap::generate(&terrain, &[], &config, 3);
+        let wet = ClimateMap::generate(&terrain, &[lake], &config, 3);
+
+        assert!(wet.humidity[1] > dry.humidity[1]);
+        // Beyond the reach the lake has no effect
+        assert_eq!(wet.humidity[20], dry.humidity[20]);
+        assert_eq!(wet.temperature, dry.temperature);
+    }
+
+    #[test]
+    fn test_climate_blend_matches_biome_climate() {
+        let biomes = create_default_biomes();
+        let config = ClimateConfig::default();
+
+        let hot_dry = climate_blend(0.7, -0.8, 0.1, 0.0, &biomes, &config);
+        assert_eq!(hot_dry.dominant_biome(), Some(BiomeType::Desert));
+
+        let cold = climate_blend(-0.8, -0.3, 0.5, 0.0, &biomes, &config);
+        assert_eq!(cold.dominant_biome(), Some(BiomeType::Tundra));
+    }
+}
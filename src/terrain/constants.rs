@@ -24,8 +24,9 @@ pub const FALLBACK_TERRAIN_HEIGHT: f32 = 0.0;
 pub const FALLBACK_BIOME_SUITABILITY: f32 = 0.1;
 
 /// Object placement constants
-pub const MAX_OBJECT_PLACEMENT_ATTEMPTS: u32 = 10;
+pub const POISSON_CANDIDATE_ATTEMPTS: u32 = 30; // Bridson's k
 pub const DEFAULT_OBJECT_DENSITY: f32 = 0.1;
+pub const SCATTER_CANDIDATES_PER_TASK: usize = 256; // Candidates checked per parallel task
 
 /// Coordinate transformation constants
 pub const GRID_INTERPOLATION_MARGIN: f32 = 1.0;
@@ -159,3 +159,50 @@ pub fn get_height_at_world_nearest(
     let z = grid_z.round() as u32;
     get_height_at_grid(terrain, x, z)
 }
+
+/// World distance from each heightmap sample to the nearest `sources` sample,
+/// `f32::MAX` when there are no sources
+pub fn distance_to_samples(terrain: &TerrainData, sources: &[bool]) -> Vec<f32> {
+    let (width, height) = (terrain.width as usize, terrain.height as usize);
+    let mut distance: Vec<f32> = sources
+        .iter()
+        .map(|&source| if source { 0.0 } else { f32::MAX })
+        .collect();
+
+    // Two-pass chamfer transform with 8-connected steps
+    let diagonal = std::f32::consts::SQRT_2;
+    let relax = |distance: &mut Vec<f32>, x: usize, z: usize, dx: isize, dz: isize| {
+        let (Some(nx), Some(nz)) = (x.checked_add_signed(dx), z.checked_add_signed(dz)) else {
+            return;
+        };
+        if nx >= width || nz >= height {
+            return;
+        }
+        let step = if dx != 0 && dz != 0 { diagonal } else { 1.0 };
+        let candidate = distance[nz * width + nx] + step;
+        let current = &mut distance[z * width + x];
+        if candidate < *current {
+            *current = candidate;
+        }
+    };
+
+    for z in 0..height {
+        for x in 0..width {
+            for (dx, dz) in [(-1, 0), (-1, -1), (0, -1), (1, -1)] {
+                relax(&mut distance, x, z, dx, dz);
+            }
+        }
+    }
+    for z in (0..height).rev() {
+        for x in (0..width).rev() {
+            for (dx, dz) in [(1, 0), (1, 1), (0, 1), (-1, 1)] {
+                relax(&mut distance, x, z, dx, dz);
+            }
+        }
+    }
+
+    distance
+        .into_iter()
+        .map(|d| if d == f32::MAX { d } else { d * terrain.scale })
+        .collect()
+}
@@ -1,5 +1,5 @@
 use crate::game_logic::errors::MinionResult;
-use crate::map::TerrainData;
+use crate::map::{TerrainData, WaterBody, water_surface_map};
 use bevy::prelude::*;
 use bevy::render::mesh::{Indices, PrimitiveTopology};
 use bevy_rapier3d::prelude::*;
@@ -7,9 +7,15 @@ use bevy_rapier3d::prelude::*;
 pub mod biome_generator;
 pub mod biome_integration;
 pub mod biomes;
+pub mod chunks;
+pub mod climate;
 pub mod constants;
 pub mod coordinates;
 pub mod path_generator;
+pub mod road_carving;
+pub mod scatter;
+pub mod settlements;
+pub mod surface;
 
 /// Generate a 3D mesh from heightmap terrain data
 pub fn generate_terrain_mesh(terrain: &TerrainData) -> MinionResult<Mesh> {
@@ -178,11 +184,46 @@ pub fn generate_terrain_collider(terrain: &TerrainData) -> MinionResult<Collider
     Ok(collider)
 }
 
-/// Generate both visual mesh and physics collider from terrain data
-pub fn generate_terrain_mesh_and_collider(terrain: &TerrainData) -> MinionResult<(Mesh, Collider)> {
-    let mesh = generate_terrain_mesh(terrain)?;
-    let collider = generate_terrain_collider(terrain)?;
-    Ok((mesh, collider))
+/// Generate a flat-shaded water mesh with one quad per wet heightmap sample
+pub fn generate_water_mesh(terrain: &TerrainData, bodies: &[WaterBody]) -> Option<Mesh> {
+    let surfaces = water_surface_map(terrain, bodies);
+    let width = terrain.width as usize;
+    let scale = terrain.scale;
+    let center_x_offset = (terrain.width as f32 * scale) / 2.0;
+    let center_z_offset = (terrain.height as f32 * scale) / 2.0;
+
+    let mut vertices = Vec::new();
+    let mut indices = Vec::new();
+    for (index, surface) in surfaces.iter().enumerate() {
+        let Some(surface) = *surface else {
+            continue;
+        };
+        let world_x = (index % width) as f32 * scale - center_x_offset;
+        let world_z = (index / width) as f32 * scale - center_z_offset;
+        let half = scale / 2.0;
+
+        // Quad centered on the sample, wound counter-clockwise from above
+        let base = vertices.len() as u32;
+        vertices.push([world_x - half, surface, world_z - half]);
+        vertices.push([world_x + half, surface, world_z - half]);
+        vertices.push([world_x - half, surface, world_z + half]);
+        vertices.push([world_x + half, surface, world_z + half]);
+        indices.extend([base, base + 2, base + 1, base + 1, base + 2, base + 3]);
+    }
+
+    if vertices.is_empty() {
+        return None;
+    }
+
+    let normals = vec![[0.0, 1.0, 0.0]; vertices.len()];
+    let mut mesh = Mesh::new(
+        PrimitiveTopology::TriangleList,
+        bevy::render::render_asset::RenderAssetUsages::RENDER_WORLD,
+    );
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, vertices);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    mesh.insert_indices(Indices::U32(indices));
+    Some(mesh)
 }
 
 #[cfg(test)]
@@ -211,18 +252,17 @@ mod tests {
     }
 
     #[test]
-    fn test_terrain_mesh_and_collider_generation() {
-        let terrain = TerrainData::create_flat(4, 4, 2.0, 1.5).unwrap();
-        let result = generate_terrain_mesh_and_collider(&terrain);
-        assert!(result.is_ok());
-
-        let (mesh, _collider) = result.unwrap();
-
-        // Verify mesh has required attributes
-        assert!(mesh.attribute(Mesh::ATTRIBUTE_POSITION).is_some());
-        assert!(mesh.attribute(Mesh::ATTRIBUTE_NORMAL).is_some());
-        assert!(mesh.attribute(Mesh::ATTRIBUTE_UV_0).is_some());
-        assert!(mesh.indices().is_some());
+    fn test_water_mesh_generation() {
+        use crate::map::WaterKind;
+
+        let terrain = TerrainData::create_flat(4, 4, 1.0, 0.0).unwrap();
+        assert!(generate_water_mesh(&terrain, &[]).is_none());
+
+        let mut lake = WaterBody::new(WaterKind::Lake);
+        lake.push(5, 0.5);
+        lake.push(6, 0.5);
+        let mesh = generate_water_mesh(&terrain, &[lake]).unwrap();
+        assert_eq!(mesh.count_vertices(), 8);
     }
 
     #[test]
@@ -7,7 +7,7 @@ use pathfinding::prelude::astar;
 use rand::{Rng, SeedableRng};
 use rand_pcg::Pcg64;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PathPoint {
@@ -314,7 +314,7 @@ impl PathGenerator {
 
     fn find_biome_centers(&self, biomes: &BiomeData) -> Vec<(u32, u32)> {
         let mut centers = Vec::new();
-        let mut biome_points: HashMap<BiomeType, Vec<(u32, u32)>> = HashMap::new();
+        let mut biome_points: BTreeMap<BiomeType, Vec<(u32, u32)>> = BTreeMap::new();
 
         // Collect all points for each biome type
         for (x, row) in biomes.biome_map.iter().enumerate() {
@@ -340,8 +340,8 @@ impl PathGenerator {
         centers
     }
 
-    fn get_biome_regions(&self, biomes: &BiomeData) -> HashMap<BiomeType, Vec<(u32, u32)>> {
-        let mut regions: HashMap<BiomeType, Vec<(u32, u32)>> = HashMap::new();
+    fn get_biome_regions(&self, biomes: &BiomeData) -> BTreeMap<BiomeType, Vec<(u32, u32)>> {
+        let mut regions: BTreeMap<BiomeType, Vec<(u32, u32)>> = BTreeMap::new();
 
         for (x, row) in biomes.biome_map.iter().enumerate() {
             for (z, &biome_type) in row.iter().enumerate() {
@@ -357,7 +357,7 @@ impl PathGenerator {
 
     fn find_path_junctions(&self, paths: &[Path]) -> Vec<PathPoint> {
         let mut junctions = Vec::new();
-        let mut point_counts: HashMap<(u32, u32), u32> = HashMap::new();
+        let mut point_counts: BTreeMap<(u32, u32), u32> = BTreeMap::new();
 
         // Count how many paths pass through each point
         for path in paths {
@@ -0,0 +1,214 @@
+//! Flatten roads and trails into the heightmap.
+//!
+//! Each path gets a smoothed longitudinal height profile, its corridor is
+//! leveled to that profile across the path's full width, and a shoulder
+//! blends the corridor back into the surrounding terrain.
+
+use crate::map::{RoadSurface, TerrainData};
+use crate::terrain::path_generator::{Path, PathNetwork};
+
+/// Tuning for road carving
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoadCarvingConfig {
+    /// Path points averaged on each side when smoothing the height profile
+    pub profile_window: usize,
+    /// Width in world units over which the road edge blends into the terrain
+    pub shoulder: f32,
+}
+
+impl Default for RoadCarvingConfig {
+    fn default() -> Self {
+        Self {
+            profile_window: 6, // Irons out single-cell bumps without cutting hills flat
+            shoulder: 1.5,
+        }
+    }
+}
+
+/// Carve every path in `network`, widest last so main roads win at junctions
+pub fn carve_path_network(
+    terrain: &mut TerrainData,
+    network: &PathNetwork,
+    config: &RoadCarvingConfig,
+) -> Vec<RoadSurface> {
+    let mut paths: Vec<&Path> = network.paths.iter().collect();
+    paths.sort_by(|a, b| a.width.total_cmp(&b.width));
+
+    paths
+        .into_iter()
+        .filter_map(|path| carve_path(terrain, path, config))
+        .collect()
+}
+
+/// Moving average of the terrain height under each path point
+fn smoothed_profile(terrain: &TerrainData, path: &Path, window: usize) -> Vec<f32> {
+    let raw: Vec<f32> = path
+        .points
+        .iter()
+        .map(|p| terrain.heights[(p.z * terrain.width + p.x) as usize])
+        .collect();
+
+    (0..raw.len())
+        .map(|i| {
+            let start = i.saturating_sub(window);
+            let end = (i + window + 1).min(raw.len());
+            raw[start..end].iter().sum::<f32>() / (end - start) as f32
+        })
+        .collect()
+}
+
+/// Distance from `point` to segment `a`-`b` and the position along it (0-1)
+fn segment_distance(point: (f32, f32), a: (f32, f32), b: (f32, f32)) -> (f32, f32) {
+    let (dx, dz) = (b.0 - a.0, b.1 - a.1);
+    let length_squared = dx * dx + dz * dz;
+    let t = if length_squared > 0.0 {
+        (((point.0 - a.0) * dx + (point.1 - a.1) * dz) / length_squared).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let (px, pz) = (a.0 + dx * t, a.1 + dz * t);
+    (((point.0 - px).powi(2) + (point.1 - pz).powi(2)).sqrt(), t)
+}
+
+/// Level one path's corridor, returning the cells it paved
+fn carve_path(
+    terrain: &mut TerrainData,
+    path: &Path,
+    config: &RoadCarvingConfig,
+) -> Option<RoadSurface> {
+    if path.points.is_empty()
+        || path
+            .points
+            .iter()
+            .any(|p| p.x >= terrain.width || p.z >= terrain.height)
+    {
+        return None;
+    }
+
+    let profile = smoothed_profile(terrain, path, config.profile_window);
+    let half_width = path.width / 2.0 / terrain.scale;
+    let shoulder = config.shoulder / terrain.scale;
+    let reach = half_width + shoulder;
+
+    // Nearest distance to the path centerline and the profile height there
+    let mut nearest = vec![(f32::MAX, 0.0f32); terrain.heights.len()];
+    let points: Vec<(f32, f32)> = path
+        .points
+        .iter()
+        .map(|p| (p.x as f32, p.z as f32))
+        .collect();
+    let segments: Vec<usize> = if points.len() == 1 {
+        vec![0]
+    } else {
+        (0..points.len() - 1).collect()
+    };
+
+    for i in segments {
+        let j = (i + 1).min(points.len() - 1);
+        let (a, b) = (points[i], points[j]);
+        let min_x = (a.0.min(b.0) - reach).floor().max(0.0) as u32;
+        let max_x = (a.0.max(b.0) + reach)
+            .ceil()
+            .min((terrain.width - 1) as f32) as u32;
+        let min_z = (a.1.min(b.1) - reach).floor().max(0.0) as u32;
+        let max_z = (a.1.max(b.1) + reach)
+            .ceil()
+            .min((terrain.height - 1) as f32) as u32;
+
+        for z in min_z..=max_z {
+            for x in min_x..=max_x {
+                let (distance, t) = segment_distance((x as f32, z as f32), a, b);
+                let index = (z * terrain.width + x) as usize;
+                if distance <= reach && distance < nearest[index].0 {
+                    let target = profile[i] + (profile[j] - profile[i]) * t;
+                    nearest[index] = (distance, target);
+                }
+            }
+        }
+    }
+
+    let mut cells = Vec::new();
+    for (index, &(distance, target)) in nearest.iter().enumerate() {
+        if distance <= half_width {
+            terrain.heights[index] = target;
+            cells.push(index as u32);
+        } else if distance <= reach {
+            // Smoothstep from the road edge out to untouched terrain
+            let t = 1.0 - (distance - half_width) / shoulder.max(f32::EPSILON);
+            let weight = t * t * (3.0 - 2.0 * t);
+            let height = &mut terrain.heights[index];
+            *height += (target - *height) * weight;
+        }
+    }
+
+    Some(RoadSurface {
+        path_type: path.path_type,
+        cells,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::terrain::path_generator::{PathPoint, PathType};
+
+    /// Straight road along row 8 of a bumpy 17x17 terrain
+    fn bumpy_road() -> (TerrainData, PathNetwork) {
+        let heights = (0..17 * 17)
+            .map(|i| ((i % 17) as f32 * 1.3).sin() + (i / 17) as f32 * 0.2)
+            .collect();
+        let terrain = TerrainData::new(17, 17, heights, 1.0).unwrap();
+        let points = (2..15)
+            .map(|x| PathPoint {
+                x,
+                z: 8,
+                elevation: 0.0,
+            })
+            .collect();
+        let network = PathNetwork {
+            paths: vec![Path {
+                points,
+                path_type: PathType::MainRoad,
+                width: 3.0,
+            }],
+            junctions: vec![],
+        };
+        (terrain, network)
+    }
+
+    #[test]
+    fn test_road_is_level_across_its_width() {
+        let (mut terrain, network) = bumpy_road();
+        let roads = carve_path_network(&mut terrain, &network, &RoadCarvingConfig::default());
+
+        assert_eq!(roads.len(), 1);
+        assert_eq!(roads[0].path_type, PathType::MainRoad);
+        for x in 2..15 {
+            let center = terrain.heights[8 * 17 + x];
+            assert!((terrain.heights[7 * 17 + x] - center).abs() < 1e-5);
+            assert!((terrain.heights[9 * 17 + x] - center).abs() < 1e-5);
+            assert!(roads[0].cells.contains(&((8 * 17 + x) as u32)));
+        }
+    }
+
+    #[test]
+    fn test_road_profile_is_smoother_than_terrain() {
+        let (original, network) = bumpy_road();
+        let mut terrain = original.clone();
+        carve_path_network(&mut terrain, &network, &RoadCarvingConfig::default());
+
+        let roughness = |t: &TerrainData| {
+            (2..14)
+                .map(|x| (t.heights[8 * 17 + x + 1] - t.heights[8 * 17 + x]).abs())
+                .sum::<f32>()
+        };
+        assert!(roughness(&terrain) < roughness(&original) * 0.5);
+
+        // Far from the road nothing changes
+        assert_eq!(terrain.heights[0], original.heights[0]);
+        assert_eq!(
+            terrain.heights[16 * 17 + 16],
+            original.heights[16 * 17 + 16]
+        );
+    }
+}
@@ -0,0 +1,474 @@
+//! Seeded Poisson-disk scattering of environment objects.
+//!
+//! Bridson sampling produces evenly spread candidate points at the smallest
+//! spacing any rule needs. Each candidate then picks an object type from the
+//! rules active there, weighted by density and a clustering noise field, and
+//! is kept only if its footprint clears every object already placed. Rule
+//! choice and terrain checks run in parallel; only the footprint checks,
+//! which depend on earlier placements, run in candidate order.
+
+use super::biomes::{BiomeConfig, BiomeMap, BiomeType, ObjectRule};
+use super::constants::{POISSON_CANDIDATE_ATTEMPTS, SCATTER_CANDIDATES_PER_TASK};
+use crate::map::{EnvironmentObject, TerrainData};
+use crate::terrain::coordinates::get_height_at_world_interpolated;
+use crate::terrain_generation::{is_suitable_for_spawning, par_map};
+use bevy::prelude::*;
+use noise::{NoiseFn, Perlin};
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_pcg::Pcg64;
+use std::collections::HashMap;
+
+/// Where object rules come from
+#[derive(Debug, Clone, Copy)]
+pub enum ScatterRules<'a> {
+    /// Rules of the biomes blended at each point, weighted by the blend
+    Biomes {
+        map: &'a BiomeMap,
+        configs: &'a HashMap<BiomeType, BiomeConfig>,
+    },
+    /// The same rules everywhere
+    Uniform(&'a [ObjectRule]),
+}
+
+/// Settings for one scattering pass
+#[derive(Debug, Clone)]
+pub struct ScatterConfig {
+    pub seed: u64,
+    /// Scales every rule's density
+    pub density_multiplier: f32,
+    /// Only place these object types; every rule applies when `None`
+    pub object_types: Option<Vec<String>>,
+    /// World positions and radii kept clear of objects
+    pub exclusions: Vec<(Vec3, f32)>,
+    /// Stop after placing this many objects
+    pub max_objects: Option<usize>,
+}
+
+impl Default for ScatterConfig {
+    fn default() -> Self {
+        Self {
+            seed: 42,
+            density_multiplier: 1.0,
+            object_types: None,
+            exclusions: Vec::new(),
+            max_objects: None,
+        }
+    }
+}
+
+impl ScatterConfig {
+    fn allows(&self, rule: &ObjectRule) -> bool {
+        self.object_types
+            .as_ref()
+            .is_none_or(|types| types.contains(&rule.object_type))
+    }
+}
+
+/// Evenly spread points at least `radius` apart inside `min`..`max` (Bridson)
+pub fn poisson_disk_samples(min: Vec2, max: Vec2, radius: f32, rng: &mut Pcg64) -> Vec<Vec2> {
+    let size = max - min;
+    if radius <= 0.0 || size.x <= 0.0 || size.y <= 0.0 {
+        return Vec::new();
+    }
+
+    let cell = radius / std::f32::consts::SQRT_2;
+    let columns = (size.x / cell).ceil() as usize + 1;
+    let rows = (size.y / cell).ceil() as usize + 1;
+    let mut grid = vec![usize::MAX; columns * rows];
+    let cell_of = |p: Vec2| {
+        let local = (p - min) / cell;
+        (local.x as usize, local.y as usize)
+    };
+
+    let first = Vec2::new(rng.gen_range(min.x..max.x), rng.gen_range(min.y..max.y));
+    let mut samples = vec![first];
+    let mut active = vec![0];
+    let (cx, cz) = cell_of(first);
+    grid[cz * columns + cx] = 0;
+
+    while !active.is_empty() {
+        let slot = rng.gen_range(0..active.len());
+        let origin = samples[active[slot]];
+        let mut found = false;
+
+        for _ in 0..POISSON_CANDIDATE_ATTEMPTS {
+            let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+            let distance = rng.gen_range(radius..2.0 * radius);
+            let candidate = origin + Vec2::new(angle.cos(), angle.sin()) * distance;
+            if candidate.x < min.x
+                || candidate.y < min.y
+                || candidate.x >= max.x
+                || candidate.y >= max.y
+            {
+                continue;
+            }
+
+            // Anything closer than `radius` lies within two cells
+            let (cx, cz) = cell_of(candidate);
+            let clear = (cz.saturating_sub(2)..(cz + 3).min(rows)).all(|z| {
+                (cx.saturating_sub(2)..(cx + 3).min(columns)).all(|x| {
+                    let index = grid[z * columns + x];
+                    index == usize::MAX || samples[index].distance(candidate) >= radius
+                })
+            });
+            if clear {
+                grid[cz * columns + cx] = samples.len();
+                active.push(samples.len());
+                samples.push(candidate);
+                found = true;
+                break;
+            }
+        }
+
+        if !found {
+            active.swap_remove(slot);
+        }
+    }
+
+    samples
+}
+
+/// Stable hash so each object type gets its own clustering noise
+fn type_seed(object_type: &str) -> u32 {
+    object_type.bytes().fold(0x811c_9dc5_u32, |hash, byte| {
+        (hash ^ byte as u32).wrapping_mul(0x0100_0193)
+    })
+}
+
+/// Density multiplier from clustering: averages about 1.0, zero between groups
+fn cluster_factor(rule: &ObjectRule, noise: &Perlin, position: Vec2) -> f32 {
+    if rule.clustering <= 0.0 {
+        return 1.0;
+    }
+    let frequency = 1.0 / rule.cluster_size.max(f32::EPSILON) as f64;
+    let value = noise.get([position.x as f64 * frequency, position.y as f64 * frequency]) as f32;
+    let t = ((value * 1.4 + 1.0) / 2.0 - 0.3) / 0.4;
+    let t = t.clamp(0.0, 1.0);
+    let grouped = 2.0 * t * t * (3.0 - 2.0 * t);
+    let strength = rule.clustering.min(1.0);
+    (1.0 - strength) + strength * grouped
+}
+
+/// Rules active at a world position with their blend-weighted densities
+fn rules_at<'a>(rules: ScatterRules<'a>, x: f32, z: f32) -> Vec<(&'a ObjectRule, f32)> {
+    match rules {
+        ScatterRules::Uniform(rules) => rules.iter().map(|rule| (rule, rule.density)).collect(),
+        ScatterRules::Biomes { map, configs } => map
+            .get_blend_at_world(x, z)
+            .map(|blend| {
+                blend
+                    .weights
+                    .iter()
+                    .filter_map(|(biome_type, weight)| {
+                        configs.get(biome_type).map(|config| (config, *weight))
+                    })
+                    .flat_map(|(config, weight)| {
+                        config
+                            .objects
+                            .iter()
+                            .map(move |rule| (rule, rule.density * weight))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default(),
+    }
+}
+
+/// Every rule that can apply anywhere, used to size the candidate spacing
+fn all_rules<'a>(rules: ScatterRules<'a>) -> Vec<&'a ObjectRule> {
+    match rules {
+        ScatterRules::Uniform(rules) => rules.iter().collect(),
+        ScatterRules::Biomes { configs, .. } => configs
+            .values()
+            .flat_map(|config| &config.objects)
+            .collect(),
+    }
+}
+
+/// A placed position and its footprint radius; no radius mirrors the candidate's
+type Placed = (Vec2, Option<f32>);
+
+/// Objects already placed, bucketed by footprint so overlap checks stay local
+struct Footprints {
+    cell: f32,
+    buckets: HashMap<(i32, i32), Vec<Placed>>,
+}
+
+impl Footprints {
+    fn key(&self, position: Vec2) -> (i32, i32) {
+        (
+            (position.x / self.cell).floor() as i32,
+            (position.y / self.cell).floor() as i32,
+        )
+    }
+
+    /// A footprint of `radius` at `position` overlaps nothing placed so far
+    fn is_clear(&self, position: Vec2, radius: f32) -> bool {
+        let (kx, kz) = self.key(position);
+        (kz - 1..=kz + 1).all(|z| {
+            (kx - 1..=kx + 1).all(|x| {
+                self.buckets.get(&(x, z)).is_none_or(|placed| {
+                    placed.iter().all(|(other, other_radius)| {
+                        other.distance(position) >= radius + other_radius.unwrap_or(radius)
+                    })
+                })
+            })
+        })
+    }
+
+    fn insert(&mut self, position: Vec2, radius: Option<f32>) {
+        let key = self.key(position);
+        self.buckets
+            .entry(key)
+            .or_default()
+            .push((position, radius));
+    }
+}
+
+/// Scatter objects over `terrain`, keeping clear of `existing` ones.
+///
+/// Each object's footprint radius is half its rule's spacing times its scale,
+/// so large boulders push neighbours further away than pebbles do. Objects
+/// in `existing` are given the footprint of the candidate being tested.
+pub fn scatter_objects(
+    terrain: &TerrainData,
+    rules: ScatterRules,
+    config: &ScatterConfig,
+    existing: &[EnvironmentObject],
+) -> Vec<EnvironmentObject> {
+    let active_rules: Vec<&ObjectRule> = all_rules(rules)
+        .into_iter()
+        .filter(|rule| config.allows(rule) && rule.density > 0.0 && rule.spacing > 0.0)
+        .collect();
+    if active_rules.is_empty() || config.density_multiplier <= 0.0 {
+        return Vec::new();
+    }
+
+    let candidate_spacing = active_rules
+        .iter()
+        .map(|rule| rule.spacing * rule.scale_range.0)
+        .fold(f32::MAX, f32::min)
+        .max(0.25);
+    let largest_footprint = active_rules
+        .iter()
+        .map(|rule| rule.spacing * rule.scale_range.1)
+        .fold(0.0, f32::max);
+
+    // Heightmap samples span 0..width-1 cells around the centered origin
+    let half_width = terrain.width as f32 * terrain.scale / 2.0;
+    let half_height = terrain.height as f32 * terrain.scale / 2.0;
+    let min = Vec2::new(-half_width, -half_height);
+    let max = Vec2::new(half_width - terrain.scale, half_height - terrain.scale);
+
+    let mut rng = Pcg64::seed_from_u64(config.seed);
+    let mut candidates = poisson_disk_samples(min, max, candidate_spacing, &mut rng);
+    // Bridson grows outwards from one point, so `max_objects` would fill one corner
+    candidates.shuffle(&mut rng);
+    let area = (max.x - min.x) * (max.y - min.y);
+    if candidates.is_empty() || area <= 0.0 {
+        return Vec::new();
+    }
+    // Scales a rule's density per 100 world units² into a per-candidate chance
+    let per_candidate = area / candidates.len() as f32 / 100.0 * config.density_multiplier;
+
+    // Draw every roll up front so the stream does not depend on outcomes
+    let rolls: Vec<(Vec2, [f32; 3])> = candidates
+        .into_iter()
+        .map(|candidate| {
+            let roll = rng.gen_range(0.0..1.0_f32);
+            let scale_roll = rng.gen_range(0.0..1.0_f32);
+            let rotation = rng.gen_range(0.0..std::f32::consts::TAU);
+            (candidate, [roll, scale_roll, rotation])
+        })
+        .collect();
+
+    let rule_list = all_rules(rules);
+    let noises: HashMap<&str, Perlin> = rule_list
+        .iter()
+        .map(|rule| {
+            let seed = config.seed as u32 ^ type_seed(&rule.object_type);
+            (rule.object_type.as_str(), Perlin::new(seed))
+        })
+        .collect();
+
+    // Chosen rule (as an index into `rule_list`) and ground height per candidate
+    let pick = |(candidate, [roll, ..]): &(Vec2, [f32; 3])| {
+        let position = Vec3::new(candidate.x, 0.0, candidate.y);
+        if config
+            .exclusions
+            .iter()
+            .any(|(center, radius)| center.with_y(0.0).distance(position) < *radius)
+        {
+            return None;
+        }
+
+        let mut cumulative = 0.0;
+        let (rule, _) = rules_at(rules, candidate.x, candidate.y)
+            .into_iter()
+            .filter(|(rule, _)| config.allows(rule))
+            .find(|(rule, density)| {
+                let noise = &noises[rule.object_type.as_str()];
+                cumulative += density * per_candidate * cluster_factor(rule, noise, *candidate);
+                *roll < cumulative
+            })?;
+
+        if !is_suitable_for_spawning(terrain, candidate.x, candidate.y, rule.max_slope) {
+            return None;
+        }
+        let height =
+            get_height_at_world_interpolated(terrain, candidate.x, candidate.y).unwrap_or(0.0);
+        if rule
+            .height_range
+            .is_some_and(|(low, high)| !(low..=high).contains(&height))
+        {
+            return None;
+        }
+
+        let index = rule_list
+            .iter()
+            .position(|known| std::ptr::eq(*known, rule))?;
+        Some((index, height))
+    };
+    let picks = par_map(&rolls, SCATTER_CANDIDATES_PER_TASK, pick);
+
+    let mut footprints = Footprints {
+        cell: largest_footprint.max(candidate_spacing),
+        buckets: HashMap::new(),
+    };
+    for object in existing {
+        footprints.insert(Vec2::new(object.position.x, object.position.z), None);
+    }
+
+    let mut objects = Vec::new();
+    for ((candidate, [_, scale_roll, rotation]), pick) in rolls.into_iter().zip(picks) {
+        if config.max_objects.is_some_and(|max| objects.len() >= max) {
+            break;
+        }
+        let Some((index, height)) = pick else {
+            continue;
+        };
+        let rule = rule_list[index];
+
+        let (low, high) = rule.scale_range;
+        let scale = low + (high - low) * scale_roll;
+        let radius = rule.spacing * scale / 2.0;
+        if !footprints.is_clear(candidate, radius) {
+            continue;
+        }
+
+        footprints.insert(candidate, Some(radius));
+        objects.push(EnvironmentObject::new(
+            rule.object_type.clone(),
+            Vec3::new(candidate.x, height, candidate.y),
+            Vec3::new(0.0, rotation, 0.0),
+            Vec3::splat(scale),
+        ));
+    }
+
+    objects
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::terrain::biomes::create_default_biomes;
+
+    #[test]
+    fn test_poisson_disk_spacing() {
+        let mut rng = Pcg64::seed_from_u64(5);
+        let samples = poisson_disk_samples(Vec2::ZERO, Vec2::new(40.0, 30.0), 2.0, &mut rng);
+
+        // Packs the area without any pair closer than the radius
+        assert!(samples.len() > (40.0 * 30.0 / (4.0 * 4.0)) as usize);
+        for (i, a) in samples.iter().enumerate() {
+            assert!((0.0..40.0).contains(&a.x) && (0.0..30.0).contains(&a.y));
+            for b in &samples[i + 1..] {
+                assert!(a.distance(*b) >= 2.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_scatter_is_seeded_and_respects_footprints() {
+        let terrain = TerrainData::create_flat(64, 64, 1.0, 0.0).unwrap();
+        let rules = [
+            ObjectRule::new("tree", 3.0, 2.0).with_scale(1.0, 2.0),
+            ObjectRule::new("rock", 3.0, 1.0).with_scale(0.5, 1.0),
+        ];
+        let config = ScatterConfig {
+            seed: 9,
+            exclusions: vec![(Vec3::ZERO, 8.0)],
+            ..Default::default()
+        };
+
+        let first = scatter_objects(&terrain, ScatterRules::Uniform(&rules), &config, &[]);
+        let second = scatter_objects(&terrain, ScatterRules::Uniform(&rules), &config, &[]);
+        assert!(first.len() > 50);
+        assert_eq!(
+            first.iter().map(|o| o.position).collect::<Vec<_>>(),
+            second.iter().map(|o| o.position).collect::<Vec<_>>()
+        );
+
+        let footprint = |o: &EnvironmentObject| {
+            let spacing = if o.object_type == "tree" { 2.0 } else { 1.0 };
+            spacing * o.scale.x / 2.0
+        };
+        for (i, a) in first.iter().enumerate() {
+            assert!(a.position.with_y(0.0).length() >= 8.0);
+            for b in &first[i + 1..] {
+                let distance = a.position.with_y(0.0).distance(b.position.with_y(0.0));
+                assert!(distance >= footprint(a) + footprint(b) - 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn test_biome_rules_drive_object_types() {
+        let terrain = TerrainData::create_flat(64, 64, 1.0, 0.0).unwrap();
+        let configs = create_default_biomes();
+        let config = ScatterConfig::default();
+        let count = |biome, object_type: &str| {
+            let map = BiomeMap::uniform(64, 64, biome, 1.0);
+            let rules = ScatterRules::Biomes {
+                map: &map,
+                configs: &configs,
+            };
+            scatter_objects(&terrain, rules, &config, &[])
+                .iter()
+                .filter(|o| o.object_type == object_type)
+                .count()
+        };
+
+        assert_eq!(count(BiomeType::Desert, "tree"), 0);
+        assert!(count(BiomeType::Desert, "rock") > 0);
+        assert!(count(BiomeType::Forest, "tree") > count(BiomeType::Plains, "tree"));
+    }
+
+    #[test]
+    fn test_existing_objects_and_type_filter() {
+        let terrain = TerrainData::create_flat(32, 32, 1.0, 0.0).unwrap();
+        let rules = [
+            ObjectRule::new("tree", 20.0, 2.0),
+            ObjectRule::new("rock", 20.0, 2.0),
+        ];
+        let existing = vec![EnvironmentObject::simple(
+            "statue".to_string(),
+            Vec3::new(2.0, 0.0, 2.0),
+        )];
+        let config = ScatterConfig {
+            object_types: Some(vec!["rock".to_string()]),
+            max_objects: Some(25),
+            ..Default::default()
+        };
+
+        let objects = scatter_objects(&terrain, ScatterRules::Uniform(&rules), &config, &existing);
+        assert_eq!(objects.len(), 25);
+        assert!(objects.iter().all(|o| o.object_type == "rock"));
+        assert!(
+            objects
+                .iter()
+                .all(|o| o.position.distance(existing[0].position) >= 2.0 * 0.8)
+        );
+    }
+}
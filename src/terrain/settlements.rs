@@ -0,0 +1,482 @@
+//! Towns and camps placed on flat ground beside the path network.
+//!
+//! Sites are rated with `slope_map` over their whole footprint and, when
+//! the map has paths, must sit close to a road without one running through
+//! the middle. Each site is laid out from a template of buildings, fences, and
+//! props, and keeps a safe radius where no enemy spawn zone may be placed.
+
+use super::coordinates::{distance_to_samples, get_height_at_world_interpolated, grid_to_world};
+use super::path_generator::PathNetwork;
+use crate::map::{EnvironmentObject, TerrainData, WaterBody, water_depth_map};
+use crate::objects::ObjectRegistry;
+use crate::terrain_generation::slope_map;
+use bevy::prelude::*;
+use rand::{Rng, SeedableRng};
+use rand_pcg::Pcg64;
+use std::f32::consts::TAU;
+
+/// Settlement template
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettlementKind {
+    /// Fenced ring of houses around a well
+    Town,
+    /// Tents and supplies around a campfire
+    Camp,
+}
+
+/// Tuning for settlement placement
+#[derive(Debug, Clone, PartialEq)]
+pub struct SettlementConfig {
+    pub towns: u32,
+    pub camps: u32,
+    /// World radius of a town footprint
+    pub town_radius: f32,
+    /// World radius of a camp footprint
+    pub camp_radius: f32,
+    /// Steepest slope allowed anywhere on a site
+    pub max_slope: f32,
+    /// Furthest a site's edge may be from a road, when the map has roads
+    pub road_reach: f32,
+    /// World distance around a settlement kept free of enemy spawn zones
+    pub safe_margin: f32,
+}
+
+impl Default for SettlementConfig {
+    fn default() -> Self {
+        Self {
+            towns: 1,
+            camps: 2,
+            town_radius: 12.0,
+            camp_radius: 6.0,
+            max_slope: 0.15, // Gentle enough for buildings to sit level
+            road_reach: 6.0,
+            safe_margin: 10.0,
+        }
+    }
+}
+
+/// A placed settlement and its objects
+#[derive(Debug, Clone)]
+pub struct Settlement {
+    pub kind: SettlementKind,
+    pub center: Vec3,
+    pub radius: f32,
+    /// Enemy spawn zones must stay outside this world radius
+    pub safe_radius: f32,
+    pub objects: Vec<EnvironmentObject>,
+}
+
+impl Settlement {
+    /// Whether a circle of `radius` around `position` reaches into the safe zone
+    pub fn is_safe(&self, position: Vec3, radius: f32) -> bool {
+        position.xz().distance(self.center.xz()) < self.safe_radius + radius
+    }
+}
+
+/// Whether a circle of `radius` around `position` reaches into any settlement's safe zone
+pub fn in_safe_zone(settlements: &[Settlement], position: Vec3, radius: f32) -> bool {
+    settlements
+        .iter()
+        .any(|settlement| settlement.is_safe(position, radius))
+}
+
+/// Per-sample terrain facts shared by site search and layout
+struct SiteMap<'a> {
+    terrain: &'a TerrainData,
+    slopes: Vec<f32>,
+    wet: Vec<bool>,
+    /// World distance to the nearest path point, `f32::MAX` without paths
+    road_distance: Vec<f32>,
+    road_points: Vec<Vec2>,
+    road_half_width: f32,
+}
+
+impl<'a> SiteMap<'a> {
+    fn new(
+        terrain: &'a TerrainData,
+        network: Option<&PathNetwork>,
+        water_bodies: &[WaterBody],
+    ) -> Self {
+        let mut on_road = vec![false; terrain.heights.len()];
+        let mut road_points = Vec::new();
+        let mut road_half_width: f32 = 0.0;
+        for path in network.iter().flat_map(|network| &network.paths) {
+            road_half_width = road_half_width.max(path.width / 2.0);
+            for point in &path.points {
+                if point.x < terrain.width && point.z < terrain.height {
+                    on_road[(point.z * terrain.width + point.x) as usize] = true;
+                    let world = grid_to_world(terrain, point.x as f32, point.z as f32);
+                    road_points.push(Vec2::new(world.x, world.z));
+                }
+            }
+        }
+
+        Self {
+            terrain,
+            slopes: slope_map(terrain),
+            wet: water_depth_map(terrain, water_bodies)
+                .iter()
+                .map(|&depth| depth > 0.0)
+                .collect(),
+            road_distance: distance_to_samples(terrain, &on_road),
+            road_points,
+            road_half_width,
+        }
+    }
+
+    fn has_roads(&self) -> bool {
+        !self.road_points.is_empty()
+    }
+
+    /// Nearest-sample lookup, `None` off the map
+    fn sample(&self, position: Vec2) -> Option<usize> {
+        let terrain = self.terrain;
+        let x = ((position.x + terrain.width as f32 * terrain.scale / 2.0) / terrain.scale).round();
+        let z =
+            ((position.y + terrain.height as f32 * terrain.scale / 2.0) / terrain.scale).round();
+        if x < 0.0 || z < 0.0 || x >= terrain.width as f32 || z >= terrain.height as f32 {
+            return None;
+        }
+        Some(z as usize * terrain.width as usize + x as usize)
+    }
+
+    /// Rate a site centered on a sample; lower is better, `None` if unusable
+    fn site_score(&self, x: u32, z: u32, radius: f32, config: &SettlementConfig) -> Option<f32> {
+        let terrain = self.terrain;
+        let reach = (radius / terrain.scale).ceil() as i64;
+        let (x, z) = (x as i64, z as i64);
+        if x - reach < 1
+            || z - reach < 1
+            || x + reach >= terrain.width as i64 - 1
+            || z + reach >= terrain.height as i64 - 1
+        {
+            return None;
+        }
+
+        let mut steepest: f32 = 0.0;
+        for dz in -reach..=reach {
+            for dx in -reach..=reach {
+                if dx * dx + dz * dz > reach * reach {
+                    continue;
+                }
+                let index = ((z + dz) * terrain.width as i64 + x + dx) as usize;
+                if self.wet[index] || self.slopes[index] > config.max_slope {
+                    return None;
+                }
+                steepest = steepest.max(self.slopes[index]);
+            }
+        }
+
+        if !self.has_roads() {
+            return Some(steepest);
+        }
+        // Beside a road, with the middle left clear for the well or fire
+        let road = self.road_distance[(z * terrain.width as i64 + x) as usize];
+        if road > radius + config.road_reach || road < self.road_half_width + 2.0 {
+            return None;
+        }
+        Some(steepest + 0.1 * road / (radius + config.road_reach))
+    }
+
+    /// Whether an object of footprint `radius` at `position` is on land and off the road
+    fn is_clear(&self, position: Vec2, radius: f32) -> bool {
+        self.sample(position).is_some_and(|index| {
+            !self.wet[index] && self.road_distance[index] >= radius + self.road_half_width
+        })
+    }
+
+    /// Direction from `center` to the closest road point
+    fn road_direction(&self, center: Vec2) -> Option<Vec2> {
+        self.road_points
+            .iter()
+            .min_by(|a, b| a.distance(center).total_cmp(&b.distance(center)))
+            .map(|point| (*point - center).normalize_or_zero())
+    }
+}
+
+/// Yaw that turns an object's local X axis to face `direction` in the XZ plane
+fn yaw_along(direction: Vec2) -> f32 {
+    (-direction.y).atan2(direction.x)
+}
+
+/// Unit vector at `angle` radians in the XZ plane
+fn heading(angle: f32) -> Vec2 {
+    Vec2::new(angle.cos(), angle.sin())
+}
+
+/// Collects template pieces standing on the terrain
+struct Layout<'a, 'b> {
+    sites: &'b SiteMap<'a>,
+    objects: Vec<EnvironmentObject>,
+}
+
+impl Layout<'_, '_> {
+    /// Add a piece if its footprint is clear; returns whether it was placed
+    fn place(&mut self, object_type: &str, position: Vec2, yaw: f32, scale: Vec3) -> bool {
+        let footprint = Vec2::new(scale.x, scale.z).length() / 2.0;
+        if !self.sites.is_clear(position, footprint) {
+            return false;
+        }
+        let ground = get_height_at_world_interpolated(self.sites.terrain, position.x, position.y)
+            .unwrap_or(0.0);
+        // Placeholder meshes are centered, so lift them to stand on the ground
+        let lift = ObjectRegistry::global()
+            .get_or_fallback(object_type)
+            .model
+            .placeholder
+            .half_height();
+        self.objects.push(EnvironmentObject::new(
+            object_type.to_string(),
+            Vec3::new(position.x, ground + lift * scale.y, position.y),
+            Vec3::new(0.0, yaw, 0.0),
+            scale,
+        ));
+        true
+    }
+
+    /// Buildings on a ring facing the center, each with a prop at its side
+    fn ring_of_buildings(
+        &mut self,
+        center: Vec2,
+        ring: f32,
+        count: u32,
+        building: (&str, Vec3),
+        rng: &mut Pcg64,
+    ) {
+        let start = rng.gen_range(0.0..TAU);
+        for i in 0..count {
+            let angle = start + i as f32 * TAU / count as f32 + rng.gen_range(-0.2..0.2);
+            let outward = heading(angle);
+            let tangent = outward.perp();
+            let scale = building.1 * rng.gen_range(0.9..1.1);
+            let position = center + outward * ring;
+            if self.place(building.0, position, yaw_along(tangent), scale) {
+                let prop = if rng.gen_bool(0.5) { "barrel" } else { "crate" };
+                let side = position + tangent * (scale.x / 2.0 + 0.8);
+                self.place(prop, side, rng.gen_range(0.0..TAU), Vec3::ONE);
+            }
+        }
+    }
+
+    /// Fence ring with a gate facing `gate`, plus gaps wherever a road crosses
+    fn fence(&mut self, center: Vec2, radius: f32, gate: Vec2) {
+        const SEGMENT_LENGTH: f32 = 2.5;
+        let segments = (TAU * radius / SEGMENT_LENGTH).ceil() as u32;
+        let length = TAU * radius / segments as f32;
+        let gate_segment = (0..segments)
+            .max_by(|&a, &b| {
+                let facing = |i: u32| heading((i as f32 + 0.5) * TAU / segments as f32).dot(gate);
+                facing(a).total_cmp(&facing(b))
+            })
+            .unwrap_or(0);
+
+        for i in (0..segments).filter(|&i| i != gate_segment) {
+            let outward = heading((i as f32 + 0.5) * TAU / segments as f32);
+            let scale = Vec3::new(length, 1.0, 0.15);
+            self.place(
+                "fence",
+                center + outward * radius,
+                yaw_along(outward.perp()),
+                scale,
+            );
+        }
+    }
+}
+
+/// Find sites and lay out the configured towns and camps.
+///
+/// Towns are placed first since they need the most room. Settlements keep
+/// their safe zones apart so no two share ground.
+pub fn generate_settlements(
+    terrain: &TerrainData,
+    network: Option<&PathNetwork>,
+    water_bodies: &[WaterBody],
+    config: &SettlementConfig,
+    seed: u64,
+) -> Vec<Settlement> {
+    let sites = SiteMap::new(terrain, network, water_bodies);
+    let mut rng = Pcg64::seed_from_u64(seed);
+    let mut settlements: Vec<Settlement> = Vec::new();
+
+    for (kind, radius, count) in [
+        (SettlementKind::Town, config.town_radius, config.towns),
+        (SettlementKind::Camp, config.camp_radius, config.camps),
+    ] {
+        if count == 0 {
+            continue;
+        }
+
+        // Jitter breaks ties between equally flat sites
+        let stride = ((radius / 3.0 / terrain.scale) as u32).max(1);
+        let mut candidates: Vec<(f32, Vec3)> = Vec::new();
+        for z in (0..terrain.height).step_by(stride as usize) {
+            for x in (0..terrain.width).step_by(stride as usize) {
+                if let Some(score) = sites.site_score(x, z, radius, config) {
+                    let world = grid_to_world(terrain, x as f32, z as f32);
+                    let height = terrain.heights[(z * terrain.width + x) as usize];
+                    candidates.push((
+                        score + rng.gen_range(0.0..0.01),
+                        Vec3::new(world.x, height, world.z),
+                    ));
+                }
+            }
+        }
+        candidates.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut placed = 0;
+        for (_, center) in candidates {
+            if placed == count {
+                break;
+            }
+            let safe_radius = radius + config.safe_margin;
+            let apart = settlements.iter().all(|other| {
+                other.center.xz().distance(center.xz()) >= other.safe_radius + safe_radius
+            });
+            if !apart {
+                continue;
+            }
+
+            let objects = lay_out(&sites, kind, center.xz(), radius, &mut rng);
+            settlements.push(Settlement {
+                kind,
+                center,
+                radius,
+                safe_radius,
+                objects,
+            });
+            placed += 1;
+        }
+    }
+
+    settlements
+}
+
+/// Build a settlement's buildings, fences, and props from its template
+fn lay_out(
+    sites: &SiteMap,
+    kind: SettlementKind,
+    center: Vec2,
+    radius: f32,
+    rng: &mut Pcg64,
+) -> Vec<EnvironmentObject> {
+    let mut layout = Layout {
+        sites,
+        objects: Vec::new(),
+    };
+
+    match kind {
+        SettlementKind::Town => {
+            layout.place("well", center, 0.0, Vec3::ONE);
+            let houses = rng.gen_range(4..=6);
+            layout.ring_of_buildings(
+                center,
+                radius * 0.55,
+                houses,
+                ("house", Vec3::new(4.0, 3.0, 5.0)),
+                rng,
+            );
+            let gate = sites
+                .road_direction(center)
+                .unwrap_or_else(|| heading(rng.gen_range(0.0..TAU)));
+            layout.fence(center, radius * 0.95, gate);
+        }
+        SettlementKind::Camp => {
+            layout.place("campfire", center, 0.0, Vec3::ONE);
+            let tents = rng.gen_range(2..=4);
+            layout.ring_of_buildings(
+                center,
+                radius * 0.5,
+                tents,
+                ("tent", Vec3::new(2.5, 2.0, 3.0)),
+                rng,
+            );
+        }
+    }
+
+    layout.objects
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::terrain::path_generator::{Path, PathPoint, PathType};
+
+    /// A straight east-west road across the middle of the map
+    fn road_network(width: u32, z: u32) -> PathNetwork {
+        PathNetwork {
+            paths: vec![Path {
+                points: (0..width)
+                    .map(|x| PathPoint {
+                        x,
+                        z,
+                        elevation: 0.0,
+                    })
+                    .collect(),
+                path_type: PathType::MainRoad,
+                width: 4.0,
+            }],
+            junctions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_settlements_sit_beside_the_road() {
+        let terrain = TerrainData::create_flat(160, 96, 1.0, 0.0).unwrap();
+        let network = road_network(160, 48);
+        let config = SettlementConfig::default();
+
+        let settlements = generate_settlements(&terrain, Some(&network), &[], &config, 7);
+
+        assert_eq!(settlements.len(), 3);
+        assert_eq!(settlements[0].kind, SettlementKind::Town);
+        let road_z = grid_to_world(&terrain, 0.0, 48.0).z;
+        for settlement in &settlements {
+            let offset = (settlement.center.z - road_z).abs();
+            assert!((4.0..=settlement.radius + config.road_reach).contains(&offset));
+            // Nothing stands on the road
+            for object in &settlement.objects {
+                assert!((object.position.z - road_z).abs() > 2.0);
+            }
+        }
+
+        let town = &settlements[0].objects;
+        assert!(town.iter().any(|o| o.object_type == "house"));
+        assert!(town.iter().filter(|o| o.object_type == "fence").count() > 10);
+        assert!(town.iter().any(|o| o.object_type == "well"));
+
+        // Safe zones never overlap
+        for (i, a) in settlements.iter().enumerate() {
+            for b in &settlements[i + 1..] {
+                assert!(a.center.distance(b.center) >= a.safe_radius + b.safe_radius);
+            }
+        }
+    }
+
+    #[test]
+    fn test_steep_terrain_has_no_sites() {
+        let heights = (0..64 * 64).map(|i| (i % 64) as f32).collect();
+        let terrain = TerrainData::new(64, 64, heights, 1.0).unwrap();
+
+        let settlements =
+            generate_settlements(&terrain, None, &[], &SettlementConfig::default(), 1);
+        assert!(settlements.is_empty());
+    }
+
+    #[test]
+    fn test_safe_zone_and_determinism() {
+        let terrain = TerrainData::create_flat(80, 80, 1.0, 0.0).unwrap();
+        let config = SettlementConfig::default();
+        let a = generate_settlements(&terrain, None, &[], &config, 3);
+        let b = generate_settlements(&terrain, None, &[], &config, 3);
+
+        assert!(!a.is_empty());
+        assert_eq!(
+            a.iter().map(|s| s.center).collect::<Vec<_>>(),
+            b.iter().map(|s| s.center).collect::<Vec<_>>()
+        );
+        assert!(in_safe_zone(&a, a[0].center, 0.0));
+        let outside = a[0].center + Vec3::X * (a[0].safe_radius + 1.0);
+        assert!(!in_safe_zone(&a[..1], outside, 0.0));
+        assert!(in_safe_zone(&a[..1], outside, 2.0));
+    }
+}
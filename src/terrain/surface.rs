@@ -0,0 +1,190 @@
+//! Per-vertex surface data for painting the terrain.
+//!
+//! Every heightmap vertex gets a color blended from its biomes, so a plain PBR material paints biomes, roads, and rocky
+//! slopes through vertex colors without a custom shader.
+
+use super::biomes::{BiomeConfig, BiomeMap, BiomeType, SurfaceType};
+use super::path_generator::PathType;
+use crate::map::{RoadSurface, TerrainData, road_surface_map};
+use crate::terrain_generation::calculate_slope;
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+/// Colors and slope thresholds used to paint the terrain
+#[derive(Debug, Clone)]
+pub struct TerrainPalette {
+    pub grass: Color,
+    pub dirt: Color,
+    pub sand: Color,
+    pub ice: Color,
+    pub water: Color,
+    pub rock: Color,
+    pub road: Color,
+    pub trail: Color,
+    /// Slope (rise over run) where bare rock starts showing through
+    pub rock_slope_start: f32,
+    /// Slope where the surface is entirely rock
+    pub rock_slope_full: f32,
+}
+
+impl Default for TerrainPalette {
+    fn default() -> Self {
+        Self {
+            grass: Color::srgb(0.3, 0.5, 0.3), // The old single terrain color
+            dirt: Color::srgb(0.45, 0.36, 0.25),
+            sand: Color::srgb(0.82, 0.74, 0.52),
+            ice: Color::srgb(0.88, 0.92, 0.96),
+            water: Color::srgb(0.25, 0.38, 0.35), // Boggy ground, open water is its own mesh
+            rock: Color::srgb(0.45, 0.44, 0.42),
+            road: Color::srgb(0.55, 0.5, 0.42),
+            trail: Color::srgb(0.5, 0.42, 0.3),
+            rock_slope_start: 0.8,
+            rock_slope_full: 1.4,
+        }
+    }
+}
+
+impl TerrainPalette {
+    /// Base color of a surface type
+    pub fn surface_color(&self, surface: SurfaceType) -> Color {
+        match surface {
+            SurfaceType::Grass => self.grass,
+            SurfaceType::Dirt => self.dirt,
+            SurfaceType::Sand => self.sand,
+            SurfaceType::Ice => self.ice,
+            SurfaceType::Water => self.water,
+            SurfaceType::Rock(_) => self.rock,
+        }
+    }
+
+    /// Color of a paved path
+    pub fn road_color(&self, path_type: PathType) -> Color {
+        match path_type {
+            PathType::MainRoad => self.road,
+            PathType::Trail | PathType::RiverPath | PathType::MountainPass => self.trail,
+        }
+    }
+
+    /// Biome color: its primary surface mixed with secondaries by probability
+    fn biome_color(&self, config: &BiomeConfig) -> LinearRgba {
+        let secondary_share: f32 = config.secondary_surfaces.iter().map(|(_, p)| p).sum();
+        let mut color = self.surface_color(config.primary_surface).to_linear()
+            * (1.0 - secondary_share).max(0.0);
+        for (surface, probability) in &config.secondary_surfaces {
+            color += self.surface_color(*surface).to_linear() * *probability;
+        }
+        color.with_alpha(1.0)
+    }
+}
+
+/// Painted surface data for one terrain vertex
+#[derive(Debug, Clone, PartialEq)]
+pub struct SurfaceVertex {
+    pub color: [f32; 4], // Linear RGBA
+}
+
+/// Work out the painted color of every heightmap sample.
+///
+/// Roads win over everything, then steep slopes turn to rock, then the
+/// biome blend decides. Without a biome map the terrain is all plains.
+pub fn surface_vertices(
+    terrain: &TerrainData,
+    biome_map: Option<&BiomeMap>,
+    roads: &[RoadSurface],
+    biome_configs: &HashMap<BiomeType, BiomeConfig>,
+    palette: &TerrainPalette,
+) -> Vec<SurfaceVertex> {
+    let paved = road_surface_map(terrain, roads);
+    let biome_colors: HashMap<BiomeType, LinearRgba> = biome_configs
+        .iter()
+        .map(|(biome_type, config)| (*biome_type, palette.biome_color(config)))
+        .collect();
+    let rock = palette.rock.to_linear();
+    let slope_range = (palette.rock_slope_full - palette.rock_slope_start).max(f32::EPSILON);
+
+    let mut vertices = Vec::with_capacity(terrain.heights.len());
+    for z in 0..terrain.height {
+        for x in 0..terrain.width {
+            let index = (z * terrain.width + x) as usize;
+            let weights = biome_map
+                .and_then(|map| map.get_blend_at_grid(x, z))
+                .map(|blend| blend.weights.clone())
+                .filter(|weights| !weights.is_empty())
+                .unwrap_or_else(|| vec![(BiomeType::Plains, 1.0)]);
+
+            let mut color = LinearRgba::NONE;
+            for (biome_type, weight) in &weights {
+                let biome_color = biome_colors
+                    .get(biome_type)
+                    .copied()
+                    .unwrap_or_else(|| palette.grass.to_linear());
+                color += biome_color * *weight;
+            }
+
+            // Bare rock on steep ground
+            let slope = calculate_slope(terrain, x, z);
+            let t = ((slope - palette.rock_slope_start) / slope_range).clamp(0.0, 1.0);
+            let rockiness = t * t * (3.0 - 2.0 * t);
+            if rockiness > 0.0 {
+                color = color * (1.0 - rockiness) + rock * rockiness;
+            }
+
+            if let Some(path_type) = paved[index] {
+                color = palette.road_color(path_type).to_linear();
+            }
+
+            vertices.push(SurfaceVertex {
+                color: color.with_alpha(1.0).to_f32_array(),
+            });
+        }
+    }
+    vertices
+}
+
+/// Material for terrain painted with the colors from [`surface_vertices`]
+pub fn terrain_material() -> StandardMaterial {
+    StandardMaterial {
+        base_color: Color::WHITE, // Vertex colors carry the surface
+        perceptual_roughness: 0.95,
+        ..default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::terrain::biomes::create_default_biomes;
+
+    #[test]
+    fn test_surface_priorities() {
+        // Flat desert on the left, a cliff on the right
+        let heights = (0..8 * 8)
+            .map(|i| if i % 8 < 5 { 0.0 } else { (i % 8) as f32 * 4.0 })
+            .collect();
+        let terrain = TerrainData::new(8, 8, heights, 1.0).unwrap();
+        let biomes = BiomeMap::uniform(8, 8, BiomeType::Desert, 1.0);
+        let roads = [RoadSurface {
+            path_type: PathType::MainRoad,
+            cells: vec![8 * 3 + 1],
+        }];
+        let palette = TerrainPalette::default();
+
+        let vertices = surface_vertices(
+            &terrain,
+            Some(&biomes),
+            &roads,
+            &create_default_biomes(),
+            &palette,
+        );
+
+        assert_eq!(vertices.len(), 64);
+        let rock = palette.rock.to_linear().to_f32_array();
+        assert_ne!(vertices[0].color, rock);
+        assert_ne!(vertices[0].color, vertices[8 * 3 + 1].color);
+        assert_eq!(
+            vertices[8 * 3 + 1].color,
+            palette.road.to_linear().to_f32_array()
+        );
+        assert_eq!(vertices[8 * 3 + 5].color, rock);
+    }
+}
@@ -0,0 +1,337 @@
+//! Difficulty tiers for enemy spawn zones.
+//!
+//! Zones are rated by how far the player has to walk from the spawn point,
+//! so a zone just across a cliff still counts as deep. The walkable range
+//! beyond a safe radius is split into equal bands, one per tier, and deeper
+//! tiers get more and larger zones with more and stronger enemies.
+
+use super::{QueuedCell, is_suitable_for_spawning};
+use crate::game_logic::errors::MinionResult;
+use crate::map::{SpawnZone, TerrainData, ZoneStats};
+use crate::terrain::coordinates::{get_height_at_world_nearest, grid_to_world, world_to_grid};
+use bevy::prelude::*;
+use rand::SeedableRng;
+use rand::seq::SliceRandom;
+use rand_pcg::Pcg64;
+use std::collections::BinaryHeap;
+
+/// Zone settings for one difficulty tier
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpawnTier {
+    pub zones: u32,
+    pub radius: f32,
+    pub max_enemies: u32,
+    /// Multipliers on the configured enemy health and speed
+    pub stats: ZoneStats,
+}
+
+impl SpawnTier {
+    pub fn new(zones: u32, radius: f32, max_enemies: u32) -> Self {
+        Self {
+            zones,
+            radius,
+            max_enemies,
+            stats: ZoneStats::default(),
+        }
+    }
+
+    /// Builder method to scale the stats of enemies spawned in this tier
+    pub fn with_stats(mut self, health_scale: f32, speed_scale: f32) -> Self {
+        self.stats = ZoneStats {
+            health_scale,
+            speed_scale,
+        };
+        self
+    }
+}
+
+/// How spawn zones are rated and placed
+#[derive(Debug, Clone)]
+pub struct DifficultyConfig {
+    /// No part of a zone comes closer to the player spawn than this
+    pub safe_radius: f32,
+    /// Steepest rise over run a walker climbs between heightmap samples
+    pub max_walk_slope: f32,
+    /// Steepest ground a zone center may sit on
+    pub max_zone_slope: f32,
+    /// Minimum gap between the edges of two zones
+    pub zone_spacing: f32,
+    /// Easiest tier first
+    pub tiers: Vec<SpawnTier>,
+    /// Weakest enemy first; tier `n` allows the first `n + 1` types
+    pub enemy_types: Vec<String>,
+}
+
+impl Default for DifficultyConfig {
+    fn default() -> Self {
+        Self {
+            safe_radius: 12.0,
+            max_walk_slope: 1.0, // 45 degrees
+            max_zone_slope: 0.3,
+            zone_spacing: 2.0,
+            tiers: vec![
+                SpawnTier::new(1, 3.0, 2),
+                SpawnTier::new(2, 4.0, 3).with_stats(1.5, 1.1),
+                SpawnTier::new(2, 5.0, 4).with_stats(2.0, 1.2),
+            ],
+            enemy_types: vec!["dark-knight".to_string()],
+        }
+    }
+}
+
+impl DifficultyConfig {
+    /// Builder method to set the no-spawn radius around the start
+    pub fn with_safe_radius(mut self, safe_radius: f32) -> Self {
+        self.safe_radius = safe_radius;
+        self
+    }
+
+    /// Builder method to set the enemy roster, weakest first
+    pub fn with_enemy_types(mut self, enemy_types: Vec<String>) -> Self {
+        self.enemy_types = enemy_types;
+        self
+    }
+
+    /// Enemy types allowed in zones of `tier`
+    pub fn enemy_types_for(&self, tier: u32) -> Vec<String> {
+        let unlocked = (tier as usize + 1).min(self.enemy_types.len());
+        self.enemy_types[..unlocked].to_vec()
+    }
+
+    /// Tier of a spot `distance` from the start when the farthest walkable
+    /// spot is `farthest` away; `None` inside the safe radius or out of reach
+    pub fn tier_at(&self, distance: f32, farthest: f32) -> Option<u32> {
+        if self.tiers.is_empty() || distance < self.safe_radius || distance > farthest {
+            return None;
+        }
+        let span = (farthest - self.safe_radius).max(f32::EPSILON);
+        let band = ((distance - self.safe_radius) / span * self.tiers.len() as f32) as usize;
+        Some(band.min(self.tiers.len() - 1) as u32)
+    }
+}
+
+/// Walking distance in world units from `start` to every heightmap sample.
+///
+/// Steps are 8-connected and blocked where the rise between samples is
+/// steeper than `max_slope`. Unreachable samples are `f32::MAX`.
+pub fn walking_distances(terrain: &TerrainData, start: Vec3, max_slope: f32) -> Vec<f32> {
+    let (width, height) = (terrain.width as usize, terrain.height as usize);
+    let mut distances = vec![f32::MAX; width * height];
+
+    let (grid_x, grid_z) = world_to_grid(terrain, start.x, start.z);
+    let start_x = (grid_x.round().max(0.0) as usize).min(width - 1);
+    let start_z = (grid_z.round().max(0.0) as usize).min(height - 1);
+    let start = start_z * width + start_x;
+    distances[start] = 0.0;
+    let mut queue = BinaryHeap::from([QueuedCell {
+        cost: 0.0,
+        index: start,
+    }]);
+
+    while let Some(QueuedCell {
+        cost: distance,
+        index,
+    }) = queue.pop()
+    {
+        if distance > distances[index] {
+            continue;
+        }
+        let (x, z) = (index % width, index / width);
+        for (dx, dz) in [
+            (-1, -1),
+            (0, -1),
+            (1, -1),
+            (-1, 0),
+            (1, 0),
+            (-1, 1),
+            (0, 1),
+            (1, 1),
+        ] {
+            let (Some(nx), Some(nz)) = (x.checked_add_signed(dx), z.checked_add_signed(dz)) else {
+                continue;
+            };
+            if nx >= width || nz >= height {
+                continue;
+            }
+            let next = nz * width + nx;
+            let run = if dx != 0 && dz != 0 {
+                std::f32::consts::SQRT_2 * terrain.scale
+            } else {
+                terrain.scale
+            };
+            if (terrain.heights[next] - terrain.heights[index]).abs() > max_slope * run {
+                continue;
+            }
+            let walked = distance + run;
+            if walked < distances[next] {
+                distances[next] = walked;
+                queue.push(QueuedCell {
+                    cost: walked,
+                    index: next,
+                });
+            }
+        }
+    }
+
+    distances
+}
+
+/// Place spawn zones tier by tier, deepest first, and return them easiest first.
+///
+/// `accept` can veto a zone by its center and radius, e.g. in water or
+/// reaching into a settlement.
+/// Tiers whose band has no room simply end up with fewer zones.
+pub fn generate_difficulty_zones(
+    terrain: &TerrainData,
+    player_spawn: Vec3,
+    config: &DifficultyConfig,
+    seed: u64,
+    accept: impl Fn(Vec3, f32) -> bool,
+) -> MinionResult<Vec<SpawnZone>> {
+    let distances = walking_distances(terrain, player_spawn, config.max_walk_slope);
+    let farthest = distances
+        .iter()
+        .copied()
+        .filter(|distance| *distance != f32::MAX)
+        .fold(0.0, f32::max);
+
+    let mut candidates = vec![Vec::new(); config.tiers.len()];
+    for (index, &distance) in distances.iter().enumerate() {
+        let Some(tier) = config.tier_at(distance, farthest) else {
+            continue;
+        };
+        let x = index as u32 % terrain.width;
+        let z = index as u32 / terrain.width;
+        let world = grid_to_world(terrain, x as f32, z as f32);
+        let position = Vec2::new(world.x, world.z);
+        // Keep the whole zone out of the safe radius, even across a cliff
+        let clearance = config.safe_radius + config.tiers[tier as usize].radius;
+        if position.distance(player_spawn.xz()) < clearance {
+            continue;
+        }
+        candidates[tier as usize].push(position);
+    }
+
+    let mut rng = Pcg64::seed_from_u64(seed);
+    let mut zones: Vec<SpawnZone> = Vec::new();
+    for (tier, spec) in config.tiers.iter().enumerate().rev() {
+        let mut pool = std::mem::take(&mut candidates[tier]);
+        pool.shuffle(&mut rng);
+
+        let mut placed = 0;
+        for position in pool {
+            if placed == spec.zones {
+                break;
+            }
+            let clear = zones.iter().all(|zone| {
+                zone.center.xz().distance(position)
+                    >= zone.radius + spec.radius + config.zone_spacing
+            });
+            if !clear
+                || !is_suitable_for_spawning(terrain, position.x, position.y, config.max_zone_slope)
+            {
+                continue;
+            }
+            let ground =
+                get_height_at_world_nearest(terrain, position.x, position.y).unwrap_or(0.0);
+            let center = Vec3::new(position.x, ground + 1.0, position.y);
+            if !accept(center, spec.radius) {
+                continue;
+            }
+
+            let tier = tier as u32;
+            zones.push(
+                SpawnZone::new(
+                    center,
+                    spec.radius,
+                    spec.max_enemies,
+                    config.enemy_types_for(tier),
+                )?
+                .with_tier(tier)
+                .with_stats(spec.stats),
+            );
+            placed += 1;
+        }
+    }
+
+    zones.sort_by_key(|zone| zone.tier);
+    Ok(zones)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_walking_distances_detour_around_cliff() {
+        // A sheer wall across the middle with a gap at the top edge
+        let heights = (0..32 * 32)
+            .map(|i| {
+                let (x, z) = (i % 32, i / 32);
+                if x == 16 && z < 28 { 50.0 } else { 0.0 }
+            })
+            .collect();
+        let terrain = TerrainData::new(32, 32, heights, 1.0).unwrap();
+        let start = Vec3::new(-6.0, 0.0, 0.0); // Grid (10, 16)
+
+        let distances = walking_distances(&terrain, start, 1.0);
+
+        assert_eq!(distances[16 * 32 + 10], 0.0);
+        assert!((distances[16 * 32 + 14] - 4.0).abs() < 1e-4);
+        assert_eq!(distances[16 * 32 + 16], f32::MAX);
+        // Eight cells away in a straight line, but the walk goes round the wall
+        assert!(distances[16 * 32 + 18] > 20.0);
+    }
+
+    #[test]
+    fn test_tiers_scale_with_depth() {
+        let terrain = TerrainData::create_flat(64, 64, 1.0, 0.0).unwrap();
+        let config = DifficultyConfig::default()
+            .with_enemy_types(vec!["dark-knight".to_string(), "dark-champion".to_string()]);
+        let spawn = Vec3::new(0.0, 1.0, 0.0);
+
+        let zones = generate_difficulty_zones(&terrain, spawn, &config, 9, |_, _| true).unwrap();
+
+        assert_eq!(zones.len(), 5);
+        assert!(zones.windows(2).all(|pair| pair[0].tier <= pair[1].tier));
+        for zone in &zones {
+            let spec = &config.tiers[zone.tier as usize];
+            assert_eq!(zone.radius, spec.radius);
+            assert_eq!(zone.max_enemies, spec.max_enemies);
+            assert_eq!(zone.stats, spec.stats);
+            assert!(zone.center.xz().distance(spawn.xz()) >= config.safe_radius + zone.radius);
+        }
+        assert_eq!(zones[0].enemy_types, vec!["dark-knight".to_string()]);
+        assert_eq!(zones[4].enemy_types.len(), 2);
+
+        // Every deeper zone lies farther out than every easier one
+        let distance = |zone: &SpawnZone| zone.center.xz().length();
+        for easy in zones.iter().filter(|zone| zone.tier == 0) {
+            for hard in zones.iter().filter(|zone| zone.tier == 2) {
+                assert!(distance(hard) > distance(easy));
+            }
+        }
+
+        let again = generate_difficulty_zones(&terrain, spawn, &config, 9, |_, _| true).unwrap();
+        let centers: Vec<Vec3> = zones.iter().map(|zone| zone.center).collect();
+        assert_eq!(
+            centers,
+            again.iter().map(|zone| zone.center).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_no_zones_inside_safe_radius() {
+        let terrain = TerrainData::create_flat(32, 32, 1.0, 0.0).unwrap();
+        let config = DifficultyConfig::default().with_safe_radius(40.0);
+
+        let zones =
+            generate_difficulty_zones(&terrain, Vec3::ZERO, &config, 1, |_, _| true).unwrap();
+
+        assert!(zones.is_empty());
+        assert_eq!(config.tier_at(5.0, 60.0), None);
+        assert_eq!(config.tier_at(45.0, 60.0), Some(0));
+        assert_eq!(config.tier_at(50.0, 60.0), Some(1));
+        assert_eq!(config.tier_at(60.0, 60.0), Some(2));
+    }
+}
@@ -0,0 +1,737 @@
+//! Indoor dungeon levels built on a flat terrain floor.
+//!
+//! A tile grid is carved either into BSP rooms joined by corridors or into
+//! cellular-automaton caves. Solid tiles that touch the floor become `wall`
+//! objects, merged into as few rectangles as possible, so the result is an
+//! ordinary `MapDefinition` that the loader, physics, and pathfinding handle.
+
+use super::DifficultyConfig;
+use crate::game_logic::errors::{MinionError, MinionResult};
+use crate::map::{EnvironmentObject, MapDefinition, SpawnZone, TerrainData};
+use bevy::prelude::*;
+use rand::{Rng, SeedableRng};
+use rand_pcg::Pcg64;
+use std::collections::VecDeque;
+
+/// Object type used for dungeon walls
+pub const WALL_OBJECT_TYPE: &str = "wall";
+
+/// How the floor plan is carved
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DungeonStyle {
+    /// Rectangular rooms from a binary space partition, joined by corridors
+    #[default]
+    Rooms,
+    /// Organic caves smoothed from random noise
+    Caves,
+}
+
+/// Tuning for dungeon generation
+#[derive(Debug, Clone, PartialEq)]
+pub struct DungeonConfig {
+    pub style: DungeonStyle,
+    /// Smallest room side in tiles
+    pub min_room_size: u32,
+    /// Largest room side in tiles
+    pub max_room_size: u32,
+    /// BSP recursion depth, up to 2^depth rooms
+    pub split_depth: u32,
+    /// Corridor width in tiles
+    pub corridor_width: u32,
+    /// Chance a cave tile starts solid
+    pub cave_fill: f32,
+    /// Cellular automaton smoothing passes
+    pub cave_iterations: u32,
+    pub floor_height: f32,
+    pub wall_height: f32,
+    pub spawn_zones: u32,
+}
+
+impl Default for DungeonConfig {
+    fn default() -> Self {
+        Self {
+            style: DungeonStyle::Rooms,
+            min_room_size: 5,
+            max_room_size: 14,
+            split_depth: 4,
+            corridor_width: 3, // Wide enough for inflated agents to pass
+            cave_fill: 0.45,
+            cave_iterations: 5,
+            floor_height: 0.0,
+            wall_height: 3.0,
+            spawn_zones: 5,
+        }
+    }
+}
+
+/// Inclusive tile rectangle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DungeonRoom {
+    pub min_x: u32,
+    pub min_z: u32,
+    pub max_x: u32,
+    pub max_z: u32,
+}
+
+impl DungeonRoom {
+    pub fn center(&self) -> (u32, u32) {
+        ((self.min_x + self.max_x) / 2, (self.min_z + self.max_z) / 2)
+    }
+
+    /// Shorter side in tiles
+    pub fn min_side(&self) -> u32 {
+        (self.max_x - self.min_x + 1).min(self.max_z - self.min_z + 1)
+    }
+}
+
+/// Carved floor plan on a tile grid
+#[derive(Debug, Clone)]
+pub struct DungeonLayout {
+    pub width: u32,
+    pub height: u32,
+    pub floor: Vec<bool>, // Flattened 2D array (row-major)
+    /// Rooms, or open chambers for caves
+    pub rooms: Vec<DungeonRoom>,
+    /// Index into `rooms` of the room the player starts in
+    pub entrance: usize,
+}
+
+impl DungeonLayout {
+    /// Carve a floor plan of `width` x `height` tiles
+    pub fn generate(
+        width: u32,
+        height: u32,
+        config: &DungeonConfig,
+        seed: u64,
+    ) -> MinionResult<Self> {
+        let smallest = config.min_room_size.max(config.corridor_width) + 4;
+        if width < smallest || height < smallest {
+            return Err(MinionError::InvalidConfig {
+                reason: format!("Dungeon needs at least {smallest}x{smallest} tiles"),
+            });
+        }
+        if config.min_room_size == 0 || config.max_room_size < config.min_room_size {
+            return Err(MinionError::InvalidConfig {
+                reason: "Dungeon room sizes must satisfy 0 < min <= max".to_string(),
+            });
+        }
+        if !(0.0..1.0).contains(&config.cave_fill) {
+            return Err(MinionError::InvalidConfig {
+                reason: format!("Cave fill must be in 0.0..1.0, got {}", config.cave_fill),
+            });
+        }
+
+        let mut rng = Pcg64::seed_from_u64(seed);
+        let mut layout = Self {
+            width,
+            height,
+            floor: vec![false; (width * height) as usize],
+            rooms: Vec::new(),
+            entrance: 0,
+        };
+
+        match config.style {
+            DungeonStyle::Rooms => {
+                let root = DungeonRoom {
+                    min_x: 1,
+                    min_z: 1,
+                    max_x: width - 2,
+                    max_z: height - 2,
+                };
+                layout.split(root, config.split_depth, config, &mut rng);
+            }
+            DungeonStyle::Caves => layout.carve_caves(config, &mut rng)?,
+        }
+
+        // The room farthest from an arbitrary start is one end of a long route
+        let distances = layout.floor_distances(layout.rooms[0].center());
+        layout.entrance = layout.farthest_room(&distances);
+        Ok(layout)
+    }
+
+    fn index(&self, x: u32, z: u32) -> usize {
+        (z * self.width + x) as usize
+    }
+
+    /// Whether the tile is walkable floor
+    pub fn is_floor(&self, x: u32, z: u32) -> bool {
+        x < self.width && z < self.height && self.floor[self.index(x, z)]
+    }
+
+    fn carve(&mut self, room: DungeonRoom) {
+        for z in room.min_z..=room.max_z {
+            for x in room.min_x..=room.max_x {
+                let index = self.index(x, z);
+                self.floor[index] = true;
+            }
+        }
+    }
+
+    /// Partition `area`, place a room in each leaf, and return the rooms placed
+    fn split(
+        &mut self,
+        area: DungeonRoom,
+        depth: u32,
+        config: &DungeonConfig,
+        rng: &mut Pcg64,
+    ) -> Vec<usize> {
+        // Leaves keep a one tile margin so neighbouring rooms never merge
+        let min_leaf = config.min_room_size + 2;
+        let (width, height) = (area.max_x - area.min_x + 1, area.max_z - area.min_z + 1);
+        let can_split_x = width >= min_leaf * 2;
+        let can_split_z = height >= min_leaf * 2;
+
+        if depth == 0 || !(can_split_x || can_split_z) {
+            let room_width = rng
+                .gen_range(config.min_room_size..=config.max_room_size)
+                .min(width - 2);
+            let room_height = rng
+                .gen_range(config.min_room_size..=config.max_room_size)
+                .min(height - 2);
+            let min_x = area.min_x + 1 + rng.gen_range(0..=width - 2 - room_width);
+            let min_z = area.min_z + 1 + rng.gen_range(0..=height - 2 - room_height);
+            let room = DungeonRoom {
+                min_x,
+                min_z,
+                max_x: min_x + room_width - 1,
+                max_z: min_z + room_height - 1,
+            };
+            self.carve(room);
+            self.rooms.push(room);
+            return vec![self.rooms.len() - 1];
+        }
+
+        let split_x = match (can_split_x, can_split_z) {
+            (true, false) => true,
+            (false, true) => false,
+            _ if width * 4 > height * 5 => true,
+            _ if height * 4 > width * 5 => false,
+            _ => rng.gen_bool(0.5),
+        };
+        let (first, second) = if split_x {
+            let at = area.min_x + rng.gen_range(min_leaf..=width - min_leaf);
+            (
+                DungeonRoom {
+                    max_x: at - 1,
+                    ..area
+                },
+                DungeonRoom { min_x: at, ..area },
+            )
+        } else {
+            let at = area.min_z + rng.gen_range(min_leaf..=height - min_leaf);
+            (
+                DungeonRoom {
+                    max_z: at - 1,
+                    ..area
+                },
+                DungeonRoom { min_z: at, ..area },
+            )
+        };
+
+        let mut left = self.split(first, depth - 1, config, rng);
+        let right = self.split(second, depth - 1, config, rng);
+
+        // Join the closest pair of rooms across the split
+        let distance = |a: usize, b: usize| {
+            let (ax, az) = self.rooms[a].center();
+            let (bx, bz) = self.rooms[b].center();
+            ax.abs_diff(bx) + az.abs_diff(bz)
+        };
+        let (a, b) = left
+            .iter()
+            .flat_map(|&a| right.iter().map(move |&b| (a, b)))
+            .min_by_key(|&(a, b)| distance(a, b))
+            .expect("both halves hold a room");
+        self.carve_corridor(self.rooms[a].center(), self.rooms[b].center(), config, rng);
+
+        left.extend(right);
+        left
+    }
+
+    /// Carve an L-shaped corridor between two tiles
+    fn carve_corridor(
+        &mut self,
+        from: (u32, u32),
+        to: (u32, u32),
+        config: &DungeonConfig,
+        rng: &mut Pcg64,
+    ) {
+        let width = config.corridor_width.max(1);
+        let (before, after) = ((width - 1) / 2, width / 2);
+        let corner = if rng.gen_bool(0.5) {
+            (to.0, from.1)
+        } else {
+            (from.0, to.1)
+        };
+
+        for (start, end) in [(from, corner), (corner, to)] {
+            self.carve(DungeonRoom {
+                min_x: start.0.min(end.0).saturating_sub(before).max(1),
+                min_z: start.1.min(end.1).saturating_sub(before).max(1),
+                max_x: (start.0.max(end.0) + after).min(self.width - 2),
+                max_z: (start.1.max(end.1) + after).min(self.height - 2),
+            });
+        }
+    }
+
+    /// Smooth random noise into caves and keep the largest connected cave
+    fn carve_caves(&mut self, config: &DungeonConfig, rng: &mut Pcg64) -> MinionResult<()> {
+        let (width, height) = (self.width, self.height);
+        let interior = |x: u32, z: u32| x > 0 && z > 0 && x < width - 1 && z < height - 1;
+
+        for z in 0..height {
+            for x in 0..width {
+                let index = self.index(x, z);
+                self.floor[index] = interior(x, z) && !rng.gen_bool(config.cave_fill as f64);
+            }
+        }
+
+        for _ in 0..config.cave_iterations {
+            let mut next = self.floor.clone();
+            for z in 1..height - 1 {
+                for x in 1..width - 1 {
+                    let solid = (z - 1..=z + 1)
+                        .flat_map(|nz| (x - 1..=x + 1).map(move |nx| (nx, nz)))
+                        .filter(|&(nx, nz)| (nx, nz) != (x, z) && !self.is_floor(nx, nz))
+                        .count();
+                    // Classic 4-5 rule: crowded tiles fill in, open ones clear out
+                    if solid > 4 {
+                        next[self.index(x, z)] = false;
+                    } else if solid < 4 {
+                        next[self.index(x, z)] = true;
+                    }
+                }
+            }
+            self.floor = next;
+        }
+
+        // Keep only the largest cave so every floor tile is reachable
+        let mut label = vec![usize::MAX; self.floor.len()];
+        let mut largest = (0, 0);
+        for start in 0..self.floor.len() {
+            if !self.floor[start] || label[start] != usize::MAX {
+                continue;
+            }
+            label[start] = start;
+            let mut size = 0;
+            let mut queue = VecDeque::from([start as u32]);
+            while let Some(index) = queue.pop_front() {
+                size += 1;
+                let (x, z) = (index % width, index / width);
+                for (nx, nz) in [(x - 1, z), (x + 1, z), (x, z - 1), (x, z + 1)] {
+                    let neighbour = self.index(nx, nz);
+                    if self.floor[neighbour] && label[neighbour] == usize::MAX {
+                        label[neighbour] = start;
+                        queue.push_back(neighbour as u32);
+                    }
+                }
+            }
+            if size > largest.1 {
+                largest = (start, size);
+            }
+        }
+        if largest.1 < (width * height / 10) as usize {
+            return Err(MinionError::InvalidConfig {
+                reason: format!(
+                    "Cave fill {} left too little open floor, try a lower value",
+                    config.cave_fill
+                ),
+            });
+        }
+        for (floor, label) in self.floor.iter_mut().zip(&label) {
+            *floor = *label == largest.0;
+        }
+
+        self.rooms = self.cave_chambers(config);
+        Ok(())
+    }
+
+    /// Open chambers in a cave: floor tiles with the most clearance, kept apart
+    fn cave_chambers(&self, config: &DungeonConfig) -> Vec<DungeonRoom> {
+        let clearance = self.wall_clearance();
+        let mut sites: Vec<(u32, usize)> = clearance
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| **c >= 2)
+            .map(|(index, c)| (*c, index))
+            .collect();
+        sites.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+
+        let spacing = config.min_room_size.max(4);
+        let mut rooms: Vec<DungeonRoom> = Vec::new();
+        for (clearance, index) in sites {
+            let (x, z) = (index as u32 % self.width, index as u32 / self.width);
+            let apart = rooms.iter().all(|room| {
+                let (cx, cz) = room.center();
+                cx.abs_diff(x).max(cz.abs_diff(z)) >= spacing
+            });
+            if apart {
+                let half = clearance - 1;
+                rooms.push(DungeonRoom {
+                    min_x: x - half,
+                    min_z: z - half,
+                    max_x: x + half,
+                    max_z: z + half,
+                });
+            }
+        }
+
+        if rooms.is_empty() {
+            // Only narrow tunnels survived, start anywhere on the floor
+            let index = self.floor.iter().position(|floor| *floor).unwrap_or(0) as u32;
+            let (x, z) = (index % self.width, index / self.width);
+            rooms.push(DungeonRoom {
+                min_x: x,
+                min_z: z,
+                max_x: x,
+                max_z: z,
+            });
+        }
+        rooms
+    }
+
+    /// Chebyshev distance in tiles from each tile to the nearest solid tile
+    fn wall_clearance(&self) -> Vec<u32> {
+        let (width, height) = (self.width as usize, self.height as usize);
+        let mut clearance: Vec<u32> = self
+            .floor
+            .iter()
+            .map(|floor| if *floor { u32::MAX } else { 0 })
+            .collect();
+
+        // Forward then backward sweep, relaxing through all eight neighbours
+        let cells: Vec<usize> = (0..width * height).collect();
+        for order in [cells.clone(), cells.into_iter().rev().collect()] {
+            for index in order {
+                if clearance[index] == 0 {
+                    continue;
+                }
+                // Floor never touches the border, so neighbours are in bounds
+                let (x, z) = (index % width, index / width);
+                for nz in z - 1..=z + 1 {
+                    for nx in x - 1..=x + 1 {
+                        let through = clearance[nz * width + nx].saturating_add(1);
+                        clearance[index] = clearance[index].min(through);
+                    }
+                }
+            }
+        }
+        clearance
+    }
+
+    /// Walking distance in tiles from `start` to every floor tile, `u32::MAX` if unreachable
+    pub fn floor_distances(&self, start: (u32, u32)) -> Vec<u32> {
+        let mut distances = vec![u32::MAX; self.floor.len()];
+        if !self.is_floor(start.0, start.1) {
+            return distances;
+        }
+        distances[self.index(start.0, start.1)] = 0;
+        let mut queue = VecDeque::from([start]);
+        while let Some((x, z)) = queue.pop_front() {
+            let next = distances[self.index(x, z)] + 1;
+            for (nx, nz) in [
+                (x.wrapping_sub(1), z),
+                (x + 1, z),
+                (x, z.wrapping_sub(1)),
+                (x, z + 1),
+            ] {
+                if self.is_floor(nx, nz) && distances[self.index(nx, nz)] == u32::MAX {
+                    distances[self.index(nx, nz)] = next;
+                    queue.push_back((nx, nz));
+                }
+            }
+        }
+        distances
+    }
+
+    fn farthest_room(&self, distances: &[u32]) -> usize {
+        (0..self.rooms.len())
+            .max_by_key(|&room| {
+                let (x, z) = self.rooms[room].center();
+                let distance = distances[self.index(x, z)];
+                // Ties go to the earlier room for determinism
+                (distance != u32::MAX, distance, std::cmp::Reverse(room))
+            })
+            .unwrap_or(0)
+    }
+
+    /// Solid tiles touching the floor, merged into rectangles
+    pub fn wall_rectangles(&self) -> Vec<DungeonRoom> {
+        let is_wall = |x: u32, z: u32| {
+            !self.is_floor(x, z)
+                && (z.saturating_sub(1)..=(z + 1).min(self.height - 1)).any(|nz| {
+                    (x.saturating_sub(1)..=(x + 1).min(self.width - 1))
+                        .any(|nx| self.is_floor(nx, nz))
+                })
+        };
+
+        let mut used = vec![false; self.floor.len()];
+        let mut rectangles = Vec::new();
+        for z in 0..self.height {
+            for x in 0..self.width {
+                if used[self.index(x, z)] || !is_wall(x, z) {
+                    continue;
+                }
+                // Grow along the row, then down while every tile of the span fits
+                let mut max_x = x;
+                while max_x + 1 < self.width
+                    && !used[self.index(max_x + 1, z)]
+                    && is_wall(max_x + 1, z)
+                {
+                    max_x += 1;
+                }
+                let mut max_z = z;
+                while max_z + 1 < self.height
+                    && (x..=max_x)
+                        .all(|nx| !used[self.index(nx, max_z + 1)] && is_wall(nx, max_z + 1))
+                {
+                    max_z += 1;
+                }
+                for wz in z..=max_z {
+                    for wx in x..=max_x {
+                        let index = self.index(wx, wz);
+                        used[index] = true;
+                    }
+                }
+                rectangles.push(DungeonRoom {
+                    min_x: x,
+                    min_z: z,
+                    max_x,
+                    max_z,
+                });
+            }
+        }
+        rectangles
+    }
+
+    /// Build the map: flat floor, wall objects, entrance spawn, and spawn zones
+    pub fn into_map(
+        self,
+        name: String,
+        scale: f32,
+        config: &DungeonConfig,
+        difficulty: &DifficultyConfig,
+    ) -> MinionResult<MapDefinition> {
+        let terrain =
+            TerrainData::create_flat(self.width, self.height, scale, config.floor_height)?;
+        let tile_to_world = |x: f32, z: f32| {
+            Vec3::new(
+                x * scale - self.width as f32 * scale / 2.0,
+                config.floor_height,
+                z * scale - self.height as f32 * scale / 2.0,
+            )
+        };
+
+        // Walls stop just short of the tile edge so neighbouring floor stays walkable
+        let inset = scale * 0.1;
+        let walls = self
+            .wall_rectangles()
+            .into_iter()
+            .map(|wall| {
+                let center = tile_to_world(
+                    (wall.min_x + wall.max_x) as f32 / 2.0,
+                    (wall.min_z + wall.max_z) as f32 / 2.0,
+                );
+                let size = Vec3::new(
+                    (wall.max_x - wall.min_x + 1) as f32 * scale - inset,
+                    config.wall_height,
+                    (wall.max_z - wall.min_z + 1) as f32 * scale - inset,
+                );
+                EnvironmentObject::new(
+                    WALL_OBJECT_TYPE.to_string(),
+                    center + Vec3::Y * config.wall_height / 2.0,
+                    Vec3::ZERO,
+                    size,
+                )
+            })
+            .collect();
+
+        let (entrance_x, entrance_z) = self.rooms[self.entrance].center();
+        let player_spawn = tile_to_world(entrance_x as f32, entrance_z as f32) + Vec3::Y;
+        let enemy_zones = self.spawn_zones(scale, config, difficulty, tile_to_world)?;
+
+        MapDefinition::new(name, terrain, player_spawn, enemy_zones, walls)
+    }
+
+    /// Spawn zones in rooms spread along the route away from the entrance,
+    /// rated by walking distance with the same tiers as outdoor maps
+    fn spawn_zones(
+        &self,
+        scale: f32,
+        config: &DungeonConfig,
+        difficulty: &DifficultyConfig,
+        tile_to_world: impl Fn(f32, f32) -> Vec3,
+    ) -> MinionResult<Vec<SpawnZone>> {
+        let distances = self.floor_distances(self.rooms[self.entrance].center());
+        let mut rooms: Vec<(f32, &DungeonRoom)> = self
+            .rooms
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| *index != self.entrance)
+            .map(|(_, room)| {
+                let (x, z) = room.center();
+                (distances[self.index(x, z)], room)
+            })
+            .filter(|(distance, _)| *distance != u32::MAX)
+            .map(|(distance, room)| (distance as f32 * scale, room))
+            .collect();
+        rooms.sort_by(|(a, room_a), (b, room_b)| {
+            a.total_cmp(b).then(room_a.center().cmp(&room_b.center()))
+        });
+
+        // Rooms inside the safe radius get no tier and stay empty
+        let farthest = rooms.last().map_or(0.0, |(distance, _)| *distance);
+        let rated: Vec<(u32, &DungeonRoom)> = rooms
+            .into_iter()
+            .filter_map(|(distance, room)| {
+                difficulty
+                    .tier_at(distance, farthest)
+                    .map(|tier| (tier, room))
+            })
+            .collect();
+
+        let count = (config.spawn_zones as usize).min(rated.len());
+        (0..count)
+            .map(|i| {
+                // Evenly spaced through the route, always including the deepest room
+                let (tier, room) = rated[rated.len() - 1 - i * rated.len() / count];
+                let spec = &difficulty.tiers[tier as usize];
+                let (x, z) = room.center();
+                let radius = (room.min_side() as f32 * scale / 2.0 - scale).clamp(1.0, 100.0);
+                SpawnZone::new(
+                    tile_to_world(x as f32, z as f32) + Vec3::Y,
+                    radius,
+                    spec.max_enemies,
+                    difficulty.enemy_types_for(tier),
+                )
+                .map(|zone| zone.with_tier(tier).with_stats(spec.stats))
+            })
+            .collect()
+    }
+}
+
+/// Generate a dungeon map of `width` x `height` tiles, `scale` world units each
+pub fn generate_dungeon(
+    name: String,
+    width: u32,
+    height: u32,
+    scale: f32,
+    config: &DungeonConfig,
+    difficulty: &DifficultyConfig,
+    seed: u64,
+) -> MinionResult<MapDefinition> {
+    DungeonLayout::generate(width, height, config, seed)?.into_map(name, scale, config, difficulty)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pathfinding::{NavigationGrid, PathfindingConfig, find_path};
+
+    fn assert_connected(layout: &DungeonLayout) {
+        let distances = layout.floor_distances(layout.rooms[layout.entrance].center());
+        for (floor, distance) in layout.floor.iter().zip(&distances) {
+            assert_eq!(*floor, *distance != u32::MAX);
+        }
+    }
+
+    #[test]
+    fn test_rooms_are_connected() {
+        let layout = DungeonLayout::generate(64, 48, &DungeonConfig::default(), 3).unwrap();
+
+        assert!(layout.rooms.len() >= 8);
+        assert_connected(&layout);
+        // Border tiles stay solid
+        assert!((0..64).all(|x| !layout.is_floor(x, 0) && !layout.is_floor(x, 47)));
+    }
+
+    #[test]
+    fn test_caves_are_connected() {
+        let config = DungeonConfig {
+            style: DungeonStyle::Caves,
+            ..Default::default()
+        };
+        let layout = DungeonLayout::generate(64, 64, &config, 11).unwrap();
+
+        assert!(!layout.rooms.is_empty());
+        assert_connected(&layout);
+    }
+
+    #[test]
+    fn test_walls_enclose_floor() {
+        let layout = DungeonLayout::generate(48, 48, &DungeonConfig::default(), 5).unwrap();
+        let mut covered = vec![0; layout.floor.len()];
+        for wall in layout.wall_rectangles() {
+            for z in wall.min_z..=wall.max_z {
+                for x in wall.min_x..=wall.max_x {
+                    covered[layout.index(x, z)] += 1;
+                }
+            }
+        }
+
+        for z in 0..48 {
+            for x in 0..48 {
+                let index = layout.index(x, z);
+                if layout.floor[index] {
+                    assert_eq!(covered[index], 0);
+                    // Every floor neighbour is floor or wall, never open void
+                    for (nx, nz) in [(x - 1, z), (x + 1, z), (x, z - 1), (x, z + 1)] {
+                        let neighbour = layout.index(nx, nz);
+                        assert!(layout.floor[neighbour] || covered[neighbour] == 1);
+                    }
+                } else {
+                    assert!(covered[index] <= 1);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_dungeon_map_is_walkable() {
+        let config = DungeonConfig::default();
+        let difficulty = DifficultyConfig::default();
+        let map =
+            generate_dungeon("crypt".to_string(), 64, 64, 1.0, &config, &difficulty, 21).unwrap();
+
+        assert!(!map.enemy_zones.is_empty());
+        assert!(
+            map.environment_objects
+                .iter()
+                .all(|object| object.object_type == WALL_OBJECT_TYPE)
+        );
+
+        let grid = NavigationGrid::from_terrain_and_objects(
+            &map.terrain,
+            &map.environment_objects,
+            PathfindingConfig::default(),
+        )
+        .unwrap();
+        for zone in &map.enemy_zones {
+            assert!(find_path(&grid, map.player_spawn, zone.center, 0.0).is_some());
+        }
+
+        // The deepest room comes first and uses the deepest tier's settings
+        let deepest = difficulty.tiers.last().unwrap();
+        assert_eq!(map.enemy_zones[0].tier, 2);
+        assert_eq!(map.enemy_zones[0].max_enemies, deepest.max_enemies);
+        assert_eq!(map.enemy_zones[0].stats, deepest.stats);
+        for zone in &map.enemy_zones {
+            let spec = &difficulty.tiers[zone.tier as usize];
+            assert_eq!(zone.max_enemies, spec.max_enemies);
+            assert_eq!(zone.enemy_types, difficulty.enemy_types_for(zone.tier));
+            assert!(zone.center.xz().distance(map.player_spawn.xz()) >= difficulty.safe_radius);
+        }
+    }
+
+    #[test]
+    fn test_dungeon_is_seeded() {
+        let config = DungeonConfig::default();
+        let a = DungeonLayout::generate(40, 40, &config, 9).unwrap();
+        let b = DungeonLayout::generate(40, 40, &config, 9).unwrap();
+        let c = DungeonLayout::generate(40, 40, &config, 10).unwrap();
+
+        assert_eq!(a.floor, b.floor);
+        assert_eq!(a.rooms, b.rooms);
+        assert_ne!(a.floor, c.floor);
+    }
+}
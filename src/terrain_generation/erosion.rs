@@ -0,0 +1,302 @@
+//! Particle-based hydraulic erosion.
+//!
+//! Simulates water droplets that roll downhill, picking up sediment while
+//! they speed up and dropping it where they slow down or evaporate. Besides
+//! modifying the heightmap, the pass records where sediment settled and how
+//! much water flowed through each cell, which is useful for placing rivers
+//! and fertile biomes.
+
+use crate::game_logic::errors::{MinionError, MinionResult};
+use crate::map::TerrainData;
+use rand::{Rng, SeedableRng};
+use rand_pcg::Pcg64;
+use serde::{Deserialize, Serialize};
+
+/// Tuning for the hydraulic erosion pass
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HydraulicErosionConfig {
+    /// Number of droplets to simulate
+    pub iterations: u32,
+    /// How much a droplet keeps its direction instead of following the slope (0-1)
+    pub inertia: f32,
+    /// Sediment a droplet can carry per unit of speed, water, and descent
+    pub sediment_capacity: f32,
+    pub min_sediment_capacity: f32,
+    /// Fraction of spare capacity eroded per step (0-1)
+    pub erode_speed: f32,
+    /// Fraction of excess sediment deposited per step (0-1)
+    pub deposit_speed: f32,
+    /// Fraction of water lost per step (0-1)
+    pub evaporation: f32,
+    pub gravity: f32,
+    /// Maximum steps a droplet lives for
+    pub max_lifetime: u32,
+    pub initial_water: f32,
+    pub initial_speed: f32,
+}
+
+impl Default for HydraulicErosionConfig {
+    fn default() -> Self {
+        Self {
+            iterations: 50_000,          // Enough to carve gullies on a 256x256 map
+            inertia: 0.05,               // Mostly follow the slope
+            sediment_capacity: 4.0,      // Carries enough to visibly move material
+            min_sediment_capacity: 0.01, // Keeps eroding slightly on gentle slopes
+            erode_speed: 0.3,
+            deposit_speed: 0.3,
+            evaporation: 0.01,
+            gravity: 4.0,
+            max_lifetime: 30, // Droplets rarely travel further before evaporating
+            initial_water: 1.0,
+            initial_speed: 1.0,
+        }
+    }
+}
+
+impl HydraulicErosionConfig {
+    /// Check that rates are in range
+    pub fn validate(&self) -> MinionResult<()> {
+        let unit_ranges = [
+            ("inertia", self.inertia),
+            ("erode_speed", self.erode_speed),
+            ("deposit_speed", self.deposit_speed),
+            ("evaporation", self.evaporation),
+        ];
+        for (name, value) in unit_ranges {
+            if !(0.0..=1.0).contains(&value) {
+                return Err(MinionError::InvalidConfig {
+                    reason: format!("Erosion {name} must be between 0.0 and 1.0, got {value}"),
+                });
+            }
+        }
+        if self.sediment_capacity < 0.0 || self.min_sediment_capacity < 0.0 {
+            return Err(MinionError::InvalidConfig {
+                reason: "Erosion sediment capacity must be non-negative".to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Per-cell byproducts of an erosion pass, in the same layout as the heightmap
+#[derive(Debug, Clone, Default)]
+pub struct ErosionMaps {
+    pub width: u32,
+    pub height: u32,
+    /// Total sediment deposited in each cell
+    pub sediment: Vec<f32>,
+    /// Total water that passed through each cell
+    pub flow: Vec<f32>,
+}
+
+impl ErosionMaps {
+    fn new(width: u32, height: u32) -> Self {
+        let cells = (width * height) as usize;
+        Self {
+            width,
+            height,
+            sediment: vec![0.0; cells],
+            flow: vec![0.0; cells],
+        }
+    }
+}
+
+/// Height and downhill gradient at a fractional grid position
+fn height_and_gradient(terrain: &TerrainData, x: f32, y: f32) -> (f32, f32, f32) {
+    let cell_x = x.floor() as u32;
+    let cell_y = y.floor() as u32;
+    let fx = x - cell_x as f32;
+    let fy = y - cell_y as f32;
+
+    let index = (cell_y * terrain.width + cell_x) as usize;
+    let nw = terrain.heights[index];
+    let ne = terrain.heights[index + 1];
+    let sw = terrain.heights[index + terrain.width as usize];
+    let se = terrain.heights[index + terrain.width as usize + 1];
+
+    let gradient_x = (ne - nw) * (1.0 - fy) + (se - sw) * fy;
+    let gradient_y = (sw - nw) * (1.0 - fx) + (se - ne) * fx;
+    let height =
+        nw * (1.0 - fx) * (1.0 - fy) + ne * fx * (1.0 - fy) + sw * (1.0 - fx) * fy + se * fx * fy;
+
+    (height, gradient_x, gradient_y)
+}
+
+/// Bilinear weights for the four corners of the cell containing (x, y)
+fn corner_weights(terrain: &TerrainData, x: f32, y: f32) -> [(usize, f32); 4] {
+    let cell_x = x.floor() as u32;
+    let cell_y = y.floor() as u32;
+    let fx = x - cell_x as f32;
+    let fy = y - cell_y as f32;
+    let index = (cell_y * terrain.width + cell_x) as usize;
+    let row = terrain.width as usize;
+
+    [
+        (index, (1.0 - fx) * (1.0 - fy)),
+        (index + 1, fx * (1.0 - fy)),
+        (index + row, (1.0 - fx) * fy),
+        (index + row + 1, fx * fy),
+    ]
+}
+
+/// Erode `terrain` in place with simulated rain droplets
+pub fn hydraulic_erosion(
+    terrain: &mut TerrainData,
+    config: &HydraulicErosionConfig,
+    seed: u64,
+) -> ErosionMaps {
+    let mut maps = ErosionMaps::new(terrain.width, terrain.height);
+    if terrain.width < 2 || terrain.height < 2 {
+        return maps;
+    }
+
+    let mut rng = Pcg64::seed_from_u64(seed);
+    let max_x = (terrain.width - 1) as f32;
+    let max_y = (terrain.height - 1) as f32;
+
+    for _ in 0..config.iterations {
+        let mut x = rng.gen_range(0.0..max_x);
+        let mut y = rng.gen_range(0.0..max_y);
+        let mut direction_x = 0.0;
+        let mut direction_y = 0.0;
+        let mut speed = config.initial_speed;
+        let mut water = config.initial_water;
+        let mut sediment = 0.0;
+
+        for _ in 0..config.max_lifetime {
+            let weights = corner_weights(terrain, x, y);
+            let (height, gradient_x, gradient_y) = height_and_gradient(terrain, x, y);
+
+            // Blend the previous direction with the downhill direction
+            direction_x = direction_x * config.inertia - gradient_x * (1.0 - config.inertia);
+            direction_y = direction_y * config.inertia - gradient_y * (1.0 - config.inertia);
+            let length = (direction_x * direction_x + direction_y * direction_y).sqrt();
+            if length <= f32::EPSILON {
+                break;
+            }
+            direction_x /= length;
+            direction_y /= length;
+
+            x += direction_x;
+            y += direction_y;
+            if !(0.0..max_x).contains(&x) || !(0.0..max_y).contains(&y) {
+                break;
+            }
+
+            for (index, weight) in weights {
+                maps.flow[index] += water * weight;
+            }
+
+            let (new_height, _, _) = height_and_gradient(terrain, x, y);
+            let delta_height = new_height - height;
+            let capacity = (-delta_height * speed * water * config.sediment_capacity)
+                .max(config.min_sediment_capacity);
+
+            if sediment > capacity || delta_height > 0.0 {
+                // Fill the pit when moving uphill, otherwise drop the excess
+                let deposit = if delta_height > 0.0 {
+                    delta_height.min(sediment)
+                } else {
+                    (sediment - capacity) * config.deposit_speed
+                };
+                sediment -= deposit;
+                for (index, weight) in weights {
+                    terrain.heights[index] += deposit * weight;
+                    maps.sediment[index] += deposit * weight;
+                }
+            } else {
+                // Never dig deeper than the height difference to avoid spikes
+                let erode = ((capacity - sediment) * config.erode_speed).min(-delta_height);
+                for (index, weight) in weights {
+                    terrain.heights[index] -= erode * weight;
+                }
+                sediment += erode;
+            }
+
+            speed = (speed * speed - delta_height * config.gravity)
+                .max(0.0)
+                .sqrt();
+            water *= 1.0 - config.evaporation;
+        }
+    }
+
+    maps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A cone with some ripples so droplets have channels to follow
+    fn cone(size: u32) -> TerrainData {
+        let center = size as f32 / 2.0;
+        let heights = (0..size * size)
+            .map(|i| {
+                let x = (i % size) as f32;
+                let y = (i / size) as f32;
+                let distance = ((x - center).powi(2) + (y - center).powi(2)).sqrt();
+                (center - distance).max(0.0) + (x * 0.7).sin() * 0.3
+            })
+            .collect();
+        TerrainData::new(size, size, heights, 1.0).unwrap()
+    }
+
+    fn config(iterations: u32) -> HydraulicErosionConfig {
+        HydraulicErosionConfig {
+            iterations,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_erosion_is_deterministic() {
+        let mut first = cone(32);
+        let mut second = cone(32);
+        let maps_a = hydraulic_erosion(&mut first, &config(2_000), 9);
+        let maps_b = hydraulic_erosion(&mut second, &config(2_000), 9);
+
+        assert_eq!(first.heights, second.heights);
+        assert_eq!(maps_a.flow, maps_b.flow);
+
+        let mut other_seed = cone(32);
+        hydraulic_erosion(&mut other_seed, &config(2_000), 10);
+        assert_ne!(first.heights, other_seed.heights);
+    }
+
+    #[test]
+    fn test_erosion_wears_down_peaks() {
+        let original = cone(32);
+        let mut eroded = original.clone();
+        let maps = hydraulic_erosion(&mut eroded, &config(5_000), 1);
+
+        let peak = |t: &TerrainData| t.heights.iter().copied().fold(f32::MIN, f32::max);
+        assert!(peak(&eroded) < peak(&original));
+        assert!(eroded.heights.iter().all(|h| h.is_finite()));
+
+        assert!(maps.flow.iter().any(|&f| f > 0.0));
+        assert!(maps.sediment.iter().all(|&s| s >= 0.0));
+        assert!(maps.sediment.iter().any(|&s| s > 0.0));
+    }
+
+    #[test]
+    fn test_zero_iterations_leaves_terrain_untouched() {
+        let original = cone(16);
+        let mut terrain = original.clone();
+        let maps = hydraulic_erosion(&mut terrain, &config(0), 1);
+
+        assert_eq!(terrain.heights, original.heights);
+        assert_eq!(maps.flow.len(), terrain.heights.len());
+    }
+
+    #[test]
+    fn test_config_validation() {
+        assert!(HydraulicErosionConfig::default().validate().is_ok());
+
+        let bad_inertia = HydraulicErosionConfig {
+            inertia: 1.5,
+            ..Default::default()
+        };
+        assert!(bad_inertia.validate().is_err());
+    }
+}
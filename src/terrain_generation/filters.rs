@@ -0,0 +1,499 @@
+//! Heightmap post-processing filters.
+//!
+//! Filters reshape an existing `TerrainData` in place and can be chained.
+//! Every filter accepts an optional mask with one weight per height sample
+//! (0 leaves the cell untouched, 1 applies the filter fully), so effects can
+//! be limited to part of a map.
+
+use crate::game_logic::errors::{MinionError, MinionResult};
+use crate::map::TerrainData;
+use serde::{Deserialize, Serialize};
+
+fn default_talus_angle() -> f32 {
+    35.0
+}
+
+fn default_thermal_strength() -> f32 {
+    0.5
+}
+
+fn default_terrace_sharpness() -> f32 {
+    0.7
+}
+
+fn unbounded_min() -> f32 {
+    f32::MIN
+}
+
+fn unbounded_max() -> f32 {
+    f32::MAX
+}
+
+/// A single heightmap filter
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TerrainFilter {
+    /// Collapse slopes steeper than the talus angle, moving material downhill
+    Thermal {
+        iterations: u32,
+        /// Steepest stable slope in degrees
+        #[serde(default = "default_talus_angle")]
+        talus_angle: f32,
+        /// Fraction of the excess moved per iteration (0-1)
+        #[serde(default = "default_thermal_strength")]
+        strength: f32,
+    },
+    /// Quantize heights into stepped plateaus
+    Terrace {
+        steps: u32,
+        /// 0 leaves slopes untouched, 1 produces vertical cliffs between steps
+        #[serde(default = "default_terrace_sharpness")]
+        sharpness: f32,
+    },
+    /// Limit heights to a range
+    Clamp { min: f32, max: f32 },
+    /// Remap heights through a piecewise linear curve of `[input, output]`
+    /// points, both normalized to the terrain's current height range
+    Curve { points: Vec<[f32; 2]> },
+}
+
+/// Soft mask selecting heights inside a band
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HeightBand {
+    #[serde(default = "unbounded_min")]
+    pub min: f32,
+    #[serde(default = "unbounded_max")]
+    pub max: f32,
+    /// Distance over which the mask fades out past the band edges
+    #[serde(default)]
+    pub falloff: f32,
+}
+
+impl HeightBand {
+    /// Mask weight for a single height
+    pub fn weight(&self, height: f32) -> f32 {
+        let outside = if height < self.min {
+            self.min - height
+        } else if height > self.max {
+            height - self.max
+        } else {
+            return 1.0;
+        };
+
+        if self.falloff <= 0.0 {
+            0.0
+        } else {
+            (1.0 - outside / self.falloff).max(0.0)
+        }
+    }
+
+    /// Build a mask for every height sample of `terrain`
+    pub fn mask(&self, terrain: &TerrainData) -> Vec<f32> {
+        terrain.heights.iter().map(|&h| self.weight(h)).collect()
+    }
+}
+
+/// A filter together with the height band it is restricted to
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FilterStep {
+    #[serde(flatten)]
+    pub filter: TerrainFilter,
+    #[serde(default)]
+    pub mask: Option<HeightBand>,
+}
+
+impl TerrainFilter {
+    /// Check parameters before running the filter
+    pub fn validate(&self) -> MinionResult<()> {
+        let invalid = |reason: String| Err(MinionError::InvalidConfig { reason });
+
+        match self {
+            TerrainFilter::Thermal {
+                talus_angle,
+                strength,
+                ..
+            } => {
+                if !(0.0..90.0).contains(talus_angle) {
+                    return invalid(format!(
+                        "Talus angle must be between 0 and 90 degrees, got {talus_angle}"
+                    ));
+                }
+                if !(0.0..=1.0).contains(strength) {
+                    return invalid(format!(
+                        "Thermal strength must be between 0.0 and 1.0, got {strength}"
+                    ));
+                }
+            }
+            TerrainFilter::Terrace { steps, sharpness } => {
+                if *steps == 0 {
+                    return invalid("Terrace steps must be greater than 0".to_string());
+                }
+                if !(0.0..=1.0).contains(sharpness) {
+                    return invalid(format!(
+                        "Terrace sharpness must be between 0.0 and 1.0, got {sharpness}"
+                    ));
+                }
+            }
+            TerrainFilter::Clamp { min, max } => {
+                if min > max {
+                    return invalid(format!("Clamp minimum {min} is above maximum {max}"));
+                }
+            }
+            TerrainFilter::Curve { points } => {
+                if points.len() < 2 {
+                    return invalid("Curves need at least two points".to_string());
+                }
+                if points.windows(2).any(|pair| pair[0][0] >= pair[1][0]) {
+                    return invalid("Curve inputs must be strictly increasing".to_string());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply the filter, blending with the original heights by `mask`
+    pub fn apply(&self, terrain: &mut TerrainData, mask: Option<&[f32]>) -> MinionResult<()> {
+        self.validate()?;
+        if let Some(mask) = mask.filter(|mask| mask.len() != terrain.heights.len()) {
+            return Err(MinionError::InvalidTerrainData {
+                reason: format!(
+                    "Filter mask has {} values but terrain has {}",
+                    mask.len(),
+                    terrain.heights.len()
+                ),
+            });
+        }
+
+        match self {
+            TerrainFilter::Thermal {
+                iterations,
+                talus_angle,
+                strength,
+            } => thermal_erosion(terrain, *iterations, *talus_angle, *strength, mask),
+            TerrainFilter::Terrace { steps, sharpness } => {
+                let (low, high) = height_range(terrain);
+                map_heights(terrain, mask, |h| terrace(h, low, high, *steps, *sharpness));
+            }
+            TerrainFilter::Clamp { min, max } => {
+                map_heights(terrain, mask, |h| h.clamp(*min, *max));
+            }
+            TerrainFilter::Curve { points } => {
+                let (low, high) = height_range(terrain);
+                let span = (high - low).max(f32::EPSILON);
+                map_heights(terrain, mask, |h| {
+                    low + evaluate_curve(points, (h - low) / span) * span
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Run filter steps in order, building each mask from the terrain as it is at that step
+pub fn apply_filters(terrain: &mut TerrainData, steps: &[FilterStep]) -> MinionResult<()> {
+    for step in steps {
+        let mask = step.mask.map(|band| band.mask(terrain));
+        step.filter.apply(terrain, mask.as_deref())?;
+    }
+    Ok(())
+}
+
+fn height_range(terrain: &TerrainData) -> (f32, f32) {
+    terrain
+        .heights
+        .iter()
+        .fold((f32::MAX, f32::MIN), |(low, high), &h| {
+            (low.min(h), high.max(h))
+        })
+}
+
+fn map_heights(terrain: &mut TerrainData, mask: Option<&[f32]>, filter: impl Fn(f32) -> f32) {
+    for (i, height) in terrain.heights.iter_mut().enumerate() {
+        let weight = mask.map_or(1.0, |m| m[i].clamp(0.0, 1.0));
+        *height = *height * (1.0 - weight) + filter(*height) * weight;
+    }
+}
+
+fn terrace(height: f32, low: f32, high: f32, steps: u32, sharpness: f32) -> f32 {
+    let span = high - low;
+    if span <= f32::EPSILON {
+        return height;
+    }
+
+    let scaled = (height - low) / span * steps as f32;
+    let step = scaled.floor().min(steps as f32 - 1.0);
+    let within = scaled - step;
+    // Raising the fraction to a power keeps each step flat until it rises near the next
+    let shaped = if sharpness >= 1.0 {
+        0.0
+    } else {
+        within.powf(1.0 / (1.0 - sharpness))
+    };
+
+    low + (step + shaped) / steps as f32 * span
+}
+
+fn evaluate_curve(points: &[[f32; 2]], input: f32) -> f32 {
+    let first = points[0];
+    let last = points[points.len() - 1];
+    if input <= first[0] {
+        return first[1];
+    }
+    if input >= last[0] {
+        return last[1];
+    }
+
+    points
+        .windows(2)
+        .find(|pair| input <= pair[1][0])
+        .map(|pair| {
+            let t = (input - pair[0][0]) / (pair[1][0] - pair[0][0]);
+            pair[0][1] + (pair[1][1] - pair[0][1]) * t
+        })
+        .unwrap_or(last[1])
+}
+
+/// Move material from cells steeper than the talus angle to their lower neighbors
+fn thermal_erosion(
+    terrain: &mut TerrainData,
+    iterations: u32,
+    talus_angle: f32,
+    strength: f32,
+    mask: Option<&[f32]>,
+) {
+    let width = terrain.width as i32;
+    let height = terrain.height as i32;
+    let talus = talus_angle.to_radians().tan() * terrain.scale;
+    let mut deltas = vec![0.0; terrain.heights.len()];
+
+    for _ in 0..iterations {
+        deltas.iter_mut().for_each(|d| *d = 0.0);
+
+        for y in 0..height {
+            for x in 0..width {
+                let index = (y * width + x) as usize;
+                let weight = mask.map_or(1.0, |m| m[index].clamp(0.0, 1.0));
+                if weight <= 0.0 {
+                    continue;
+                }
+
+                let center = terrain.heights[index];
+                let mut lower = [(0usize, 0.0f32); 4];
+                let mut count = 0;
+                let mut total_excess = 0.0;
+                let mut max_excess = 0.0f32;
+
+                for (dx, dy) in [(1, 0), (-1, 0), (0, 1), (0, -1)] {
+                    let (nx, ny) = (x + dx, y + dy);
+                    if nx < 0 || ny < 0 || nx >= width || ny >= height {
+                        continue;
+                    }
+                    let neighbor = (ny * width + nx) as usize;
+                    let excess = center - terrain.heights[neighbor] - talus;
+                    if excess > 0.0 {
+                        lower[count] = (neighbor, excess);
+                        count += 1;
+                        total_excess += excess;
+                        max_excess = max_excess.max(excess);
+                    }
+                }
+
+                if count == 0 {
+                    continue;
+                }
+
+                // Move half the excess at most so the slope settles instead of flipping
+                let moved = max_excess * 0.5 * strength * weight;
+                deltas[index] -= moved;
+                for &(neighbor, excess) in &lower[..count] {
+                    deltas[neighbor] += moved * excess / total_excess;
+                }
+            }
+        }
+
+        for (height, delta) in terrain.heights.iter_mut().zip(&deltas) {
+            *height += delta;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ramp(width: u32, step: f32) -> TerrainData {
+        let heights = (0..width * 4).map(|i| (i % width) as f32 * step).collect();
+        TerrainData::new(width, 4, heights, 1.0).unwrap()
+    }
+
+    fn max_slope(terrain: &TerrainData) -> f32 {
+        let width = terrain.width as usize;
+        terrain
+            .heights
+            .chunks(width)
+            .flat_map(|row| row.windows(2).map(|pair| (pair[1] - pair[0]).abs()))
+            .fold(0.0, f32::max)
+    }
+
+    #[test]
+    fn test_thermal_erosion_flattens_steep_slopes() {
+        let mut terrain = ramp(16, 3.0);
+        let total_before: f32 = terrain.heights.iter().sum();
+
+        TerrainFilter::Thermal {
+            iterations: 200,
+            talus_angle: 45.0,
+            strength: 1.0,
+        }
+        .apply(&mut terrain, None)
+        .unwrap();
+
+        assert!(max_slope(&terrain) < 3.0);
+        // Material is moved, not destroyed
+        let total_after: f32 = terrain.heights.iter().sum();
+        assert!((total_before - total_after).abs() < 0.01 * total_before);
+    }
+
+    #[test]
+    fn test_thermal_erosion_leaves_gentle_slopes() {
+        let original = ramp(16, 0.5);
+        let mut terrain = original.clone();
+        TerrainFilter::Thermal {
+            iterations: 50,
+            talus_angle: 45.0,
+            strength: 1.0,
+        }
+        .apply(&mut terrain, None)
+        .unwrap();
+
+        assert_eq!(terrain.heights, original.heights);
+    }
+
+    #[test]
+    fn test_terrace_creates_plateaus() {
+        let mut terrain = ramp(64, 0.25);
+        TerrainFilter::Terrace {
+            steps: 4,
+            sharpness: 1.0,
+        }
+        .apply(&mut terrain, None)
+        .unwrap();
+
+        let mut levels: Vec<f32> = terrain.heights.clone();
+        levels.sort_by(f32::total_cmp);
+        levels.dedup();
+        assert_eq!(levels.len(), 4);
+        assert_eq!(levels[0], 0.0);
+    }
+
+    #[test]
+    fn test_clamp_and_curve() {
+        let mut terrain = ramp(11, 1.0);
+        TerrainFilter::Clamp { min: 2.0, max: 8.0 }
+            .apply(&mut terrain, None)
+            .unwrap();
+        assert_eq!(terrain.heights[0], 2.0);
+        assert_eq!(terrain.heights[10], 8.0);
+
+        // Square-ish curve pushes mid heights down while keeping the range
+        TerrainFilter::Curve {
+            points: vec![[0.0, 0.0], [0.5, 0.25], [1.0, 1.0]],
+        }
+        .apply(&mut terrain, None)
+        .unwrap();
+        assert_eq!(terrain.heights[0], 2.0);
+        assert_eq!(terrain.heights[10], 8.0);
+        assert!((terrain.heights[5] - 3.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_mask_limits_filter() {
+        let mut terrain = ramp(11, 1.0);
+        let band = HeightBand {
+            min: 5.0,
+            max: f32::MAX,
+            falloff: 0.0,
+        };
+        apply_filters(
+            &mut terrain,
+            &[FilterStep {
+                filter: TerrainFilter::Clamp { min: 0.0, max: 6.0 },
+                mask: Some(band),
+            }],
+        )
+        .unwrap();
+
+        assert_eq!(terrain.heights[4], 4.0);
+        assert_eq!(terrain.heights[10], 6.0);
+
+        let wrong_size = [1.0; 3];
+        let clamp = TerrainFilter::Clamp { min: 0.0, max: 1.0 };
+        assert!(clamp.apply(&mut terrain, Some(&wrong_size)).is_err());
+    }
+
+    #[test]
+    fn test_height_band_falloff() {
+        let band = HeightBand {
+            min: 0.0,
+            max: 10.0,
+            falloff: 2.0,
+        };
+        assert_eq!(band.weight(5.0), 1.0);
+        assert_eq!(band.weight(11.0), 0.5);
+        assert_eq!(band.weight(-3.0), 0.0);
+    }
+
+    #[test]
+    fn test_invalid_filters_rejected() {
+        let mut terrain = ramp(4, 1.0);
+        let bad = [
+            TerrainFilter::Terrace {
+                steps: 0,
+                sharpness: 0.5,
+            },
+            TerrainFilter::Clamp { min: 2.0, max: 1.0 },
+            TerrainFilter::Curve {
+                points: vec![[0.5, 0.0], [0.2, 1.0]],
+            },
+            TerrainFilter::Thermal {
+                iterations: 1,
+                talus_angle: 95.0,
+                strength: 0.5,
+            },
+        ];
+        for filter in bad {
+            assert!(filter.apply(&mut terrain, None).is_err(), "{filter:?}");
+        }
+    }
+
+    #[test]
+    fn test_filter_steps_deserialize() {
+        #[derive(Deserialize)]
+        struct Steps {
+            filter: Vec<FilterStep>,
+        }
+
+        let steps: Steps = toml::from_str(
+            r#"
+            [[filter]]
+            type = "terrace"
+            steps = 5
+
+            [[filter]]
+            type = "thermal"
+            iterations = 20
+            mask = { min = 10.0, falloff = 2.0 }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            steps.filter[0].filter,
+            TerrainFilter::Terrace {
+                steps: 5,
+                sharpness: 0.7
+            }
+        );
+        assert_eq!(steps.filter[1].mask.unwrap().max, f32::MAX);
+    }
+}
@@ -0,0 +1,442 @@
+//! Rivers, lakes, and seas carved into a heightmap.
+//!
+//! Drainage is computed with a priority flood from the map edges, which gives
+//! every cell a downstream neighbor even inside pits and flats. Pits the flood
+//! has to fill become lakes, rivers follow the drainage from high sources down
+//! to the edge, a lake, or the sea, and their channels are carved into the
+//! terrain along a monotonic downhill profile. When an erosion pass ran first,
+//! rivers start from the sources its droplets drained most water through.
+
+use super::{ErosionMaps, QueuedCell};
+use crate::map::{TerrainData, WaterBody, WaterKind};
+use rand::SeedableRng;
+use rand::seq::SliceRandom;
+use rand_pcg::Pcg64;
+use std::collections::{BinaryHeap, VecDeque};
+
+/// Tuning for the hydrology pass
+#[derive(Debug, Clone, PartialEq)]
+pub struct HydrologyConfig {
+    /// Number of rivers to trace
+    pub rivers: u32,
+    /// Rivers start above this fraction of the terrain's height range (0-1)
+    pub source_height: f32,
+    /// Rivers shorter than this many cells are discarded
+    pub min_river_length: u32,
+    /// Channel depth at the river mouth in world units
+    pub channel_depth: f32,
+    /// Channel half-width in world units
+    pub channel_radius: f32,
+    /// Keep filled depressions as lakes
+    pub lakes: bool,
+    /// Depressions shallower than this are left dry
+    pub min_lake_depth: f32,
+    pub min_lake_cells: u32,
+    /// Everything below this height becomes sea
+    pub sea_level: Option<f32>,
+}
+
+impl Default for HydrologyConfig {
+    fn default() -> Self {
+        Self {
+            rivers: 3,
+            source_height: 0.6, // Upper slopes
+            min_river_length: 12,
+            channel_depth: 1.0,
+            channel_radius: 1.5,
+            lakes: true,
+            min_lake_depth: 0.3, // Ignore puddles from noise
+            min_lake_cells: 6,
+            sea_level: None, // Inland map
+        }
+    }
+}
+
+fn neighbors(width: usize, height: usize, index: usize) -> impl Iterator<Item = usize> {
+    let (x, y) = (index % width, index / width);
+    [(1isize, 0isize), (-1, 0), (0, 1), (0, -1)]
+        .into_iter()
+        .filter_map(move |(dx, dy)| {
+            let nx = x.checked_add_signed(dx)?;
+            let ny = y.checked_add_signed(dy)?;
+            (nx < width && ny < height).then_some(ny * width + nx)
+        })
+}
+
+/// Drainage computed by flooding inward from the map edges
+struct Drainage {
+    /// Height with every depression filled to its spill point
+    filled: Vec<f32>,
+    /// Downstream cell, `None` for edge cells that drain off the map
+    receiver: Vec<Option<usize>>,
+}
+
+fn flood(terrain: &TerrainData) -> Drainage {
+    let width = terrain.width as usize;
+    let height = terrain.height as usize;
+    let cells = terrain.heights.len();
+
+    let mut filled = terrain.heights.clone();
+    let mut receiver = vec![None; cells];
+    let mut visited = vec![false; cells];
+    let mut queue = BinaryHeap::new();
+
+    for index in 0..cells {
+        let (x, y) = (index % width, index / width);
+        if x == 0 || y == 0 || x == width - 1 || y == height - 1 {
+            visited[index] = true;
+            queue.push(QueuedCell {
+                cost: filled[index],
+                index,
+            });
+        }
+    }
+
+    while let Some(QueuedCell { index, .. }) = queue.pop() {
+        for neighbor in neighbors(width, height, index) {
+            if visited[neighbor] {
+                continue;
+            }
+            visited[neighbor] = true;
+            filled[neighbor] = filled[neighbor].max(filled[index]);
+            receiver[neighbor] = Some(index);
+            queue.push(QueuedCell {
+                cost: filled[neighbor],
+                index: neighbor,
+            });
+        }
+    }
+
+    Drainage { filled, receiver }
+}
+
+/// Group connected cells matching `include` into components
+fn components(width: usize, height: usize, include: impl Fn(usize) -> bool) -> Vec<Vec<usize>> {
+    let mut seen = vec![false; width * height];
+    let mut groups = Vec::new();
+    let mut queue = VecDeque::new();
+
+    for start in 0..width * height {
+        if seen[start] || !include(start) {
+            continue;
+        }
+        seen[start] = true;
+        queue.push_back(start);
+        let mut group = Vec::new();
+        while let Some(index) = queue.pop_front() {
+            group.push(index);
+            for neighbor in neighbors(width, height, index) {
+                if !seen[neighbor] && include(neighbor) {
+                    seen[neighbor] = true;
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+        groups.push(group);
+    }
+
+    groups
+}
+
+/// Trace rivers, fill lakes, and flood the sea, carving channels into `terrain`
+pub fn generate_hydrology(
+    terrain: &mut TerrainData,
+    config: &HydrologyConfig,
+    seed: u64,
+    erosion: Option<&ErosionMaps>,
+) -> Vec<WaterBody> {
+    let width = terrain.width as usize;
+    let height = terrain.height as usize;
+    if width < 3 || height < 3 {
+        return Vec::new();
+    }
+
+    let drainage = flood(terrain);
+    let mut bodies = Vec::new();
+    // Cells already claimed by a lake or the sea
+    let mut standing = vec![false; terrain.heights.len()];
+
+    if let Some(sea_level) = config.sea_level {
+        for group in components(width, height, |i| terrain.heights[i] < sea_level) {
+            let mut ocean = WaterBody::new(WaterKind::Ocean);
+            for index in group {
+                standing[index] = true;
+                ocean.push(index as u32, sea_level);
+            }
+            bodies.push(ocean);
+        }
+    }
+
+    if config.lakes {
+        let flooded = |i: usize| drainage.filled[i] - terrain.heights[i] > 1e-3;
+        for group in components(width, height, |i| flooded(i) && !standing[i]) {
+            let deepest = group
+                .iter()
+                .map(|&i| drainage.filled[i] - terrain.heights[i])
+                .fold(0.0, f32::max);
+            if deepest < config.min_lake_depth || group.len() < config.min_lake_cells as usize {
+                continue;
+            }
+
+            let mut lake = WaterBody::new(WaterKind::Lake);
+            for index in group {
+                standing[index] = true;
+                lake.push(index as u32, drainage.filled[index]);
+            }
+            bodies.push(lake);
+        }
+    }
+
+    let (low, high) = terrain
+        .heights
+        .iter()
+        .fold((f32::MAX, f32::MIN), |(lo, hi), &h| (lo.min(h), hi.max(h)));
+    let source_min = low + (high - low) * config.source_height.clamp(0.0, 1.0);
+    let mut sources: Vec<usize> = (0..terrain.heights.len())
+        .filter(|&i| terrain.heights[i] >= source_min && !standing[i])
+        .collect();
+    sources.shuffle(&mut Pcg64::seed_from_u64(seed));
+    // Eroded gullies carry the most water; stable sort keeps the shuffle for ties
+    if let Some(maps) = erosion.filter(|maps| maps.flow.len() == terrain.heights.len()) {
+        sources.sort_by(|&a, &b| maps.flow[b].total_cmp(&maps.flow[a]));
+    }
+
+    let mut on_river = vec![false; terrain.heights.len()];
+    let mut paths = Vec::new();
+    for source in sources {
+        if paths.len() >= config.rivers as usize {
+            break;
+        }
+        if on_river[source] {
+            continue;
+        }
+
+        // Follow drainage until leaving the map, reaching water, or joining another river
+        let mut path = vec![source];
+        let mut current = source;
+        while let Some(next) = drainage.receiver[current] {
+            if standing[next] {
+                break;
+            }
+            path.push(next);
+            if on_river[next] {
+                break;
+            }
+            current = next;
+        }
+
+        if path.len() < config.min_river_length as usize {
+            continue;
+        }
+        for &index in &path {
+            on_river[index] = true;
+        }
+        paths.push(path);
+    }
+
+    for path in paths {
+        bodies.push(carve_river(terrain, &path, config));
+    }
+
+    bodies
+}
+
+/// Carve a channel along `path` and return the river's water surface
+fn carve_river(terrain: &mut TerrainData, path: &[usize], config: &HydrologyConfig) -> WaterBody {
+    let width = terrain.width as usize;
+    let height = terrain.height as usize;
+    let radius_cells = (config.channel_radius / terrain.scale).max(0.5);
+    let reach = radius_cells.ceil() as i64;
+
+    // Never let the bed rise downstream
+    let mut profile = Vec::with_capacity(path.len());
+    let mut lowest = f32::MAX;
+    for &index in path {
+        lowest = lowest.min(terrain.heights[index]);
+        profile.push(lowest);
+    }
+
+    let mut river = WaterBody::new(WaterKind::River);
+    let mut claimed = vec![false; terrain.heights.len()];
+    for (step, (&index, &ground)) in path.iter().zip(&profile).enumerate() {
+        // Streams start shallow and deepen toward the mouth
+        let progress = step as f32 / (path.len() - 1).max(1) as f32;
+        let depth = config.channel_depth * (0.4 + 0.6 * progress);
+        let bed = ground - depth;
+        let surface = bed + depth * 0.7;
+
+        let (x, y) = ((index % width) as i64, (index / width) as i64);
+        for dy in -reach..=reach {
+            for dx in -reach..=reach {
+                let (nx, ny) = (x + dx, y + dy);
+                if nx < 0 || ny < 0 || nx >= width as i64 || ny >= height as i64 {
+                    continue;
+                }
+                let distance = ((dx * dx + dy * dy) as f32).sqrt() / radius_cells;
+                if distance > 1.0 {
+                    continue;
+                }
+
+                // Banks rise quadratically from the bed to the surrounding ground
+                let cell = ny as usize * width + nx as usize;
+                let target = bed + depth * distance * distance;
+                terrain.heights[cell] = terrain.heights[cell].min(target);
+
+                if !claimed[cell] && terrain.heights[cell] < surface {
+                    claimed[cell] = true;
+                    river.push(cell as u32, surface);
+                }
+            }
+        }
+    }
+
+    river
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tilted plane with a bowl near the low end
+    fn valley(size: u32) -> TerrainData {
+        let heights = (0..size * size)
+            .map(|i| {
+                let x = (i % size) as f32;
+                let y = (i / size) as f32;
+                let bowl = ((x - 8.0).powi(2) + (y - 8.0).powi(2)).sqrt();
+                y * 0.5 - (3.0 - bowl).max(0.0)
+            })
+            .collect();
+        TerrainData::new(size, size, heights, 1.0).unwrap()
+    }
+
+    #[test]
+    fn test_flood_fills_depressions() {
+        let terrain = valley(24);
+        let drainage = flood(&terrain);
+
+        let bowl = 8 * 24 + 8;
+        assert!(drainage.filled[bowl] > terrain.heights[bowl]);
+        // Every interior cell drains somewhere
+        assert!((0..24 * 24).all(|i| {
+            let (x, y) = (i % 24, i / 24);
+            let edge = x == 0 || y == 0 || x == 23 || y == 23;
+            edge || drainage.receiver[i].is_some()
+        }));
+    }
+
+    #[test]
+    fn test_lake_fills_bowl() {
+        let mut terrain = valley(24);
+        let config = HydrologyConfig {
+            rivers: 0,
+            ..Default::default()
+        };
+        let bodies = generate_hydrology(&mut terrain, &config, 1, None);
+
+        let lake = bodies
+            .iter()
+            .find(|b| b.kind == WaterKind::Lake)
+            .expect("Bowl should hold a lake");
+        assert!(lake.cells.contains(&(8 * 24 + 8)));
+        // Lakes are level
+        assert!(
+            lake.surface
+                .iter()
+                .all(|&s| (s - lake.surface[0]).abs() < 1e-4)
+        );
+    }
+
+    #[test]
+    fn test_rivers_run_downhill_and_carve() {
+        let original = valley(32);
+        let mut terrain = original.clone();
+        let config = HydrologyConfig {
+            rivers: 2,
+            lakes: false,
+            ..Default::default()
+        };
+        let bodies = generate_hydrology(&mut terrain, &config, 7, None);
+
+        let rivers: Vec<_> = bodies
+            .iter()
+            .filter(|b| b.kind == WaterKind::River)
+            .collect();
+        assert!(!rivers.is_empty());
+        for river in rivers {
+            assert!(
+                river
+                    .cells
+                    .iter()
+                    .all(|&c| { terrain.heights[c as usize] <= original.heights[c as usize] })
+            );
+        }
+        assert!(
+            terrain
+                .heights
+                .iter()
+                .zip(&original.heights)
+                .any(|(a, b)| a < b)
+        );
+    }
+
+    #[test]
+    fn test_rivers_start_where_erosion_flowed() {
+        let mut terrain = valley(32);
+        let config = HydrologyConfig {
+            rivers: 1,
+            lakes: false,
+            ..Default::default()
+        };
+        let gully = 28 * 32 + 20;
+        let mut flow = vec![0.0; 32 * 32];
+        flow[gully] = 100.0;
+        let erosion = ErosionMaps {
+            width: 32,
+            height: 32,
+            sediment: vec![0.0; 32 * 32],
+            flow,
+        };
+
+        let bodies = generate_hydrology(&mut terrain, &config, 7, Some(&erosion));
+
+        let river = bodies
+            .iter()
+            .find(|b| b.kind == WaterKind::River)
+            .expect("Gully should feed a river");
+        assert!(river.cells.contains(&(gully as u32)));
+    }
+
+    #[test]
+    fn test_sea_level_floods_low_ground() {
+        let mut terrain = valley(16);
+        let config = HydrologyConfig {
+            rivers: 0,
+            lakes: false,
+            sea_level: Some(1.0),
+            ..Default::default()
+        };
+        let bodies = generate_hydrology(&mut terrain, &config, 1, None);
+
+        let ocean_cells: usize = bodies
+            .iter()
+            .filter(|b| b.kind == WaterKind::Ocean)
+            .map(|b| b.cells.len())
+            .sum();
+        let below = terrain.heights.iter().filter(|&&h| h < 1.0).count();
+        assert_eq!(ocean_cells, below);
+    }
+
+    #[test]
+    fn test_hydrology_is_deterministic() {
+        let config = HydrologyConfig::default();
+        let mut first = valley(32);
+        let mut second = valley(32);
+
+        assert_eq!(
+            generate_hydrology(&mut first, &config, 3, None),
+            generate_hydrology(&mut second, &config, 3, None)
+        );
+        assert_eq!(first.heights, second.heights);
+    }
+}
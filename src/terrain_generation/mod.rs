@@ -1,23 +1,109 @@
 use crate::game_logic::errors::MinionResult;
 use crate::map::TerrainData;
-use noise::{MultiFractal, NoiseFn, Perlin, RidgedMulti};
+use noise::{MultiFractal, NoiseFn, OpenSimplex, Perlin, RidgedMulti};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+pub mod difficulty;
+pub mod dungeon;
+pub mod erosion;
+pub mod filters;
+pub mod hydrology;
+pub mod parallel;
+pub mod recipe;
+pub mod seeds;
+pub mod stamps;
+
+pub use difficulty::{DifficultyConfig, SpawnTier, generate_difficulty_zones, walking_distances};
+pub use dungeon::{DungeonConfig, DungeonLayout, DungeonStyle, generate_dungeon};
+pub use erosion::{ErosionMaps, HydraulicErosionConfig, hydraulic_erosion};
+pub use filters::{FilterStep, HeightBand, TerrainFilter, apply_filters};
+pub use hydrology::{HydrologyConfig, generate_hydrology};
+pub use parallel::{par_map, par_rows};
+pub use recipe::{RecipeStep, TerrainRecipe};
+pub use seeds::{MapSeed, SeedStream};
+pub use stamps::{BlendMode, StampImage, StampShape, StampSource, TerrainStamp, apply_stamps};
+
+fn default_octaves() -> u32 {
+    4
+}
+
+fn default_persistence() -> f32 {
+    0.5
+}
+
+fn default_lacunarity() -> f32 {
+    2.0
+}
+
+fn default_jitter() -> f32 {
+    1.0
+}
+
+/// Base noise summed by fractal (fBm) algorithms
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NoiseBasis {
+    #[default]
+    Perlin,
+    Simplex,
+    /// Folded Perlin (`2|n| - 1`), giving rounded mounds and sharp creases
+    Billow,
+}
 
 /// Terrain generation algorithms
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "algorithm", rename_all = "snake_case")]
 pub enum TerrainAlgorithm {
     Flat {
+        #[serde(default)]
         height: f32,
     },
     Perlin {
         amplitude: f32,
         frequency: f32,
+        #[serde(default = "default_octaves")]
         octaves: u32,
     },
     Ridged {
         amplitude: f32,
         frequency: f32,
+        #[serde(default = "default_octaves")]
         octaves: u32,
     },
+    /// Fractal Brownian motion with configurable octave falloff
+    Fbm {
+        #[serde(default)]
+        basis: NoiseBasis,
+        amplitude: f32,
+        frequency: f32,
+        #[serde(default = "default_octaves")]
+        octaves: u32,
+        /// Amplitude multiplier per octave
+        #[serde(default = "default_persistence")]
+        persistence: f32,
+        /// Frequency multiplier per octave
+        #[serde(default = "default_lacunarity")]
+        lacunarity: f32,
+    },
+    /// Cellular noise from jittered feature points
+    Worley {
+        amplitude: f32,
+        frequency: f32,
+        /// How far feature points stray from their cell centers (0-1)
+        #[serde(default = "default_jitter")]
+        jitter: f32,
+        /// Use the distance to the nearest cell border instead of the nearest point
+        #[serde(default)]
+        edges: bool,
+    },
+    /// Offset the sample position of another algorithm by low-frequency noise
+    DomainWarp {
+        base: Box<TerrainAlgorithm>,
+        /// Maximum offset in world units
+        strength: f32,
+        frequency: f32,
+    },
 }
 
 /// Main terrain generator struct
@@ -33,71 +119,236 @@ impl TerrainGenerator {
         Self { seed, algorithm }
     }
 
-    /// Generate terrain using the configured algorithm
+    /// Generate terrain using the configured algorithm, rows sampled in parallel
     pub fn generate(&self, width: u32, height: u32, scale: f32) -> MinionResult<TerrainData> {
-        let total_points = (width * height) as usize;
-        let mut heights = Vec::with_capacity(total_points);
+        let sampler = HeightSampler::new(self.seed, &self.algorithm);
+        let scale = scale as f64;
 
-        match &self.algorithm {
-            TerrainAlgorithm::Flat { height } => {
-                heights.resize(total_points, *height);
-            }
+        let heights = par_rows(height, |y| {
+            let world_y = y as f64 * scale;
+            (0..width)
+                .map(|x| sampler.sample(x as f64 * scale, world_y) as f32)
+                .collect()
+        });
+
+        TerrainData::new(width, height, heights, scale as f32)
+    }
+}
+
+/// Noise functions built once per generation, sampled at world coordinates
+enum HeightSampler {
+    Flat(f64),
+    Fractal {
+        basis: Box<BasisNoise>,
+        amplitude: f64,
+        frequency: f64,
+        octaves: u32,
+        persistence: f64,
+        lacunarity: f64,
+    },
+    Ridged {
+        noise: RidgedMulti<Perlin>,
+        amplitude: f64,
+    },
+    Worley {
+        seed: u32,
+        amplitude: f64,
+        frequency: f64,
+        jitter: f64,
+        edges: bool,
+    },
+    Warp {
+        base: Box<HeightSampler>,
+        offset_x: Box<Perlin>,
+        offset_y: Box<Perlin>,
+        strength: f64,
+        frequency: f64,
+    },
+}
+
+enum BasisNoise {
+    Perlin(Perlin),
+    Simplex(OpenSimplex),
+    Billow(Perlin),
+}
+
+impl BasisNoise {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        match self {
+            BasisNoise::Perlin(noise) => noise.get(point),
+            BasisNoise::Simplex(noise) => noise.get(point),
+            BasisNoise::Billow(noise) => noise.get(point).abs() * 2.0 - 1.0,
+        }
+    }
+}
+
+impl HeightSampler {
+    fn new(seed: u32, algorithm: &TerrainAlgorithm) -> Self {
+        match algorithm {
+            TerrainAlgorithm::Flat { height } => HeightSampler::Flat(*height as f64),
             TerrainAlgorithm::Perlin {
                 amplitude,
                 frequency,
                 octaves,
-            } => {
-                let perlin = Perlin::new(self.seed);
-                let freq_scale = scale as f64 * *frequency as f64;
-
-                for y in 0..height {
-                    let world_y = y as f64 * freq_scale;
-                    for x in 0..width {
-                        let world_x = x as f64 * freq_scale;
-
-                        let mut noise_value = 0.0;
-                        let mut current_amplitude = *amplitude as f64;
-                        let mut current_frequency = 1.0;
-
-                        for _ in 0..*octaves {
-                            noise_value += perlin
-                                .get([world_x * current_frequency, world_y * current_frequency])
-                                * current_amplitude;
-                            current_amplitude *= 0.5; // Persistence
-                            current_frequency *= 2.0; // Lacunarity
-                        }
-
-                        heights.push(noise_value as f32);
-                    }
-                }
-            }
+            } => HeightSampler::Fractal {
+                basis: Box::new(BasisNoise::Perlin(Perlin::new(seed))),
+                amplitude: *amplitude as f64,
+                frequency: *frequency as f64,
+                octaves: *octaves,
+                persistence: 0.5,
+                lacunarity: 2.0,
+            },
             TerrainAlgorithm::Ridged {
                 amplitude,
                 frequency,
                 octaves,
-            } => {
-                let ridged = RidgedMulti::<Perlin>::new(self.seed)
+            } => HeightSampler::Ridged {
+                noise: RidgedMulti::<Perlin>::new(seed)
                     .set_octaves(*octaves as usize)
-                    .set_frequency(*frequency as f64);
-
-                for y in 0..height {
-                    let world_y = y as f64 * scale as f64;
-                    for x in 0..width {
-                        let world_x = x as f64 * scale as f64;
-                        heights.push((ridged.get([world_x, world_y]) * *amplitude as f64) as f32);
-                    }
+                    .set_frequency(*frequency as f64),
+                amplitude: *amplitude as f64,
+            },
+            TerrainAlgorithm::Fbm {
+                basis,
+                amplitude,
+                frequency,
+                octaves,
+                persistence,
+                lacunarity,
+            } => HeightSampler::Fractal {
+                basis: Box::new(match basis {
+                    NoiseBasis::Perlin => BasisNoise::Perlin(Perlin::new(seed)),
+                    NoiseBasis::Simplex => BasisNoise::Simplex(OpenSimplex::new(seed)),
+                    NoiseBasis::Billow => BasisNoise::Billow(Perlin::new(seed)),
+                }),
+                amplitude: *amplitude as f64,
+                frequency: *frequency as f64,
+                octaves: *octaves,
+                persistence: *persistence as f64,
+                lacunarity: *lacunarity as f64,
+            },
+            TerrainAlgorithm::Worley {
+                amplitude,
+                frequency,
+                jitter,
+                edges,
+            } => HeightSampler::Worley {
+                seed,
+                amplitude: *amplitude as f64,
+                frequency: *frequency as f64,
+                jitter: jitter.clamp(0.0, 1.0) as f64,
+                edges: *edges,
+            },
+            TerrainAlgorithm::DomainWarp {
+                base,
+                strength,
+                frequency,
+            } => HeightSampler::Warp {
+                base: Box::new(HeightSampler::new(seed, base)),
+                offset_x: Box::new(Perlin::new(seed.wrapping_add(1))),
+                offset_y: Box::new(Perlin::new(seed.wrapping_add(2))),
+                strength: *strength as f64,
+                frequency: *frequency as f64,
+            },
+        }
+    }
+
+    fn sample(&self, x: f64, y: f64) -> f64 {
+        match self {
+            HeightSampler::Flat(height) => *height,
+            HeightSampler::Fractal {
+                basis,
+                amplitude,
+                frequency,
+                octaves,
+                persistence,
+                lacunarity,
+            } => {
+                let (x, y) = (x * frequency, y * frequency);
+                let mut value = 0.0;
+                let mut current_amplitude = *amplitude;
+                let mut current_frequency = 1.0;
+
+                for _ in 0..*octaves {
+                    value += basis.get([x * current_frequency, y * current_frequency])
+                        * current_amplitude;
+                    current_amplitude *= persistence;
+                    current_frequency *= lacunarity;
                 }
+
+                value
+            }
+            HeightSampler::Ridged { noise, amplitude } => noise.get([x, y]) * amplitude,
+            HeightSampler::Worley {
+                seed,
+                amplitude,
+                frequency,
+                jitter,
+                edges,
+            } => {
+                let (nearest, second) =
+                    worley_distances(*seed, x * frequency, y * frequency, *jitter);
+                let value = if *edges { second - nearest } else { nearest };
+                value * amplitude
+            }
+            HeightSampler::Warp {
+                base,
+                offset_x,
+                offset_y,
+                strength,
+                frequency,
+            } => {
+                let point = [x * frequency, y * frequency];
+                base.sample(
+                    x + offset_x.get(point) * strength,
+                    y + offset_y.get(point) * strength,
+                )
             }
         }
+    }
+}
+
+/// Deterministic per-cell hash (SplitMix64 finalizer)
+fn cell_hash(seed: u32, x: i64, y: i64) -> u64 {
+    let mut h = (seed as u64)
+        ^ (x as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+        ^ (y as u64).wrapping_mul(0xC2B2_AE3D_27D4_EB4F);
+    h = (h ^ (h >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    h = (h ^ (h >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    h ^ (h >> 31)
+}
 
-        TerrainData::new(width, height, heights, scale)
+/// Distances to the nearest and second-nearest feature points
+fn worley_distances(seed: u32, x: f64, y: f64, jitter: f64) -> (f64, f64) {
+    let cell_x = x.floor() as i64;
+    let cell_y = y.floor() as i64;
+    let mut nearest = f64::MAX;
+    let mut second = f64::MAX;
+
+    for dy in -1..=1 {
+        for dx in -1..=1 {
+            let (cx, cy) = (cell_x + dx, cell_y + dy);
+            let hash = cell_hash(seed, cx, cy);
+            let offset_x = (hash & 0xFFFF) as f64 / 65535.0;
+            let offset_y = ((hash >> 16) & 0xFFFF) as f64 / 65535.0;
+            let feature_x = cx as f64 + 0.5 + (offset_x - 0.5) * jitter;
+            let feature_y = cy as f64 + 0.5 + (offset_y - 0.5) * jitter;
+
+            let distance = ((feature_x - x).powi(2) + (feature_y - y).powi(2)).sqrt();
+            if distance < nearest {
+                second = nearest;
+                nearest = distance;
+            } else if distance < second {
+                second = distance;
+            }
+        }
     }
+
+    (nearest, second)
 }
 
 /// Get a predefined terrain preset
-pub fn get_terrain_preset(name: &str, seed: Option<u32>) -> Option<TerrainGenerator> {
-    let seed = seed.unwrap_or_else(rand::random);
-
+pub fn get_terrain_preset(name: &str, seed: u32) -> Option<TerrainGenerator> {
     match name {
         "flat" => Some(TerrainGenerator::new(
             seed,
@@ -127,6 +378,46 @@ pub fn get_terrain_preset(name: &str, seed: Option<u32>) -> Option<TerrainGenera
                 octaves: 4,
             },
         )),
+        "canyons" => Some(TerrainGenerator::new(
+            seed,
+            TerrainAlgorithm::DomainWarp {
+                // Cell borders cut into plateaus, warped so they meander
+                base: Box::new(TerrainAlgorithm::Worley {
+                    amplitude: 12.0,
+                    frequency: 0.02,
+                    jitter: 1.0,
+                    edges: true,
+                }),
+                strength: 10.0,
+                frequency: 0.01,
+            },
+        )),
+        "mesas" => Some(TerrainGenerator::new(
+            seed,
+            TerrainAlgorithm::Fbm {
+                basis: NoiseBasis::Billow,
+                amplitude: 14.0,
+                frequency: 0.008,
+                octaves: 3,
+                persistence: 0.35, // Keep tops broad and flat
+                lacunarity: 2.2,
+            },
+        )),
+        "dunes" => Some(TerrainGenerator::new(
+            seed,
+            TerrainAlgorithm::DomainWarp {
+                base: Box::new(TerrainAlgorithm::Fbm {
+                    basis: NoiseBasis::Simplex,
+                    amplitude: 6.0,
+                    frequency: 0.03,
+                    octaves: 3,
+                    persistence: 0.4,
+                    lacunarity: 2.0,
+                }),
+                strength: 6.0,
+                frequency: 0.015,
+            },
+        )),
         _ => None,
     }
 }
@@ -151,6 +442,15 @@ pub fn calculate_slope(terrain: &TerrainData, x: u32, y: u32) -> f32 {
     (dx * dx + dy * dy).sqrt()
 }
 
+/// Slope of every heightmap sample in row-major order, see [`calculate_slope`]
+pub fn slope_map(terrain: &TerrainData) -> Vec<f32> {
+    par_rows(terrain.height, |z| {
+        (0..terrain.width)
+            .map(|x| calculate_slope(terrain, x, z))
+            .collect()
+    })
+}
+
 /// Check if a terrain position is suitable for spawning
 pub fn is_suitable_for_spawning(
     terrain: &TerrainData,
@@ -187,10 +487,61 @@ pub fn is_suitable_for_spawning(
     }
 }
 
+/// Heightmap sample queued by cost in a `BinaryHeap`, cheapest first
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct QueuedCell {
+    pub cost: f32,
+    pub index: usize,
+}
+
+impl PartialEq for QueuedCell {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for QueuedCell {}
+
+impl PartialOrd for QueuedCell {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for QueuedCell {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed for a min-heap; index breaks ties so results are deterministic
+        other
+            .cost
+            .total_cmp(&self.cost)
+            .then_with(|| other.index.cmp(&self.index))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_queued_cells_pop_cheapest_first() {
+        let mut queue = std::collections::BinaryHeap::from([
+            QueuedCell {
+                cost: 2.0,
+                index: 0,
+            },
+            QueuedCell {
+                cost: 1.0,
+                index: 5,
+            },
+            QueuedCell {
+                cost: 1.0,
+                index: 3,
+            },
+        ]);
+        let order: Vec<usize> = std::iter::from_fn(|| queue.pop().map(|cell| cell.index)).collect();
+        assert_eq!(order, vec![3, 5, 0]);
+    }
+
     #[test]
     fn test_flat_terrain_generation() {
         let generator = TerrainGenerator::new(12345, TerrainAlgorithm::Flat { height: 5.0 });
@@ -239,20 +590,182 @@ mod tests {
 
     #[test]
     fn test_terrain_presets() {
-        let flat = get_terrain_preset("flat", Some(123)).expect("Flat terrain preset should exist");
-        let hills =
-            get_terrain_preset("hills", Some(123)).expect("Hills terrain preset should exist");
-        let mountains = get_terrain_preset("mountains", Some(123))
-            .expect("Mountains terrain preset should exist");
+        let flat = get_terrain_preset("flat", 123).expect("Flat terrain preset should exist");
+        let hills = get_terrain_preset("hills", 123).expect("Hills terrain preset should exist");
+        let mountains =
+            get_terrain_preset("mountains", 123).expect("Mountains terrain preset should exist");
         let valleys =
-            get_terrain_preset("valleys", Some(123)).expect("Valleys terrain preset should exist");
+            get_terrain_preset("valleys", 123).expect("Valleys terrain preset should exist");
 
         assert_eq!(flat.seed, 123);
         assert_eq!(hills.seed, 123);
         assert_eq!(mountains.seed, 123);
         assert_eq!(valleys.seed, 123);
 
-        assert!(get_terrain_preset("invalid", Some(123)).is_none());
+        assert!(get_terrain_preset("invalid", 123).is_none());
+    }
+
+    #[test]
+    fn test_new_presets_generate_varied_terrain() {
+        for name in ["canyons", "mesas", "dunes"] {
+            let generator = get_terrain_preset(name, 99).expect("Preset should exist");
+            let terrain = generator.generate(32, 32, 1.0).unwrap();
+            let min = terrain.heights.iter().copied().fold(f32::MAX, f32::min);
+            let max = terrain.heights.iter().copied().fold(f32::MIN, f32::max);
+            assert!(max - min > 0.5, "{name} terrain should not be flat");
+
+            // Same seed, same terrain
+            let again = get_terrain_preset(name, 99)
+                .unwrap()
+                .generate(32, 32, 1.0)
+                .unwrap();
+            assert_eq!(terrain.heights, again.heights);
+        }
+    }
+
+    #[test]
+    fn test_fbm_matches_perlin_with_default_falloff() {
+        let perlin = TerrainGenerator::new(
+            7,
+            TerrainAlgorithm::Perlin {
+                amplitude: 10.0,
+                frequency: 0.1,
+                octaves: 3,
+            },
+        );
+        let fbm = TerrainGenerator::new(
+            7,
+            TerrainAlgorithm::Fbm {
+                basis: NoiseBasis::Perlin,
+                amplitude: 10.0,
+                frequency: 0.1,
+                octaves: 3,
+                persistence: 0.5,
+                lacunarity: 2.0,
+            },
+        );
+        assert_eq!(
+            perlin.generate(8, 8, 1.0).unwrap().heights,
+            fbm.generate(8, 8, 1.0).unwrap().heights
+        );
+
+        // Persistence only matters once there is more than one octave
+        let rougher = TerrainGenerator::new(
+            7,
+            TerrainAlgorithm::Fbm {
+                basis: NoiseBasis::Perlin,
+                amplitude: 10.0,
+                frequency: 0.1,
+                octaves: 3,
+                persistence: 0.9,
+                lacunarity: 2.0,
+            },
+        );
+        assert_ne!(
+            fbm.generate(8, 8, 1.0).unwrap().heights,
+            rougher.generate(8, 8, 1.0).unwrap().heights
+        );
+    }
+
+    #[test]
+    fn test_parallel_generation_matches_serial() {
+        let generator = get_terrain_preset("mountains", 99).unwrap();
+        let terrain = generator.generate(100, 70, 1.5).unwrap();
+
+        let sampler = HeightSampler::new(generator.seed, &generator.algorithm);
+        let mut serial = Vec::new();
+        for y in 0..70 {
+            for x in 0..100 {
+                serial.push(sampler.sample(x as f64 * 1.5, y as f64 * 1.5) as f32);
+            }
+        }
+        assert_eq!(terrain.heights, serial);
+
+        let slopes = slope_map(&terrain);
+        assert_eq!(slopes.len(), 100 * 70);
+        assert_eq!(slopes[3 * 100 + 42], calculate_slope(&terrain, 42, 3));
+    }
+
+    #[test]
+    fn test_billow_and_simplex_bases() {
+        let generate = |basis| {
+            TerrainGenerator::new(
+                3,
+                TerrainAlgorithm::Fbm {
+                    basis,
+                    amplitude: 1.0,
+                    frequency: 0.13,
+                    octaves: 1,
+                    persistence: 0.5,
+                    lacunarity: 2.0,
+                },
+            )
+            .generate(16, 16, 1.0)
+            .unwrap()
+            .heights
+        };
+
+        let perlin = generate(NoiseBasis::Perlin);
+        let billow = generate(NoiseBasis::Billow);
+        for (p, b) in perlin.iter().zip(&billow) {
+            assert!((b - (p.abs() * 2.0 - 1.0)).abs() < 1e-5);
+        }
+
+        let simplex = generate(NoiseBasis::Simplex);
+        assert_ne!(simplex, perlin);
+        assert!(simplex.iter().all(|h| h.abs() <= 1.5));
+    }
+
+    #[test]
+    fn test_worley_distances() {
+        for i in 0..50 {
+            let (x, y) = (i as f64 * 0.37, i as f64 * 0.61);
+            let (nearest, second) = worley_distances(5, x, y, 1.0);
+            assert!(nearest <= second);
+            assert!(
+                nearest < 1.5,
+                "A feature point is always in a neighboring cell"
+            );
+        }
+
+        // Without jitter feature points sit at cell centers
+        let (nearest, _) = worley_distances(5, 2.5, 7.5, 0.0);
+        assert!(nearest.abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_domain_warp_changes_sampling() {
+        let base = TerrainAlgorithm::Perlin {
+            amplitude: 5.0,
+            frequency: 0.1,
+            octaves: 2,
+        };
+        let plain = TerrainGenerator::new(11, base.clone())
+            .generate(16, 16, 1.0)
+            .unwrap();
+        let unwarped = TerrainGenerator::new(
+            11,
+            TerrainAlgorithm::DomainWarp {
+                base: Box::new(base.clone()),
+                strength: 0.0,
+                frequency: 0.05,
+            },
+        )
+        .generate(16, 16, 1.0)
+        .unwrap();
+        let warped = TerrainGenerator::new(
+            11,
+            TerrainAlgorithm::DomainWarp {
+                base: Box::new(base),
+                strength: 4.0,
+                frequency: 0.05,
+            },
+        )
+        .generate(16, 16, 1.0)
+        .unwrap();
+
+        assert_eq!(plain.heights, unwarped.heights);
+        assert_ne!(plain.heights, warped.heights);
     }
 
     #[test]
@@ -305,9 +818,7 @@ mod tests {
 
     #[test]
     fn test_integration_with_terrain_mesh() {
-        use crate::terrain::{
-            generate_terrain_collider, generate_terrain_mesh, generate_terrain_mesh_and_collider,
-        };
+        use crate::terrain::{generate_terrain_collider, generate_terrain_mesh};
 
         // Create a terrain generator with Perlin noise
         let generator = TerrainGenerator::new(
@@ -331,9 +842,5 @@ mod tests {
 
         // Test collider generation works with generated terrain
         let _collider = generate_terrain_collider(&terrain).unwrap();
-
-        // Test combined generation works
-        let (mesh2, _collider2) = generate_terrain_mesh_and_collider(&terrain).unwrap();
-        assert!(mesh2.count_vertices() > 0);
     }
 }
@@ -0,0 +1,64 @@
+//! Parallel evaluation of per-sample generation work.
+//!
+//! Work is split into fixed-size batches on Bevy's compute task pool and the
+//! results are stitched back together in input order. As long as each item
+//! only depends on its own input, the output is identical to a serial loop
+//! whatever the thread count or scheduling.
+
+use bevy::tasks::{ComputeTaskPool, ParallelSlice, TaskPool};
+
+/// Rows handed to one task; small grids stay on the calling thread
+pub const ROWS_PER_TASK: usize = 16;
+
+/// Map `items` through `f` across the compute task pool, `batch` items per task
+pub fn par_map<T, R, F>(items: &[T], batch: usize, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send + 'static,
+    F: Fn(&T) -> R + Send + Sync,
+{
+    let batch = batch.max(1);
+    if items.len() <= batch {
+        return items.iter().map(f).collect();
+    }
+
+    let pool = ComputeTaskPool::get_or_init(TaskPool::default);
+    items
+        .par_chunk_map(pool, batch, |_, chunk| {
+            chunk.iter().map(&f).collect::<Vec<R>>()
+        })
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+/// Build a row-major grid `height` rows tall, one `row` call per row
+pub fn par_rows<T, F>(height: u32, row: F) -> Vec<T>
+where
+    T: Send + 'static,
+    F: Fn(u32) -> Vec<T> + Send + Sync,
+{
+    let rows: Vec<u32> = (0..height).collect();
+    par_map(&rows, ROWS_PER_TASK, |&z| row(z))
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_par_rows_matches_serial_order() {
+        let cell = |x: u32, z: u32| ((x * 31 + z * 17) as f32).sin();
+        let serial: Vec<f32> = (0..100)
+            .flat_map(|z| (0..37).map(move |x| cell(x, z)))
+            .collect();
+
+        let parallel = par_rows(100, |z| (0..37).map(|x| cell(x, z)).collect());
+
+        assert_eq!(parallel, serial);
+        assert_eq!(par_map(&[1, 2, 3], 8, |v| v * 2), vec![2, 4, 6]);
+    }
+}
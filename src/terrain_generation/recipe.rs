@@ -0,0 +1,522 @@
+//! Declarative terrain recipes.
+//!
+//! A recipe is an ordered stack of operations evaluated on a heightmap that
+//! starts flat at 0. Every step derives its own seed from the recipe seed and
+//! its position in the stack, so the same recipe and seed always produce the
+//! same terrain, and inserting a step only reseeds the steps after it.
+//!
+//! ```toml
+//! [[step]]
+//! op = "noise"
+//! algorithm = "fbm"
+//! basis = "simplex"
+//! amplitude = 12.0
+//! frequency = 0.02
+//! octaves = 5
+//!
+//! [[step]]
+//! op = "noise"
+//! algorithm = "ridged"
+//! amplitude = 8.0
+//! frequency = 0.01
+//! blend = "max"
+//! weight = 0.5
+//!
+//! [[step]]
+//! op = "island"
+//! radius = 0.7
+//! floor = -4.0
+//!
+//! [[step]]
+//! op = "stamp"
+//! shape = "crater"
+//! x = 10.0
+//! z = -5.0
+//! radius = 8.0
+//! height = 4.0
+//! falloff = 0.2
+//!
+//! [[step]]
+//! op = "erosion"
+//! iterations = 20000
+//!
+//! [[step]]
+//! op = "filter"
+//! type = "terrace"
+//! steps = 6
+//! mask = { min = 2.0, falloff = 1.0 }
+//! ```
+
+use crate::game_logic::errors::{MinionError, MinionResult};
+use crate::map::TerrainData;
+use crate::terrain_generation::{
+    BlendMode, FilterStep, HydraulicErosionConfig, StampShape, StampSource, TerrainAlgorithm,
+    TerrainGenerator, TerrainStamp, hydraulic_erosion,
+};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+fn default_weight() -> f32 {
+    1.0
+}
+
+fn default_iterations() -> u32 {
+    1
+}
+
+fn default_island_radius() -> f32 {
+    0.7
+}
+
+fn default_island_falloff() -> f32 {
+    0.3
+}
+
+fn default_island_floor() -> f32 {
+    -5.0
+}
+
+/// A single operation in a recipe
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum RecipeStep {
+    /// Generate a noise layer and blend it into the heights
+    Noise {
+        #[serde(flatten)]
+        algorithm: TerrainAlgorithm,
+        #[serde(default)]
+        blend: BlendMode,
+        /// How much of the blended result to keep (0-1)
+        #[serde(default = "default_weight")]
+        weight: f32,
+    },
+    /// Radial falloff that sinks the map edges toward `floor`
+    Island {
+        /// Fully raised radius as a fraction of half the shorter map side
+        #[serde(default = "default_island_radius")]
+        radius: f32,
+        /// Width of the fade past `radius`, in the same units
+        #[serde(default = "default_island_falloff")]
+        falloff: f32,
+        #[serde(default = "default_island_floor")]
+        floor: f32,
+    },
+    /// Average each height with its neighbors
+    Smooth {
+        #[serde(default = "default_iterations")]
+        iterations: u32,
+        /// Fraction of the averaged height applied per iteration (0-1)
+        #[serde(default = "default_weight")]
+        strength: f32,
+    },
+    /// Rescale heights to a range
+    Normalize {
+        min: f32,
+        max: f32,
+    },
+    /// Add a parametric feature centered at world coordinates, see `TerrainStamp`
+    Stamp {
+        shape: StampShape,
+        x: f32,
+        z: f32,
+        /// Radius in world units
+        radius: f32,
+        /// Peak height of the profile. Under `max`, `min`, and `replace` it is
+        /// measured from the ground at the center, not from zero; negative
+        /// values dig
+        height: f32,
+        #[serde(default)]
+        blend: BlendMode,
+        /// Fraction of the radius faded into the ground (0-1)
+        #[serde(default)]
+        falloff: f32,
+    },
+    Erosion(HydraulicErosionConfig),
+    Filter(FilterStep),
+}
+
+/// An ordered stack of terrain operations
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TerrainRecipe {
+    #[serde(default, rename = "step")]
+    pub steps: Vec<RecipeStep>,
+}
+
+/// Seed for the step at `index`, so steps never share noise
+fn step_seed(seed: u32, index: usize) -> u32 {
+    seed.wrapping_add((index as u32 + 1).wrapping_mul(0x9E37_79B9))
+}
+
+impl TerrainRecipe {
+    /// Load a recipe from a TOML file
+    pub fn load(path: &Path) -> MinionResult<Self> {
+        if !path.exists() {
+            return Err(MinionError::ConfigFileNotFound {
+                path: path.to_path_buf(),
+            });
+        }
+        let content =
+            std::fs::read_to_string(path).map_err(MinionError::ConfigDirCreationFailed)?;
+        Self::from_toml_str(&content)
+    }
+
+    /// Parse and validate a recipe
+    pub fn from_toml_str(content: &str) -> MinionResult<Self> {
+        let recipe: Self = toml::from_str(content)?;
+        recipe.validate()?;
+        Ok(recipe)
+    }
+
+    /// Check every step's parameters
+    pub fn validate(&self) -> MinionResult<()> {
+        let invalid = |index: usize, reason: String| {
+            Err(MinionError::InvalidConfig {
+                reason: format!("Recipe step {index}: {reason}"),
+            })
+        };
+
+        if self.steps.is_empty() {
+            return Err(MinionError::InvalidConfig {
+                reason: "Recipe has no steps".to_string(),
+            });
+        }
+
+        for (index, step) in self.steps.iter().enumerate() {
+            match step {
+                RecipeStep::Noise { weight, .. }
+                | RecipeStep::Smooth {
+                    strength: weight, ..
+                } => {
+                    if !(0.0..=1.0).contains(weight) {
+                        return invalid(index, format!("weight must be 0.0-1.0, got {weight}"));
+                    }
+                }
+                RecipeStep::Island {
+                    radius, falloff, ..
+                } => {
+                    if *radius <= 0.0 || *falloff < 0.0 {
+                        return invalid(
+                            index,
+                            "island radius must be positive and falloff non-negative".to_string(),
+                        );
+                    }
+                }
+                RecipeStep::Normalize { min, max } => {
+                    if min >= max {
+                        return invalid(index, format!("normalize range {min}..{max} is empty"));
+                    }
+                }
+                RecipeStep::Stamp {
+                    radius, falloff, ..
+                } => {
+                    if *radius <= 0.0 {
+                        return invalid(
+                            index,
+                            format!("stamp radius must be positive, got {radius}"),
+                        );
+                    }
+                    if !(0.0..=1.0).contains(falloff) {
+                        return invalid(
+                            index,
+                            format!("stamp falloff must be between 0 and 1, got {falloff}"),
+                        );
+                    }
+                }
+                RecipeStep::Erosion(config) => config.validate()?,
+                RecipeStep::Filter(step) => step.filter.validate()?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Evaluate the recipe on flat ground
+    pub fn generate(
+        &self,
+        width: u32,
+        height: u32,
+        scale: f32,
+        seed: u32,
+    ) -> MinionResult<TerrainData> {
+        let mut terrain = TerrainData::create_flat(width, height, scale, 0.0)?;
+        self.apply(&mut terrain, seed)?;
+        Ok(terrain)
+    }
+
+    /// Run every step in order on existing terrain
+    pub fn apply(&self, terrain: &mut TerrainData, seed: u32) -> MinionResult<()> {
+        self.validate()?;
+
+        for (index, step) in self.steps.iter().enumerate() {
+            let seed = step_seed(seed, index);
+            match step {
+                RecipeStep::Noise {
+                    algorithm,
+                    blend,
+                    weight,
+                } => {
+                    let layer = TerrainGenerator::new(seed, algorithm.clone()).generate(
+                        terrain.width,
+                        terrain.height,
+                        terrain.scale,
+                    )?;
+                    for (base, value) in terrain.heights.iter_mut().zip(layer.heights) {
+                        *base += (blend.blend(*base, value) - *base) * weight;
+                    }
+                }
+                RecipeStep::Island {
+                    radius,
+                    falloff,
+                    floor,
+                } => island(terrain, *radius, *falloff, *floor),
+                RecipeStep::Smooth {
+                    iterations,
+                    strength,
+                } => {
+                    for _ in 0..*iterations {
+                        smooth(terrain, *strength);
+                    }
+                }
+                RecipeStep::Normalize { min, max } => normalize(terrain, *min, *max),
+                RecipeStep::Stamp {
+                    shape,
+                    x,
+                    z,
+                    radius,
+                    height,
+                    blend,
+                    falloff,
+                } => {
+                    TerrainStamp::new(StampSource::Shape(*shape), *x, *z, *radius)
+                        .with_height(*height)
+                        .with_blend(*blend)
+                        .with_falloff(*falloff)
+                        .apply(terrain)?;
+                }
+                RecipeStep::Erosion(config) => {
+                    hydraulic_erosion(terrain, config, seed as u64);
+                }
+                RecipeStep::Filter(step) => {
+                    let mask = step.mask.map(|band| band.mask(terrain));
+                    step.filter.apply(terrain, mask.as_deref())?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn island(terrain: &mut TerrainData, radius: f32, falloff: f32, floor: f32) {
+    let width = terrain.width as usize;
+    let center_x = (terrain.width as f32 - 1.0) / 2.0;
+    let center_y = (terrain.height as f32 - 1.0) / 2.0;
+    let half_side = center_x.min(center_y).max(1.0);
+
+    for (index, height) in terrain.heights.iter_mut().enumerate() {
+        let dx = (index % width) as f32 - center_x;
+        let dy = (index / width) as f32 - center_y;
+        let distance = (dx * dx + dy * dy).sqrt() / half_side;
+
+        let t = if falloff <= 0.0 {
+            if distance <= radius { 0.0 } else { 1.0 }
+        } else {
+            ((distance - radius) / falloff).clamp(0.0, 1.0)
+        };
+        let weight = 1.0 - t * t * (3.0 - 2.0 * t);
+        *height = floor + (*height - floor) * weight;
+    }
+}
+
+fn smooth(terrain: &mut TerrainData, strength: f32) {
+    let width = terrain.width as i64;
+    let height = terrain.height as i64;
+    let source = terrain.heights.clone();
+
+    for y in 0..height {
+        for x in 0..width {
+            let mut sum = 0.0;
+            let mut count = 0.0;
+            for ny in (y - 1).max(0)..=(y + 1).min(height - 1) {
+                for nx in (x - 1).max(0)..=(x + 1).min(width - 1) {
+                    sum += source[(ny * width + nx) as usize];
+                    count += 1.0;
+                }
+            }
+            let index = (y * width + x) as usize;
+            terrain.heights[index] += (sum / count - source[index]) * strength;
+        }
+    }
+}
+
+fn normalize(terrain: &mut TerrainData, min: f32, max: f32) {
+    let (low, high) = terrain
+        .heights
+        .iter()
+        .fold((f32::MAX, f32::MIN), |(lo, hi), &h| (lo.min(h), hi.max(h)));
+    let span = high - low;
+    for height in &mut terrain.heights {
+        *height = if span > f32::EPSILON {
+            min + (*height - low) / span * (max - min)
+        } else {
+            min
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::terrain_generation::{NoiseBasis, TerrainFilter};
+
+    const RECIPE: &str = r#"
+        [[step]]
+        op = "noise"
+        algorithm = "fbm"
+        basis = "simplex"
+        amplitude = 6.0
+        frequency = 0.05
+
+        [[step]]
+        op = "noise"
+        algorithm = "ridged"
+        amplitude = 4.0
+        frequency = 0.03
+        blend = "max"
+        weight = 0.5
+
+        [[step]]
+        op = "island"
+        floor = -3.0
+
+        [[step]]
+        op = "smooth"
+        iterations = 2
+
+        [[step]]
+        op = "stamp"
+        shape = "crater"
+        x = 0.0
+        z = 0.0
+        radius = 4.0
+        height = 3.0
+
+        [[step]]
+        op = "erosion"
+        iterations = 200
+
+        [[step]]
+        op = "filter"
+        type = "terrace"
+        steps = 4
+        mask = { min = 0.0, falloff = 1.0 }
+    "#;
+
+    #[test]
+    fn test_parse_recipe() {
+        let recipe = TerrainRecipe::from_toml_str(RECIPE).unwrap();
+        assert_eq!(recipe.steps.len(), 7);
+
+        assert_eq!(
+            recipe.steps[0],
+            RecipeStep::Noise {
+                algorithm: TerrainAlgorithm::Fbm {
+                    basis: NoiseBasis::Simplex,
+                    amplitude: 6.0,
+                    frequency: 0.05,
+                    octaves: 4,
+                    persistence: 0.5,
+                    lacunarity: 2.0,
+                },
+                blend: BlendMode::Add,
+                weight: 1.0,
+            }
+        );
+        match &recipe.steps[5] {
+            RecipeStep::Erosion(config) => {
+                assert_eq!(config.iterations, 200);
+                assert_eq!(config.inertia, HydraulicErosionConfig::default().inertia);
+            }
+            other => panic!("Expected erosion step, got {other:?}"),
+        }
+        match &recipe.steps[6] {
+            RecipeStep::Filter(step) => {
+                assert!(matches!(
+                    step.filter,
+                    TerrainFilter::Terrace { steps: 4, .. }
+                ));
+                assert!(step.mask.is_some());
+            }
+            other => panic!("Expected filter step, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_recipe_is_deterministic() {
+        let recipe = TerrainRecipe::from_toml_str(RECIPE).unwrap();
+        let first = recipe.generate(32, 32, 0.5, 11).unwrap();
+        let second = recipe.generate(32, 32, 0.5, 11).unwrap();
+        let other = recipe.generate(32, 32, 0.5, 12).unwrap();
+
+        assert_eq!(first.heights, second.heights);
+        assert_ne!(first.heights, other.heights);
+        assert!(first.heights.iter().all(|h| h.is_finite()));
+    }
+
+    #[test]
+    fn test_island_sinks_edges() {
+        let recipe = TerrainRecipe {
+            steps: vec![
+                RecipeStep::Noise {
+                    algorithm: TerrainAlgorithm::Flat { height: 5.0 },
+                    blend: BlendMode::Add,
+                    weight: 1.0,
+                },
+                RecipeStep::Island {
+                    radius: 0.5,
+                    falloff: 0.3,
+                    floor: -2.0,
+                },
+            ],
+        };
+        let terrain = recipe.generate(21, 21, 1.0, 0).unwrap();
+
+        assert_eq!(terrain.heights[10 * 21 + 10], 5.0);
+        assert_eq!(terrain.heights[0], -2.0);
+    }
+
+    #[test]
+    fn test_blend_modes_and_stamps() {
+        assert_eq!(BlendMode::Max.blend(1.0, 3.0), 3.0);
+        assert_eq!(BlendMode::Subtract.blend(1.0, 3.0), -2.0);
+        assert_eq!(BlendMode::Replace.blend(1.0, 3.0), 3.0);
+
+        let mut terrain = TerrainData::create_flat(11, 11, 1.0, 1.0).unwrap();
+        TerrainStamp::new(StampSource::Shape(StampShape::Cone), -0.5, -0.5, 3.0)
+            .with_height(4.0)
+            .with_blend(BlendMode::Max)
+            .with_falloff(0.0)
+            .apply(&mut terrain)
+            .unwrap();
+        // Cell (5, 5) is the world origin shifted by half a cell; the peak
+        // stands 4.0 above the ground there
+        assert_eq!(terrain.heights[5 * 11 + 5], 5.0);
+        assert_eq!(terrain.heights[0], 1.0);
+        assert!(StampShape::Crater.profile(0.0) < 0.0);
+        assert!(StampShape::Crater.profile(0.8) > 0.0);
+    }
+
+    #[test]
+    fn test_invalid_recipes_rejected() {
+        assert!(TerrainRecipe::from_toml_str("").is_err());
+        assert!(TerrainRecipe::from_toml_str("[[step]]\nop = \"unknown\"").is_err());
+        assert!(
+            TerrainRecipe::from_toml_str("[[step]]\nop = \"normalize\"\nmin = 1.0\nmax = 0.0")
+                .is_err()
+        );
+        assert!(
+            TerrainRecipe::from_toml_str(
+                "[[step]]\nop = \"filter\"\ntype = \"terrace\"\nsteps = 0"
+            )
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,494 @@
+//! Hand-placed heightmap features.
+//!
+//! A stamp drops one feature such as a crater, mesa, volcano, ramp, or arena
+//! onto existing terrain. Its source is a parametric profile or a grayscale
+//! image, scaled to `height` above the ground at the stamp's center and
+//! combined with the terrain through a blend mode. Falloff fades the result
+//! into the surrounding terrain towards the edge of the footprint, so the
+//! same stamp works as a soft editor brush.
+
+use crate::game_logic::errors::{MinionError, MinionResult};
+use crate::map::TerrainData;
+use crate::terrain::coordinates::{get_height_at_world_interpolated, grid_to_world, world_to_grid};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// How a layer is combined with the heights below it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BlendMode {
+    #[default]
+    Add,
+    Subtract,
+    Multiply,
+    Max,
+    Min,
+    Replace,
+}
+
+impl BlendMode {
+    /// Combine an existing height with a layer value
+    pub fn blend(self, base: f32, layer: f32) -> f32 {
+        match self {
+            BlendMode::Add => base + layer,
+            BlendMode::Subtract => base - layer,
+            BlendMode::Multiply => base * layer,
+            BlendMode::Max => base.max(layer),
+            BlendMode::Min => base.min(layer),
+            BlendMode::Replace => layer,
+        }
+    }
+}
+
+/// Parametric height profiles for stamps
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StampShape {
+    Cone,
+    Dome,
+    /// Flat top with smooth shoulders
+    Plateau,
+    /// Bowl below the surrounding ground with a raised rim
+    Crater,
+    /// Flat top with near vertical cliffs
+    Mesa,
+    /// Cone with a sunken vent at the summit
+    Volcano,
+    /// Flat floor ringed by a wall
+    Arena,
+}
+
+impl StampShape {
+    /// Profile height at normalized distance `d` from the center (0-1)
+    pub fn profile(self, d: f32) -> f32 {
+        let d = d.clamp(0.0, 1.0);
+        match self {
+            StampShape::Cone => 1.0 - d,
+            StampShape::Dome => (1.0 - d * d).sqrt(),
+            StampShape::Plateau => smoothstep((1.0 - d) / 0.3),
+            StampShape::Crater => {
+                if d < 0.8 {
+                    (d / 0.8).powi(2) * 1.6 - 0.6
+                } else {
+                    (1.0 - d) / 0.2
+                }
+            }
+            StampShape::Mesa => smoothstep((1.0 - d) / 0.08),
+            StampShape::Volcano => {
+                if d < 0.2 {
+                    0.5 + 1.5 * d
+                } else {
+                    1.0 - d
+                }
+            }
+            StampShape::Arena => {
+                if d < 0.7 {
+                    0.0
+                } else if d < 0.85 {
+                    smoothstep((d - 0.7) / 0.15)
+                } else {
+                    (1.0 - d) / 0.15
+                }
+            }
+        }
+    }
+}
+
+fn smoothstep(t: f32) -> f32 {
+    let t = t.clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+/// Grayscale heightmap used as a stamp, 0 is low and 1 is high
+#[derive(Debug, Clone, PartialEq)]
+pub struct StampImage {
+    pub width: u32,
+    pub height: u32,
+    pub values: Vec<f32>, // Flattened 2D array (row-major)
+}
+
+impl StampImage {
+    pub fn new(width: u32, height: u32, values: Vec<f32>) -> MinionResult<Self> {
+        let cells = width
+            .checked_mul(height)
+            .ok_or_else(|| MinionError::InvalidConfig {
+                reason: format!("Stamp image of {width}x{height} is too large"),
+            })?;
+        if cells == 0 || values.len() != cells as usize {
+            return Err(MinionError::InvalidConfig {
+                reason: format!(
+                    "Stamp image of {width}x{height} needs {cells} values, got {}",
+                    values.len()
+                ),
+            });
+        }
+        Ok(Self {
+            width,
+            height,
+            values,
+        })
+    }
+
+    /// Load a grayscale image; color images are converted by luminance
+    pub fn load(path: &Path) -> MinionResult<Self> {
+        let image = image::open(path)
+            .map_err(|error| MinionError::InvalidConfig {
+                reason: format!("Failed to load stamp image {}: {error}", path.display()),
+            })?
+            .to_luma16();
+        let (width, height) = image.dimensions();
+        let values = image
+            .into_raw()
+            .into_iter()
+            .map(|value| value as f32 / u16::MAX as f32)
+            .collect();
+        Self::new(width, height, values)
+    }
+
+    /// Bilinear sample at normalized coordinates, -1 to 1 across the image
+    pub fn sample(&self, u: f32, v: f32) -> f32 {
+        let x = (u.clamp(-1.0, 1.0) + 1.0) / 2.0 * (self.width - 1) as f32;
+        let y = (v.clamp(-1.0, 1.0) + 1.0) / 2.0 * (self.height - 1) as f32;
+        let (x0, y0) = (x.floor() as u32, y.floor() as u32);
+        let (x1, y1) = ((x0 + 1).min(self.width - 1), (y0 + 1).min(self.height - 1));
+        let (fx, fy) = (x - x0 as f32, y - y0 as f32);
+
+        let at = |x: u32, y: u32| self.values[(y * self.width + x) as usize];
+        let top = at(x0, y0) + (at(x1, y0) - at(x0, y0)) * fx;
+        let bottom = at(x0, y1) + (at(x1, y1) - at(x0, y1)) * fx;
+        top + (bottom - top) * fy
+    }
+}
+
+/// What a stamp draws
+#[derive(Debug, Clone, PartialEq)]
+pub enum StampSource {
+    /// Radial profile over a round footprint
+    Shape(StampShape),
+    /// Straight incline rising along the stamp's x axis over a square footprint
+    Ramp,
+    /// Heightmap image over a square footprint
+    Image(StampImage),
+}
+
+impl StampSource {
+    /// Blend mode a stamp of this source starts with
+    pub fn default_blend(&self) -> BlendMode {
+        match self {
+            StampSource::Shape(StampShape::Crater) => BlendMode::Add,
+            StampSource::Shape(StampShape::Arena) => BlendMode::Replace,
+            _ => BlendMode::Max,
+        }
+    }
+
+    fn is_square(&self) -> bool {
+        !matches!(self, StampSource::Shape(_))
+    }
+}
+
+/// One feature placed onto a heightmap
+#[derive(Debug, Clone, PartialEq)]
+pub struct TerrainStamp {
+    pub source: StampSource,
+    /// World position of the center
+    pub x: f32,
+    pub z: f32,
+    /// Radius, or half the side of a square footprint, in world units
+    pub radius: f32,
+    /// Height of the full profile above the ground at the center. Negative
+    /// values dig; under `Max`, which never lowers ground, they blend with `Add`.
+    pub height: f32,
+    pub blend: BlendMode,
+    /// Fraction of the radius over which the stamp fades into the ground (0-1)
+    pub falloff: f32,
+    /// Turn about the vertical axis in degrees, for ramps and images
+    pub rotation: f32,
+}
+
+impl TerrainStamp {
+    /// Stamp rising half its radius, with the source's usual blend mode
+    pub fn new(source: StampSource, x: f32, z: f32, radius: f32) -> Self {
+        let blend = source.default_blend();
+        Self {
+            source,
+            x,
+            z,
+            radius,
+            height: radius * 0.5,
+            blend,
+            falloff: 0.2,
+            rotation: 0.0,
+        }
+    }
+
+    /// Builder method to set the profile height
+    pub fn with_height(mut self, height: f32) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// Builder method to set the blend mode
+    pub fn with_blend(mut self, blend: BlendMode) -> Self {
+        self.blend = blend;
+        self
+    }
+
+    /// Builder method to set the edge falloff
+    pub fn with_falloff(mut self, falloff: f32) -> Self {
+        self.falloff = falloff;
+        self
+    }
+
+    /// Builder method to set the rotation in degrees
+    pub fn with_rotation(mut self, rotation: f32) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Check parameters before stamping
+    pub fn validate(&self) -> MinionResult<()> {
+        let invalid = |reason: String| Err(MinionError::InvalidConfig { reason });
+
+        for (name, value) in [
+            ("x", self.x),
+            ("z", self.z),
+            ("radius", self.radius),
+            ("height", self.height),
+            ("falloff", self.falloff),
+            ("rotation", self.rotation),
+        ] {
+            if !value.is_finite() {
+                return invalid(format!("Stamp {name} must be finite, got {value}"));
+            }
+        }
+        if self.radius <= 0.0 {
+            return invalid(format!(
+                "Stamp radius must be positive, got {}",
+                self.radius
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.falloff) {
+            return invalid(format!(
+                "Stamp falloff must be between 0.0 and 1.0, got {}",
+                self.falloff
+            ));
+        }
+        Ok(())
+    }
+
+    /// Blend the stamp into `terrain`
+    pub fn apply(&self, terrain: &mut TerrainData) -> MinionResult<()> {
+        self.validate()?;
+
+        let reach = if self.source.is_square() {
+            self.radius * std::f32::consts::SQRT_2
+        } else {
+            self.radius
+        };
+        // One spare sample each side so rounding never clips the footprint
+        let (low_x, low_z) = world_to_grid(terrain, self.x - reach, self.z - reach);
+        let (high_x, high_z) = world_to_grid(terrain, self.x + reach, self.z + reach);
+        let cells = |low: f32, high: f32, size: u32| {
+            ((low.floor() - 1.0).max(0.0) as u32)..((high.ceil() + 1.0).max(0.0) as u32).min(size)
+        };
+        let (sin, cos) = self.rotation.to_radians().sin_cos();
+
+        let blend = if self.height < 0.0 && self.blend == BlendMode::Max {
+            BlendMode::Add
+        } else {
+            self.blend
+        };
+        // Absolute modes measure the profile from the ground at the center
+        let level = match blend {
+            BlendMode::Max | BlendMode::Min | BlendMode::Replace => self.ground(terrain),
+            BlendMode::Add | BlendMode::Subtract | BlendMode::Multiply => 0.0,
+        };
+
+        for grid_z in cells(low_z, high_z, terrain.height) {
+            for grid_x in cells(low_x, high_x, terrain.width) {
+                let world = grid_to_world(terrain, grid_x as f32, grid_z as f32);
+                let dx = world.x - self.x;
+                let dz = world.z - self.z;
+
+                let (extent, layer) = match &self.source {
+                    StampSource::Shape(shape) => {
+                        let distance = (dx.powi(2) + dz.powi(2)).sqrt() / self.radius;
+                        (distance, shape.profile(distance))
+                    }
+                    square => {
+                        let u = (dx * cos + dz * sin) / self.radius;
+                        let v = (dz * cos - dx * sin) / self.radius;
+                        let layer = match square {
+                            StampSource::Image(image) => image.sample(u, v),
+                            _ => ((u + 1.0) / 2.0).clamp(0.0, 1.0),
+                        };
+                        (u.abs().max(v.abs()), layer)
+                    }
+                };
+                if extent > 1.0 {
+                    continue;
+                }
+
+                let base = &mut terrain.heights[(grid_z * terrain.width + grid_x) as usize];
+                let blended = blend.blend(*base, level + layer * self.height);
+                let weight = if self.falloff > 0.0 {
+                    smoothstep((1.0 - extent) / self.falloff)
+                } else {
+                    1.0
+                };
+                *base = if weight < 1.0 {
+                    *base + (blended - *base) * weight
+                } else {
+                    blended
+                };
+            }
+        }
+        Ok(())
+    }
+
+    /// Terrain height under the center, from the nearest edge sample when off the map
+    fn ground(&self, terrain: &TerrainData) -> f32 {
+        get_height_at_world_interpolated(terrain, self.x, self.z).unwrap_or_else(|| {
+            let (grid_x, grid_z) = world_to_grid(terrain, self.x, self.z);
+            let x = grid_x.round().clamp(0.0, (terrain.width - 1) as f32) as u32;
+            let z = grid_z.round().clamp(0.0, (terrain.height - 1) as f32) as u32;
+            terrain.heights[(z * terrain.width + x) as usize]
+        })
+    }
+}
+
+/// Apply stamps in order
+pub fn apply_stamps(terrain: &mut TerrainData, stamps: &[TerrainStamp]) -> MinionResult<()> {
+    for stamp in stamps {
+        stamp.apply(terrain)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(terrain: &TerrainData, x: u32, z: u32) -> f32 {
+        terrain.heights[(z * terrain.width + x) as usize]
+    }
+
+    #[test]
+    fn test_parametric_stamps() {
+        // Cell (10, 10) sits at world (-0.5, -0.5)
+        let flat = TerrainData::create_flat(21, 21, 1.0, 2.0).unwrap();
+
+        let mut volcano = flat.clone();
+        TerrainStamp::new(StampSource::Shape(StampShape::Volcano), -0.5, -0.5, 10.0)
+            .with_height(8.0)
+            .with_falloff(0.0)
+            .apply(&mut volcano)
+            .unwrap();
+        assert_eq!(at(&volcano, 10, 10), 6.0); // Vent floor above the ground
+        assert!(at(&volcano, 12, 10) > at(&volcano, 10, 10));
+        assert_eq!(at(&volcano, 0, 0), 2.0);
+
+        let mut mesa = flat.clone();
+        TerrainStamp::new(StampSource::Shape(StampShape::Mesa), -0.5, -0.5, 10.0)
+            .with_height(6.0)
+            .apply(&mut mesa)
+            .unwrap();
+        assert_eq!(at(&mesa, 10, 10), 8.0);
+        assert_eq!(at(&mesa, 16, 10), 8.0);
+
+        // Arenas level a slope to the ground at their center, inside a wall
+        let heights = (0..21 * 21).map(|i| 5.0 + (i % 21) as f32 * 0.1).collect();
+        let mut arena = TerrainData::new(21, 21, heights, 1.0).unwrap();
+        TerrainStamp::new(StampSource::Shape(StampShape::Arena), -0.5, -0.5, 10.0)
+            .with_height(3.0)
+            .apply(&mut arena)
+            .unwrap();
+        assert_eq!(at(&arena, 10, 10), 6.0);
+        assert_eq!(at(&arena, 16, 10), 6.0);
+        assert!(at(&arena, 18, 10) > 6.8 + 1.0);
+        assert_eq!(at(&arena, 20, 10), 7.0);
+    }
+
+    #[test]
+    fn test_negative_height_digs() {
+        let mut terrain = TerrainData::create_flat(21, 21, 1.0, 2.0).unwrap();
+        let pit = TerrainStamp::new(StampSource::Shape(StampShape::Cone), -0.5, -0.5, 6.0)
+            .with_height(-3.0)
+            .with_falloff(0.0);
+        assert_eq!(pit.blend, BlendMode::Max);
+        pit.apply(&mut terrain).unwrap();
+
+        assert_eq!(at(&terrain, 10, 10), -1.0);
+        assert_eq!(at(&terrain, 0, 0), 2.0);
+    }
+
+    #[test]
+    fn test_falloff_fades_into_ground() {
+        let mut terrain = TerrainData::create_flat(21, 21, 1.0, 0.0).unwrap();
+        TerrainStamp::new(StampSource::Shape(StampShape::Plateau), -0.5, -0.5, 10.0)
+            .with_height(4.0)
+            .with_blend(BlendMode::Add)
+            .with_falloff(0.5)
+            .apply(&mut terrain)
+            .unwrap();
+
+        assert_eq!(at(&terrain, 10, 10), 4.0);
+        let edge = at(&terrain, 19, 10);
+        assert!(edge > 0.0 && edge < 4.0 * StampShape::Plateau.profile(0.9));
+        assert_eq!(at(&terrain, 20, 10), 0.0);
+    }
+
+    #[test]
+    fn test_ramps_and_images_rotate() {
+        let flat = TerrainData::create_flat(21, 21, 1.0, 0.0).unwrap();
+        let ramp = |rotation: f32| {
+            let mut terrain = flat.clone();
+            TerrainStamp::new(StampSource::Ramp, -0.5, -0.5, 8.0)
+                .with_height(4.0)
+                .with_falloff(0.0)
+                .with_rotation(rotation)
+                .apply(&mut terrain)
+                .unwrap();
+            terrain
+        };
+
+        let along_x = ramp(0.0);
+        assert_eq!(at(&along_x, 10, 10), 2.0);
+        assert_eq!(at(&along_x, 18, 10), 4.0);
+        assert_eq!(at(&along_x, 2, 10), 0.0);
+        assert_eq!(at(&along_x, 10, 18), 2.0);
+
+        let along_z = ramp(90.0);
+        assert!((at(&along_z, 10, 18) - 4.0).abs() < 1e-4);
+        assert!((at(&along_z, 18, 10) - 2.0).abs() < 1e-4);
+
+        // A two pixel gradient image is the same incline
+        let image = StampImage::new(2, 1, vec![0.0, 1.0]).unwrap();
+        let mut stamped = flat.clone();
+        TerrainStamp::new(StampSource::Image(image), -0.5, -0.5, 8.0)
+            .with_height(4.0)
+            .with_falloff(0.0)
+            .apply(&mut stamped)
+            .unwrap();
+        assert_eq!(stamped.heights, along_x.heights);
+    }
+
+    #[test]
+    fn test_invalid_stamps_rejected() {
+        let mut terrain = TerrainData::create_flat(8, 8, 1.0, 0.0).unwrap();
+        let stamp = TerrainStamp::new(StampSource::Ramp, 0.0, 0.0, 0.0);
+        assert!(stamp.apply(&mut terrain).is_err());
+        let stamp = TerrainStamp::new(StampSource::Ramp, 0.0, 0.0, 2.0).with_falloff(1.5);
+        assert!(stamp.apply(&mut terrain).is_err());
+        let stamp = TerrainStamp::new(StampSource::Ramp, 0.0, 0.0, 2.0).with_falloff(f32::NAN);
+        assert!(stamp.apply(&mut terrain).is_err());
+        let stamp = TerrainStamp::new(StampSource::Ramp, 0.0, 0.0, 2.0).with_height(f32::INFINITY);
+        assert!(stamp.apply(&mut terrain).is_err());
+        let stamp = TerrainStamp::new(StampSource::Ramp, 0.0, 0.0, 2.0).with_rotation(f32::NAN);
+        assert!(stamp.apply(&mut terrain).is_err());
+        assert_eq!(terrain.heights, vec![0.0; 64]);
+        assert!(StampImage::new(2, 2, vec![0.0; 3]).is_err());
+        assert!(StampImage::new(u32::MAX, 2, Vec::new()).is_err());
+        assert!(StampImage::load(Path::new("does/not/exist.png")).is_err());
+    }
+}